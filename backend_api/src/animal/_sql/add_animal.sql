@@ -1,2 +0,0 @@
-INSERT INTO animal (species, description)
-VALUES (:species, :description)
\ No newline at end of file
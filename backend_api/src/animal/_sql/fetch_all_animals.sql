@@ -1,2 +0,0 @@
-SELECT id, species, description
-FROM animal
\ No newline at end of file
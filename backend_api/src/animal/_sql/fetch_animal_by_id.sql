@@ -1,3 +0,0 @@
-SELECT id, species, description
-FROM animal
-WHERE id = :id;
\ No newline at end of file
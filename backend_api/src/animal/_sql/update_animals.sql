@@ -1,4 +0,0 @@
-UPDATE animal
-SET species=:species,
-    description=:description
-WHERE id = :id;
\ No newline at end of file
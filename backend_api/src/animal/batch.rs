@@ -0,0 +1,162 @@
+use crate::animal::object::{
+    AnimalAddUpdateObject, AnimalBatchMode, AnimalBatchObject, AnimalBatchOperationKind,
+    AnimalBatchOperationObject, AnimalBatchOperationResultObject, AnimalBatchResultObject,
+    AnimalErrorObject,
+};
+use crate::animal::repository::{AnimalRepository, AnimalRepositoryError, AnimalTransaction};
+use crate::common::context::{Context, ContextError, FromContext};
+use error_stack::Report;
+use poem::http::StatusCode;
+use poem::i18n::Locale;
+use shared::validation::rules::AnimalRules;
+use std::collections::HashSet;
+
+type OperationResult = Result<(StatusCode, i64), (StatusCode, Option<AnimalErrorObject>)>;
+
+fn to_result_object(result: OperationResult) -> AnimalBatchOperationResultObject {
+    match result {
+        Ok((status, id)) => AnimalBatchOperationResultObject {
+            status: status.as_u16(),
+            id: Some(id),
+            error: None,
+        },
+        Err((status, error)) => AnimalBatchOperationResultObject {
+            status: status.as_u16(),
+            id: None,
+            error,
+        },
+    }
+}
+
+/// Most operations `POST /animal/batch` takes, `poem.limits.max_batch_operations`.
+pub struct MaxBatchOperations(pub usize);
+
+impl FromContext for MaxBatchOperations {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let config = ctx.config.upgrade().ok_or(ContextError::ConfigError)?;
+        Ok(Self(config.poem.limits.max_batch_operations))
+    }
+}
+
+/// Runs the operations of `POST /animal/batch` in one transaction of the animal repository.
+pub struct AnimalBatch<'a> {
+    pub rules: &'a AnimalRules,
+    /// Taxa the operations refer to that do not exist, looked up before the transaction as
+    /// the taxa may share its database lock.
+    pub unknown_taxa: HashSet<i64>,
+    pub locale: &'a Locale,
+}
+
+impl AnimalBatch<'_> {
+    fn validate(
+        &self,
+        animal: &AnimalAddUpdateObject,
+        transaction: &dyn AnimalTransaction,
+        id: Option<i64>,
+    ) -> Result<(), (StatusCode, Option<AnimalErrorObject>)> {
+        animal
+            .to_validate_unique_in(self.rules, transaction, id)
+            .map_err(|animal_error| {
+                (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Some((animal_error, self.locale).into()),
+                )
+            })?;
+        if animal
+            .taxon_id
+            .is_some_and(|taxon_id| self.unknown_taxa.contains(&taxon_id))
+        {
+            return Err((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Some(AnimalErrorObject::unknown_taxon(self.locale)),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Status and error of a failed write, `otherwise` unless the species was taken.
+    fn write_error(
+        &self,
+        err: Report<AnimalRepositoryError>,
+        otherwise: StatusCode,
+    ) -> (StatusCode, Option<AnimalErrorObject>) {
+        match err.current_context() {
+            AnimalRepositoryError::SpeciesTakenError => (
+                StatusCode::CONFLICT,
+                Some(AnimalErrorObject::species_taken(self.locale)),
+            ),
+            _ => (otherwise, None),
+        }
+    }
+
+    /// Answers as `POST /animal/add`, `PATCH /animal/update/:id` and a delete would.
+    fn write(
+        &self,
+        operation: &AnimalBatchOperationObject,
+        transaction: &dyn AnimalTransaction,
+    ) -> OperationResult {
+        match (operation.op, operation.id, &operation.animal) {
+            (AnimalBatchOperationKind::Create, None, Some(animal)) => {
+                self.validate(animal, transaction, None)?;
+                transaction
+                    .add_animal(animal)
+                    .map(|id| (StatusCode::CREATED, id))
+                    .map_err(|err| self.write_error(err, StatusCode::BAD_REQUEST))
+            }
+            (AnimalBatchOperationKind::Update, Some(id), Some(animal)) => {
+                self.validate(animal, transaction, Some(id as i64))?;
+                transaction
+                    .update_animal(animal, id as i64)
+                    .map(|_| (StatusCode::OK, id as i64))
+                    .map_err(|err| self.write_error(err, StatusCode::NOT_FOUND))
+            }
+            (AnimalBatchOperationKind::Delete, Some(id), None) => transaction
+                .delete_animal(id as i64)
+                .map(|_| (StatusCode::NO_CONTENT, id as i64))
+                .map_err(|err| match err.current_context() {
+                    AnimalRepositoryError::NotFoundError => (StatusCode::NOT_FOUND, None),
+                    _ => (StatusCode::INTERNAL_SERVER_ERROR, None),
+                }),
+            _ => Err((StatusCode::BAD_REQUEST, None)),
+        }
+    }
+
+    /// Run the operations in order. An atomic batch stops at the first failure and rolls
+    /// back, the operations after it are answered with 424.
+    pub fn run(
+        &self,
+        batch: &AnimalBatchObject,
+        animal_repository: &dyn AnimalRepository,
+    ) -> Result<AnimalBatchResultObject, Report<AnimalRepositoryError>> {
+        let mut results = Vec::new();
+        let outcome = animal_repository.transaction(&mut |transaction| {
+            results.clear();
+            for operation in &batch.operations {
+                let result = self.write(operation, transaction);
+                let failed = result.is_err();
+                results.push(to_result_object(result));
+                if failed && batch.mode == AnimalBatchMode::Atomic {
+                    return Err(AnimalRepositoryError::TransactionAbortedError.into());
+                }
+            }
+            Ok(())
+        });
+
+        let committed = match outcome {
+            Ok(()) => true,
+            Err(err)
+                if matches!(
+                    err.current_context(),
+                    AnimalRepositoryError::TransactionAbortedError
+                ) =>
+            {
+                false
+            }
+            Err(err) => return Err(err),
+        };
+        results.resize_with(batch.operations.len(), || {
+            to_result_object(Err((StatusCode::FAILED_DEPENDENCY, None)))
+        });
+        Ok(AnimalBatchResultObject { committed, results })
+    }
+}
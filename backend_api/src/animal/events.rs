@@ -0,0 +1,234 @@
+use crate::animal::object::{AnimalAddUpdateObject, AnimalObject};
+use crate::animal::repository::{
+    AnimalRepository, AnimalRepositoryError, AnimalTransaction, DynAnimalRepository,
+    TransactionWrites,
+};
+use crate::common::context::{Context, ContextError, FromContext};
+use crate::tenant::pool::Tenant;
+use error_stack::Report;
+use std::cell::RefCell;
+use tokio::sync::{OnceCell, broadcast};
+
+/// Events not yet received when this many more are sent are lost to slow listeners, writers
+/// never wait on them.
+const ANIMAL_EVENTS_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimalEventKind {
+    Added,
+    Updated,
+    Imported,
+    Deleted,
+}
+
+/// A write to the animals of one database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimalEvent {
+    pub kind: AnimalEventKind,
+    pub animal_id: i64,
+    pub scope: EventScope,
+}
+
+/// The database events come from: the slug of the tenant in multi-tenant mode, `None` for the
+/// main database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventScope(pub Option<String>);
+
+impl FromContext for EventScope {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let config = ctx.config.upgrade().ok_or(ContextError::ConfigError)?;
+        if !config.tenant.enabled {
+            return Ok(Self(None));
+        }
+        let Tenant(tenant) = Tenant::from_context(ctx).await?;
+        Ok(Self(Some(tenant.slug)))
+    }
+}
+
+/// Broadcast of the animal writes, which GraphQL subscriptions listen to.
+#[derive(Clone)]
+pub struct AnimalEvents(broadcast::Sender<AnimalEvent>);
+
+impl Default for AnimalEvents {
+    fn default() -> Self {
+        Self(broadcast::channel(ANIMAL_EVENTS_CAPACITY).0)
+    }
+}
+
+impl AnimalEvents {
+    /// Send the event to the current listeners, if any.
+    pub fn publish(&self, event: AnimalEvent) {
+        let _ = self.0.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<AnimalEvent> {
+        self.0.subscribe()
+    }
+}
+
+static ANIMAL_EVENTS_CACHE: OnceCell<AnimalEvents> = OnceCell::const_new();
+
+impl FromContext for AnimalEvents {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        if let Some(events) = ctx.overridden::<Self>() {
+            return Ok(events);
+        }
+        Ok(ANIMAL_EVENTS_CACHE
+            .get_or_init(|| async { AnimalEvents::default() })
+            .await
+            .clone())
+    }
+}
+
+/// Publishes the successful writes of the repository it wraps.
+pub struct ObservedAnimalRepository {
+    inner: DynAnimalRepository,
+    events: AnimalEvents,
+    scope: EventScope,
+}
+
+impl ObservedAnimalRepository {
+    pub fn new(inner: DynAnimalRepository, events: AnimalEvents, scope: EventScope) -> Self {
+        Self {
+            inner,
+            events,
+            scope,
+        }
+    }
+
+    fn publish(&self, kind: AnimalEventKind, animal_id: i64) {
+        self.events.publish(AnimalEvent {
+            kind,
+            animal_id,
+            scope: self.scope.clone(),
+        });
+    }
+}
+
+impl AnimalRepository for ObservedAnimalRepository {
+    fn add_animal(
+        &self,
+        object: &AnimalAddUpdateObject,
+    ) -> Result<i64, Report<AnimalRepositoryError>> {
+        let id = self.inner.add_animal(object)?;
+        self.publish(AnimalEventKind::Added, id);
+        Ok(id)
+    }
+
+    fn fetch_all_animals(&self) -> Result<Box<[AnimalObject]>, Report<AnimalRepositoryError>> {
+        self.inner.fetch_all_animals()
+    }
+
+    fn fetch_animal_by_id(&self, id: i64) -> Result<AnimalObject, Report<AnimalRepositoryError>> {
+        self.inner.fetch_animal_by_id(id)
+    }
+
+    fn fetch_animals_by_taxa(
+        &self,
+        taxon_ids: &[i64],
+    ) -> Result<Box<[AnimalObject]>, Report<AnimalRepositoryError>> {
+        self.inner.fetch_animals_by_taxa(taxon_ids)
+    }
+
+    fn update_animal(
+        &self,
+        object: &AnimalAddUpdateObject,
+        id: i64,
+    ) -> Result<(), Report<AnimalRepositoryError>> {
+        self.inner.update_animal(object, id)?;
+        self.publish(AnimalEventKind::Updated, id);
+        Ok(())
+    }
+
+    fn import_animal(&self, object: &AnimalObject) -> Result<(), Report<AnimalRepositoryError>> {
+        self.inner.import_animal(object)?;
+        self.publish(AnimalEventKind::Imported, object.id);
+        Ok(())
+    }
+
+    fn is_species_taken(
+        &self,
+        species: &str,
+        except_id: Option<i64>,
+    ) -> Result<bool, Report<AnimalRepositoryError>> {
+        self.inner.is_species_taken(species, except_id)
+    }
+
+    fn delete_animal(&self, id: i64) -> Result<(), Report<AnimalRepositoryError>> {
+        self.inner.delete_animal(id)?;
+        self.publish(AnimalEventKind::Deleted, id);
+        Ok(())
+    }
+
+    /// The writes of the transaction are published once it is committed.
+    fn transaction(
+        &self,
+        writes: &mut TransactionWrites,
+    ) -> Result<(), Report<AnimalRepositoryError>> {
+        let mut written = Vec::new();
+        self.inner.transaction(&mut |transaction| {
+            let observed = ObservedAnimalTransaction {
+                inner: transaction,
+                written: RefCell::default(),
+            };
+            let result = writes(&observed);
+            written = observed.written.into_inner();
+            result
+        })?;
+        for (kind, animal_id) in written {
+            self.publish(kind, animal_id);
+        }
+        Ok(())
+    }
+}
+
+/// Records the writes of a transaction, to be published if it is committed.
+struct ObservedAnimalTransaction<'a> {
+    inner: &'a dyn AnimalTransaction,
+    written: RefCell<Vec<(AnimalEventKind, i64)>>,
+}
+
+impl ObservedAnimalTransaction<'_> {
+    fn record(&self, kind: AnimalEventKind, animal_id: i64) {
+        self.written.borrow_mut().push((kind, animal_id));
+    }
+}
+
+impl AnimalTransaction for ObservedAnimalTransaction<'_> {
+    fn add_animal(
+        &self,
+        object: &AnimalAddUpdateObject,
+    ) -> Result<i64, Report<AnimalRepositoryError>> {
+        let id = self.inner.add_animal(object)?;
+        self.record(AnimalEventKind::Added, id);
+        Ok(id)
+    }
+
+    fn fetch_animal_by_id(&self, id: i64) -> Result<AnimalObject, Report<AnimalRepositoryError>> {
+        self.inner.fetch_animal_by_id(id)
+    }
+
+    fn update_animal(
+        &self,
+        object: &AnimalAddUpdateObject,
+        id: i64,
+    ) -> Result<(), Report<AnimalRepositoryError>> {
+        self.inner.update_animal(object, id)?;
+        self.record(AnimalEventKind::Updated, id);
+        Ok(())
+    }
+
+    fn delete_animal(&self, id: i64) -> Result<(), Report<AnimalRepositoryError>> {
+        self.inner.delete_animal(id)?;
+        self.record(AnimalEventKind::Deleted, id);
+        Ok(())
+    }
+
+    fn is_species_taken(
+        &self,
+        species: &str,
+        except_id: Option<i64>,
+    ) -> Result<bool, Report<AnimalRepositoryError>> {
+        self.inner.is_species_taken(species, except_id)
+    }
+}
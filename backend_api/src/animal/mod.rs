@@ -1,51 +1,117 @@
+pub mod batch;
+pub mod events;
 pub mod object;
+pub mod query;
 pub mod repository;
 pub mod response;
+#[cfg(test)]
+mod tests;
+pub mod v2;
 
 use crate::ApiTag;
-use crate::animal::object::AnimalAddUpdateObject;
-use crate::animal::repository::AnimalRepository;
+use crate::animal::batch::{AnimalBatch, MaxBatchOperations};
+use crate::animal::object::{
+    AnimalAddUpdateObject, AnimalBatchObject, AnimalBatchOperationKind, AnimalErrorObject,
+    ConservationStatus,
+};
+use crate::animal::query::{AnimalQuery, AnimalQueryError};
+use crate::animal::repository::{AnimalRepositoryError, DynAnimalRepository};
 use crate::animal::response::{
-    AddAnimalResponse, FetchAllAnimalsResponse, FetchAnimalByIdResponse, UpdateAnimalResponse,
+    AddAnimalResponse, AnimalBatchResponse, FetchAllAnimalsResponse, FetchAnimalByIdResponse,
+    UpdateAnimalResponse,
 };
 use crate::common::context::Dep;
+use crate::common::object::Message;
 use crate::common::results::unified;
+use crate::geo::repository::GeoRepository;
+use crate::photo::repository::PhotoRepository;
+use crate::photo::sweeper::PhotoSweeper;
+use crate::tag::object::TagMatch;
+use crate::tag::repository::TagRepository;
+use crate::taxonomy::repository::TaxonRepository;
+use crate::translation::localizer::AnimalLocalizer;
 use poem::i18n::Locale;
 use poem_openapi::OpenApi;
-use poem_openapi::param::Path;
+use poem_openapi::param::{Header, Path, Query};
 use poem_openapi::payload::Json;
+use shared::validation::rules::AnimalRules;
+use tracing::warn;
 
 pub struct AnimalApi;
 
 #[OpenApi(prefix_path = "/animal", tag = "ApiTag::Animal")]
 impl AnimalApi {
-    /// Fetch All Animals
+    /// Fetch All Animals in the language that best matches `Accept-Language`, optionally only
+    /// those of the given statuses, carrying the given tags or found in an area
     #[oai(path = "/", method = "get")]
+    #[allow(clippy::too_many_arguments)]
     async fn index(
         &self,
-        Dep(animal_repository): Dep<AnimalRepository>,
+        #[oai(name = "conservation_status", default)] Query(statuses): Query<
+            Vec<ConservationStatus>,
+        >,
+        #[oai(name = "tag", default)] Query(tags): Query<Vec<String>>,
+        #[oai(name = "tag_match")] Query(tag_match): Query<Option<TagMatch>>,
+        /// `west,south,east,north`, animals with a habitat or a sighting in the box
+        #[oai(name = "bbox")]
+        Query(bbox): Query<Option<String>>,
+        /// `longitude,latitude`, animals whose habitat covers the point
+        #[oai(name = "point")]
+        Query(point): Query<Option<String>>,
+        #[oai(name = "Accept-Language")] Header(accept_language): Header<Option<String>>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(tag_repository): Dep<TagRepository>,
+        Dep(geo_repository): Dep<GeoRepository>,
+        Dep(localizer): Dep<AnimalLocalizer>,
+        locale: Locale,
     ) -> FetchAllAnimalsResponse {
         unified(async {
-            animal_repository
-                .fetch_all_animals()
-                .map(|animal| FetchAllAnimalsResponse::Ok(Json(animal.to_vec())))
-                .map_err(|_| FetchAllAnimalsResponse::InternalServerError)
+            let mut animals = AnimalQuery {
+                statuses,
+                tags,
+                tag_match,
+                bbox,
+                point,
+            }
+            .animals(
+                animal_repository.as_ref(),
+                &tag_repository,
+                &geo_repository,
+                &locale,
+            )
+            .map_err(|err| match err {
+                AnimalQueryError::GeoQuery(geo_error) => {
+                    FetchAllAnimalsResponse::BadRequest(Json(geo_error))
+                }
+                AnimalQueryError::Repository => FetchAllAnimalsResponse::InternalServerError,
+            })?;
+            let content_language = localizer
+                .localize(&mut animals, accept_language.as_deref())
+                .map_err(|_| FetchAllAnimalsResponse::InternalServerError)?;
+
+            Ok(FetchAllAnimalsResponse::Ok(Json(animals), content_language))
         })
         .await
     }
 
-    /// Fetch Animal By ID
+    /// Fetch Animal By ID, in the language that best matches `Accept-Language`
     #[oai(path = "/fetch/:id", method = "get")]
     async fn fetch_by_id(
         &self,
         Path(id): Path<u64>,
-        Dep(animal_repository): Dep<AnimalRepository>,
+        #[oai(name = "Accept-Language")] Header(accept_language): Header<Option<String>>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(localizer): Dep<AnimalLocalizer>,
     ) -> FetchAnimalByIdResponse {
         unified(async {
-            animal_repository
+            let mut animal = [animal_repository
                 .fetch_animal_by_id(id as i64)
-                .map(|animal| FetchAnimalByIdResponse::Ok(Json(animal)))
-                .map_err(|_| FetchAnimalByIdResponse::NotFound)
+                .map_err(|_| FetchAnimalByIdResponse::NotFound)?];
+            let content_language = localizer
+                .localize(&mut animal, accept_language.as_deref())
+                .map_err(|_| FetchAnimalByIdResponse::InternalServerError)?;
+            let [animal] = animal;
+            Ok(FetchAnimalByIdResponse::Ok(Json(animal), content_language))
         })
         .await
     }
@@ -55,17 +121,34 @@ impl AnimalApi {
     async fn add(
         &self,
         Json(animal): Json<AnimalAddUpdateObject>,
-        Dep(animal_repository): Dep<AnimalRepository>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(taxon_repository): Dep<TaxonRepository>,
+        Dep(rules): Dep<AnimalRules>,
         locale: Locale,
     ) -> AddAnimalResponse {
         unified(async {
-            animal.to_validate().map_err(|animal_err| {
-                AddAnimalResponse::UnprocessableEntity(Json((animal_err, &locale).into()))
-            })?;
+            animal
+                .to_validate_unique(&rules, animal_repository.as_ref(), None)
+                .await
+                .map_err(|animal_err| {
+                    AddAnimalResponse::UnprocessableEntity(Json((animal_err, &locale).into()))
+                })?;
+            if let Some(taxon_id) = animal.taxon_id {
+                taxon_repository.fetch_taxon_by_id(taxon_id).map_err(|_| {
+                    AddAnimalResponse::UnprocessableEntity(Json(AnimalErrorObject::unknown_taxon(
+                        &locale,
+                    )))
+                })?;
+            }
             animal_repository
                 .add_animal(&animal)
                 .map(|_| AddAnimalResponse::Created)
-                .map_err(|_| AddAnimalResponse::BadRequest)
+                .map_err(|err| match err.current_context() {
+                    AnimalRepositoryError::SpeciesTakenError => {
+                        AddAnimalResponse::Conflict(Json(AnimalErrorObject::species_taken(&locale)))
+                    }
+                    _ => AddAnimalResponse::BadRequest,
+                })
         })
         .await
     }
@@ -76,17 +159,94 @@ impl AnimalApi {
         &self,
         Path(id): Path<u64>,
         Json(animal): Json<AnimalAddUpdateObject>,
-        Dep(animal_repository): Dep<AnimalRepository>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(taxon_repository): Dep<TaxonRepository>,
+        Dep(rules): Dep<AnimalRules>,
         locale: Locale,
     ) -> UpdateAnimalResponse {
         unified(async {
-            animal.to_validate().map_err(|animal_error| {
-                UpdateAnimalResponse::UnprocessableEntity(Json((animal_error, &locale).into()))
-            })?;
+            animal
+                .to_validate_unique(&rules, animal_repository.as_ref(), Some(id as i64))
+                .await
+                .map_err(|animal_error| {
+                    UpdateAnimalResponse::UnprocessableEntity(Json((animal_error, &locale).into()))
+                })?;
+            if let Some(taxon_id) = animal.taxon_id {
+                taxon_repository.fetch_taxon_by_id(taxon_id).map_err(|_| {
+                    UpdateAnimalResponse::UnprocessableEntity(Json(
+                        AnimalErrorObject::unknown_taxon(&locale),
+                    ))
+                })?;
+            }
             animal_repository
                 .update_animal(&animal, id as i64)
                 .map(|_| UpdateAnimalResponse::Ok)
-                .map_err(|_| UpdateAnimalResponse::NotFound)
+                .map_err(|err| match err.current_context() {
+                    AnimalRepositoryError::SpeciesTakenError => UpdateAnimalResponse::Conflict(
+                        Json(AnimalErrorObject::species_taken(&locale)),
+                    ),
+                    _ => UpdateAnimalResponse::NotFound,
+                })
+        })
+        .await
+    }
+
+    /// Create, update and delete Animals in one transaction, all or nothing in `atomic` mode,
+    /// keeping the operations that succeed in `best_effort` mode
+    #[oai(path = "/batch", method = "post")]
+    #[allow(clippy::too_many_arguments)]
+    async fn batch(
+        &self,
+        Json(batch): Json<AnimalBatchObject>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(taxon_repository): Dep<TaxonRepository>,
+        Dep(photo_repository): Dep<PhotoRepository>,
+        Dep(photo_sweeper): Dep<PhotoSweeper>,
+        Dep(rules): Dep<AnimalRules>,
+        Dep(max_operations): Dep<MaxBatchOperations>,
+        locale: Locale,
+    ) -> AnimalBatchResponse {
+        unified(async {
+            let MaxBatchOperations(max_operations) = max_operations;
+            if batch.operations.len() > max_operations {
+                return Err(AnimalBatchResponse::PayloadTooLarge(Json(Message {
+                    message: format!("A batch takes at most {} operations", max_operations),
+                })));
+            }
+            let mut file_keys = Vec::new();
+            for operation in &batch.operations {
+                if let (AnimalBatchOperationKind::Delete, Some(id)) = (operation.op, operation.id) {
+                    file_keys.extend(
+                        photo_repository
+                            .fetch_file_keys_by_animal(id as i64)
+                            .map_err(|_| AnimalBatchResponse::InternalServerError)?,
+                    );
+                }
+            }
+            let unknown_taxa = batch
+                .operations
+                .iter()
+                .filter_map(|operation| operation.animal.as_ref()?.taxon_id)
+                .filter(|taxon_id| taxon_repository.fetch_taxon_by_id(*taxon_id).is_err())
+                .collect();
+            let result = AnimalBatch {
+                rules: &rules,
+                unknown_taxa,
+                locale: &locale,
+            }
+            .run(&batch, animal_repository.as_ref())
+            .map_err(|_| AnimalBatchResponse::InternalServerError)?;
+
+            // The files are removed once the rows are gone for good, a failure only leaves
+            // them behind.
+            if result.committed && !file_keys.is_empty() {
+                let swept =
+                    tokio::task::spawn_blocking(move || photo_sweeper.sweep(&file_keys)).await;
+                if let Ok(Err(err)) = swept {
+                    warn!("Photos of deleted animals left in storage: {err:?}");
+                }
+            }
+            Ok(AnimalBatchResponse::Ok(Json(result)))
         })
         .await
     }
@@ -1,24 +1,178 @@
-use crate::common::locale::LocaleForStore;
+use crate::animal::repository::{AnimalRepository, AnimalTransaction};
+use crate::common::locale::LocaleForErrors;
+use crate::common::object::{ValidationErrorObject, field_messages};
+use async_graphql::Enum as GraphQLEnum;
 use poem::i18n::Locale;
-use poem_openapi::Object;
+use poem_openapi::{Enum, Object};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use serde::{Deserialize, Serialize};
+use shared::i18n::MessageId;
 use shared::validation::models::animal::{AnimalValidated, AnimalValidationError};
+use shared::validation::rules::AnimalRules;
+use shared::validation::types::conservation_status;
+use shared::validation::types::species::SpeciesError;
 
-#[derive(Debug, Object, Clone)]
+/// IUCN Red List category, by its two letter code.
+#[derive(
+    Debug, Enum, GraphQLEnum, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize,
+)]
+pub enum ConservationStatus {
+    #[oai(rename = "LC")]
+    #[serde(rename = "LC")]
+    #[graphql(name = "LC")]
+    LeastConcern,
+    #[oai(rename = "NT")]
+    #[serde(rename = "NT")]
+    #[graphql(name = "NT")]
+    NearThreatened,
+    #[oai(rename = "VU")]
+    #[serde(rename = "VU")]
+    #[graphql(name = "VU")]
+    Vulnerable,
+    #[oai(rename = "EN")]
+    #[serde(rename = "EN")]
+    #[graphql(name = "EN")]
+    Endangered,
+    #[oai(rename = "CR")]
+    #[serde(rename = "CR")]
+    #[graphql(name = "CR")]
+    CriticallyEndangered,
+    #[oai(rename = "EW")]
+    #[serde(rename = "EW")]
+    #[graphql(name = "EW")]
+    ExtinctInTheWild,
+    #[oai(rename = "EX")]
+    #[serde(rename = "EX")]
+    #[graphql(name = "EX")]
+    Extinct,
+    #[oai(rename = "DD")]
+    #[serde(rename = "DD")]
+    #[graphql(name = "DD")]
+    DataDeficient,
+    #[default]
+    #[oai(rename = "NE")]
+    #[serde(rename = "NE")]
+    #[graphql(name = "NE")]
+    NotEvaluated,
+}
+
+impl From<conservation_status::ConservationStatus> for ConservationStatus {
+    fn from(status: conservation_status::ConservationStatus) -> Self {
+        use conservation_status::ConservationStatus as Shared;
+        match status {
+            Shared::LeastConcern => Self::LeastConcern,
+            Shared::NearThreatened => Self::NearThreatened,
+            Shared::Vulnerable => Self::Vulnerable,
+            Shared::Endangered => Self::Endangered,
+            Shared::CriticallyEndangered => Self::CriticallyEndangered,
+            Shared::ExtinctInTheWild => Self::ExtinctInTheWild,
+            Shared::Extinct => Self::Extinct,
+            Shared::DataDeficient => Self::DataDeficient,
+            Shared::NotEvaluated => Self::NotEvaluated,
+        }
+    }
+}
+
+impl From<ConservationStatus> for conservation_status::ConservationStatus {
+    fn from(status: ConservationStatus) -> Self {
+        match status {
+            ConservationStatus::LeastConcern => Self::LeastConcern,
+            ConservationStatus::NearThreatened => Self::NearThreatened,
+            ConservationStatus::Vulnerable => Self::Vulnerable,
+            ConservationStatus::Endangered => Self::Endangered,
+            ConservationStatus::CriticallyEndangered => Self::CriticallyEndangered,
+            ConservationStatus::ExtinctInTheWild => Self::ExtinctInTheWild,
+            ConservationStatus::Extinct => Self::Extinct,
+            ConservationStatus::DataDeficient => Self::DataDeficient,
+            ConservationStatus::NotEvaluated => Self::NotEvaluated,
+        }
+    }
+}
+
+impl ToSql for ConservationStatus {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(conservation_status::ConservationStatus::from(*self)
+            .code()
+            .into())
+    }
+}
+
+impl FromSql for ConservationStatus {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        conservation_status::ConservationStatus::parse(value.as_str()?)
+            .map(Self::from)
+            .map_err(|_| FromSqlError::InvalidType)
+    }
+}
+
+#[derive(Debug, Object, Clone, Serialize, Deserialize)]
 pub struct AnimalObject {
     pub id: i64,
     pub species: String,
     pub description: String,
+    pub taxon_id: Option<i64>,
+    #[serde(default)]
+    pub conservation_status: ConservationStatus,
 }
 
-#[derive(Debug, Object)]
+#[derive(Debug, Object, Deserialize)]
 pub struct AnimalAddUpdateObject {
     pub species: String,
     pub description: String,
+    /// Taxon the animal is classified under. Left out on update, the current one is kept.
+    pub taxon_id: Option<i64>,
+    /// Left out on add, the animal is not evaluated. Left out on update, the current one is kept.
+    pub conservation_status: Option<ConservationStatus>,
 }
 
 impl AnimalAddUpdateObject {
-    pub fn to_validate(&self) -> Result<AnimalValidated, AnimalValidationError> {
-        AnimalValidated::parse(self.species.clone(), self.description.clone())
+    pub fn to_validate(
+        &self,
+        rules: &AnimalRules,
+    ) -> Result<AnimalValidated, AnimalValidationError> {
+        AnimalValidated::parse_custom(self.species.clone(), self.description.clone(), rules)
+    }
+
+    /// Also checks no animal other than `id` goes by the species. A failed lookup lets it
+    /// through, the unique index still catches duplicates on write.
+    pub async fn to_validate_unique(
+        &self,
+        rules: &AnimalRules,
+        animal_repository: &dyn AnimalRepository,
+        id: Option<i64>,
+    ) -> Result<AnimalValidated, AnimalValidationError> {
+        AnimalValidated::parse_unique(
+            self.species.clone(),
+            self.description.clone(),
+            rules,
+            |species| {
+                let taken = animal_repository
+                    .is_species_taken(species.as_str(), id)
+                    .unwrap_or(false);
+                async move { taken }
+            },
+        )
+        .await
+    }
+
+    /// [`Self::to_validate_unique`] within a transaction, which sees the species written
+    /// earlier in it.
+    pub fn to_validate_unique_in(
+        &self,
+        rules: &AnimalRules,
+        transaction: &dyn AnimalTransaction,
+        id: Option<i64>,
+    ) -> Result<AnimalValidated, AnimalValidationError> {
+        AnimalValidated::parse_unique_blocking(
+            self.species.clone(),
+            self.description.clone(),
+            rules,
+            |species| {
+                transaction
+                    .is_species_taken(species.as_str(), id)
+                    .unwrap_or(false)
+            },
+        )
     }
 }
 
@@ -26,58 +180,110 @@ impl AnimalAddUpdateObject {
 pub struct AnimalErrorObject {
     pub species: Vec<String>,
     pub description: Vec<String>,
+    pub taxon_id: Vec<String>,
+    /// The same errors with their code and arguments.
+    pub errors: Vec<ValidationErrorObject>,
 }
 
-impl From<AnimalValidationError> for AnimalErrorObject {
-    fn from(value: AnimalValidationError) -> Self {
+impl AnimalErrorObject {
+    fn from_errors(errors: Vec<ValidationErrorObject>) -> Self {
         Self {
-            species: value
-                .species
-                .err()
-                .map(|v| {
-                    v.0.as_original_message()
-                        .iter()
-                        .map(|s| s.clone())
-                        .collect()
-                })
-                .unwrap_or_default(),
-            description: value
-                .description
-                .err()
-                .map(|v| {
-                    v.0.as_original_message()
-                        .iter()
-                        .map(|s| s.clone())
-                        .collect()
-                })
-                .unwrap_or_default(),
+            species: field_messages(&errors, "species"),
+            description: field_messages(&errors, "description"),
+            taxon_id: field_messages(&errors, "taxon_id"),
+            errors,
+        }
+    }
+
+    fn from_validation(animal: AnimalValidationError, locale: Option<&Locale>) -> Self {
+        let mut errors = Vec::new();
+        if let Err(error) = animal.species {
+            errors.extend(error.0.as_validation_errors("species", locale));
+        }
+        if let Err(error) = animal.description {
+            errors.extend(error.0.as_validation_errors("description", locale));
         }
+        Self::from_errors(errors)
+    }
+
+    pub fn species_taken(locale: &Locale) -> Self {
+        Self::from_errors(
+            SpeciesError::taken()
+                .0
+                .as_validation_errors("species", Some(locale)),
+        )
+    }
+
+    pub fn unknown_taxon(locale: &Locale) -> Self {
+        Self::from_errors(vec![ValidationErrorObject::from_message(
+            "taxon_id",
+            MessageId::ValidateTaxonUnknown,
+            locale,
+        )])
+    }
+}
+
+impl From<AnimalValidationError> for AnimalErrorObject {
+    fn from(animal: AnimalValidationError) -> Self {
+        Self::from_validation(animal, None)
     }
 }
 
 impl From<(AnimalValidationError, &Locale)> for AnimalErrorObject {
     fn from((animal, locale): (AnimalValidationError, &Locale)) -> Self {
-        Self {
-            species: animal
-                .species
-                .err()
-                .map(|v| {
-                    v.0.as_translated_message(locale)
-                        .iter()
-                        .map(|s| s.clone())
-                        .collect()
-                })
-                .unwrap_or_default(),
-            description: animal
-                .description
-                .err()
-                .map(|v| {
-                    v.0.as_translated_message(locale)
-                        .iter()
-                        .map(|s| s.clone())
-                        .collect()
-                })
-                .unwrap_or_default(),
-        }
+        Self::from_validation(animal, Some(locale))
     }
 }
+
+/// What a batch does when one of its operations fails.
+#[derive(Debug, Enum, Clone, Copy, PartialEq, Eq)]
+#[oai(rename_all = "snake_case")]
+pub enum AnimalBatchMode {
+    /// Roll back every operation and skip the rest.
+    Atomic,
+    /// Leave out the failed operation and carry on.
+    BestEffort,
+}
+
+#[derive(Debug, Enum, Clone, Copy, PartialEq, Eq)]
+#[oai(rename_all = "snake_case")]
+pub enum AnimalBatchOperationKind {
+    Create,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Object)]
+pub struct AnimalBatchOperationObject {
+    pub op: AnimalBatchOperationKind,
+    /// Animal to update or delete, left out on create.
+    pub id: Option<u64>,
+    /// Animal to create, or the new values of the animal to update. Left out on delete.
+    pub animal: Option<AnimalAddUpdateObject>,
+}
+
+#[derive(Debug, Object)]
+pub struct AnimalBatchObject {
+    pub mode: AnimalBatchMode,
+    /// Run in order, each seeing the writes of the ones before it, at most
+    /// `poem.limits.max_batch_operations` of them.
+    pub operations: Vec<AnimalBatchOperationObject>,
+}
+
+#[derive(Debug, Object)]
+pub struct AnimalBatchOperationResultObject {
+    /// Status the operation would have got as a request of its own, or 424 when it was
+    /// skipped after an earlier operation of an atomic batch failed.
+    pub status: u16,
+    /// Animal created, updated or deleted.
+    pub id: Option<i64>,
+    pub error: Option<AnimalErrorObject>,
+}
+
+#[derive(Debug, Object)]
+pub struct AnimalBatchResultObject {
+    /// Whether the writes were kept, false when an atomic batch was rolled back.
+    pub committed: bool,
+    /// One per operation, in the same order.
+    pub results: Vec<AnimalBatchOperationResultObject>,
+}
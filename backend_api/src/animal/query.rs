@@ -0,0 +1,71 @@
+use crate::animal::object::{AnimalObject, ConservationStatus};
+use crate::animal::repository::AnimalRepository;
+use crate::geo::object::{GeoQueryErrorObject, parse_geo_query};
+use crate::geo::repository::GeoRepository;
+use crate::tag::object::TagMatch;
+use crate::tag::repository::TagRepository;
+use poem::i18n::Locale;
+
+pub enum AnimalQueryError {
+    /// `bbox` or `point` does not parse.
+    GeoQuery(GeoQueryErrorObject),
+    Repository,
+}
+
+/// Filters of `GET /animal`, shared by every version of the API.
+pub struct AnimalQuery {
+    pub statuses: Vec<ConservationStatus>,
+    pub tags: Vec<String>,
+    pub tag_match: Option<TagMatch>,
+    pub bbox: Option<String>,
+    pub point: Option<String>,
+}
+
+impl AnimalQuery {
+    /// Animals of any of the statuses, carrying the tags and found in the area, in id order.
+    pub fn animals(
+        &self,
+        animal_repository: &dyn AnimalRepository,
+        tag_repository: &TagRepository,
+        geo_repository: &GeoRepository,
+        locale: &Locale,
+    ) -> Result<Vec<AnimalObject>, AnimalQueryError> {
+        let (bbox, point) = parse_geo_query(self.bbox.as_deref(), self.point.as_deref(), locale)
+            .map_err(AnimalQueryError::GeoQuery)?;
+        let animals = animal_repository
+            .fetch_all_animals()
+            .map_err(|_| AnimalQueryError::Repository)?;
+
+        let mut filters = Vec::new();
+        if !self.tags.is_empty() {
+            filters.push(
+                tag_repository
+                    .fetch_animal_ids_by_tags(&self.tags, self.tag_match.unwrap_or(TagMatch::All))
+                    .map_err(|_| AnimalQueryError::Repository)?,
+            );
+        }
+        if let Some(bbox) = bbox {
+            filters.push(
+                geo_repository
+                    .fetch_animal_ids_in_bbox(&bbox)
+                    .map_err(|_| AnimalQueryError::Repository)?,
+            );
+        }
+        if let Some(point) = point {
+            filters.push(
+                geo_repository
+                    .fetch_animal_ids_at(&point)
+                    .map_err(|_| AnimalQueryError::Repository)?,
+            );
+        }
+
+        Ok(animals
+            .iter()
+            .filter(|animal| {
+                self.statuses.is_empty() || self.statuses.contains(&animal.conservation_status)
+            })
+            .filter(|animal| filters.iter().all(|ids| ids.contains(&animal.id)))
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,2 @@
+INSERT INTO animal (species, description, taxon_id, conservation_status)
+VALUES (:species, :description, :taxon_id, COALESCE(:conservation_status, 'NE'))
\ No newline at end of file
@@ -0,0 +1,3 @@
+DELETE
+FROM animal
+WHERE id = :id;
\ No newline at end of file
@@ -0,0 +1,3 @@
+DELETE
+FROM animal_tag
+WHERE animal_id = :animal_id;
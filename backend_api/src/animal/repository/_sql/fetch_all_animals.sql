@@ -0,0 +1,2 @@
+SELECT id, species, description, taxon_id, conservation_status
+FROM animal
\ No newline at end of file
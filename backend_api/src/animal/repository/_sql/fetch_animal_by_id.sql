@@ -0,0 +1,3 @@
+SELECT id, species, description, taxon_id, conservation_status
+FROM animal
+WHERE id = :id;
\ No newline at end of file
@@ -0,0 +1,4 @@
+SELECT id, species, description, taxon_id, conservation_status
+FROM animal
+WHERE taxon_id IN (SELECT value FROM json_each(:taxon_ids))
+ORDER BY id
\ No newline at end of file
@@ -0,0 +1,4 @@
+SELECT EXISTS(SELECT 1
+              FROM animal
+              WHERE trim(species) = trim(:species) COLLATE NOCASE
+                AND id IS NOT :except_id) AS taken
\ No newline at end of file
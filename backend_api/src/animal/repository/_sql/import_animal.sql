@@ -0,0 +1,6 @@
+INSERT INTO animal (id, species, description, taxon_id, conservation_status)
+VALUES (:id, :species, :description, :taxon_id, :conservation_status)
+ON CONFLICT (id) DO UPDATE SET species             = excluded.species,
+                               description         = excluded.description,
+                               taxon_id            = excluded.taxon_id,
+                               conservation_status = excluded.conservation_status
\ No newline at end of file
@@ -0,0 +1,6 @@
+UPDATE animal
+SET species=:species,
+    description=:description,
+    taxon_id=COALESCE(:taxon_id, taxon_id),
+    conservation_status=COALESCE(:conservation_status, conservation_status)
+WHERE id = :id;
\ No newline at end of file
@@ -0,0 +1,427 @@
+//! Behaviour every `AnimalRepository` backend has to share, run against each backend on an
+//! empty store.
+
+use crate::animal::object::{AnimalAddUpdateObject, AnimalObject, ConservationStatus};
+use crate::animal::repository::{AnimalRepository, AnimalRepositoryError};
+use crate::common::db::{SqliteClient, TenantConnection};
+
+fn animal(species: &str) -> AnimalAddUpdateObject {
+    AnimalAddUpdateObject {
+        species: species.to_string(),
+        description: format!("A {}", species),
+        taxon_id: None,
+        conservation_status: None,
+    }
+}
+
+pub fn add_then_fetch(repository: &dyn AnimalRepository) {
+    let id = repository.add_animal(&animal("otter")).expect("add");
+    let fetched = repository.fetch_animal_by_id(id).expect("fetch");
+    assert_eq!(fetched.id, id);
+    assert_eq!(fetched.species, "otter");
+    assert_eq!(fetched.description, "A otter");
+}
+
+pub fn fetch_all_in_id_order(repository: &dyn AnimalRepository) {
+    assert!(
+        repository
+            .fetch_all_animals()
+            .expect("fetch all")
+            .is_empty()
+    );
+    let first = repository.add_animal(&animal("otter")).expect("add");
+    let second = repository.add_animal(&animal("beaver")).expect("add");
+    assert!(second > first);
+
+    let animals = repository.fetch_all_animals().expect("fetch all");
+    let ids: Vec<i64> = animals.iter().map(|animal| animal.id).collect();
+    assert_eq!(ids, vec![first, second]);
+}
+
+pub fn fetch_unknown_is_not_found(repository: &dyn AnimalRepository) {
+    let err = repository.fetch_animal_by_id(404).expect_err("unknown id");
+    assert!(matches!(
+        err.current_context(),
+        AnimalRepositoryError::NotFoundError
+    ));
+}
+
+pub fn update_changes_the_animal(repository: &dyn AnimalRepository) {
+    let id = repository.add_animal(&animal("otter")).expect("add");
+    repository
+        .update_animal(&animal("sea otter"), id)
+        .expect("update");
+    assert_eq!(
+        repository.fetch_animal_by_id(id).expect("fetch").species,
+        "sea otter"
+    );
+}
+
+pub fn update_keeps_the_taxon_when_left_out(repository: &dyn AnimalRepository) {
+    let id = repository
+        .add_animal(&AnimalAddUpdateObject {
+            taxon_id: Some(7),
+            ..animal("otter")
+        })
+        .expect("add");
+    repository
+        .update_animal(&animal("sea otter"), id)
+        .expect("update");
+    assert_eq!(
+        repository.fetch_animal_by_id(id).expect("fetch").taxon_id,
+        Some(7)
+    );
+}
+
+pub fn conservation_status_defaults_and_is_kept_when_left_out(repository: &dyn AnimalRepository) {
+    let id = repository.add_animal(&animal("otter")).expect("add");
+    assert_eq!(
+        repository
+            .fetch_animal_by_id(id)
+            .expect("fetch")
+            .conservation_status,
+        ConservationStatus::NotEvaluated
+    );
+
+    repository
+        .update_animal(
+            &AnimalAddUpdateObject {
+                conservation_status: Some(ConservationStatus::Endangered),
+                ..animal("sea otter")
+            },
+            id,
+        )
+        .expect("update");
+    repository
+        .update_animal(&animal("sea otter"), id)
+        .expect("update");
+    assert_eq!(
+        repository
+            .fetch_animal_by_id(id)
+            .expect("fetch")
+            .conservation_status,
+        ConservationStatus::Endangered
+    );
+}
+
+pub fn fetch_by_taxa_filters(repository: &dyn AnimalRepository) {
+    for (species, taxon_id) in [
+        ("lion", Some(7)),
+        ("cat", Some(10)),
+        ("dog", Some(13)),
+        ("otter", None),
+    ] {
+        repository
+            .add_animal(&AnimalAddUpdateObject {
+                taxon_id,
+                ..animal(species)
+            })
+            .expect("add");
+    }
+
+    let animals = repository
+        .fetch_animals_by_taxa(&[10, 7])
+        .expect("fetch by taxa");
+    let species: Vec<&str> = animals
+        .iter()
+        .map(|animal| animal.species.as_str())
+        .collect();
+    assert_eq!(species, vec!["lion", "cat"]);
+    assert!(
+        repository
+            .fetch_animals_by_taxa(&[])
+            .expect("fetch by taxa")
+            .is_empty()
+    );
+}
+
+pub fn update_unknown_is_not_found(repository: &dyn AnimalRepository) {
+    let err = repository
+        .update_animal(&animal("otter"), 404)
+        .expect_err("unknown id");
+    assert!(matches!(
+        err.current_context(),
+        AnimalRepositoryError::NotFoundError
+    ));
+}
+
+pub fn import_keeps_ids_and_replaces(repository: &dyn AnimalRepository) {
+    let imported = AnimalObject {
+        id: 40,
+        species: "otter".to_string(),
+        description: "Imported".to_string(),
+        taxon_id: None,
+        conservation_status: ConservationStatus::Endangered,
+    };
+    repository.import_animal(&imported).expect("import");
+    repository
+        .import_animal(&AnimalObject {
+            description: "Imported again".to_string(),
+            ..imported
+        })
+        .expect("import again");
+
+    let animals = repository.fetch_all_animals().expect("fetch all");
+    assert_eq!(animals.len(), 1);
+    assert_eq!(animals[0].id, 40);
+    assert_eq!(animals[0].description, "Imported again");
+
+    let id = repository.add_animal(&animal("beaver")).expect("add");
+    assert!(id > 40);
+}
+
+pub fn species_are_unique_ignoring_case(repository: &dyn AnimalRepository) {
+    let otter = repository.add_animal(&animal("otter")).expect("add");
+    let beaver = repository.add_animal(&animal("beaver")).expect("add");
+
+    assert!(repository.is_species_taken(" Otter", None).expect("lookup"));
+    assert!(
+        !repository
+            .is_species_taken("otter", Some(otter))
+            .expect("lookup")
+    );
+    assert!(
+        !repository
+            .is_species_taken("sea otter", None)
+            .expect("lookup")
+    );
+
+    let err = repository
+        .add_animal(&animal("OTTER"))
+        .expect_err("duplicate species");
+    assert!(matches!(
+        err.current_context(),
+        AnimalRepositoryError::SpeciesTakenError
+    ));
+    let err = repository
+        .update_animal(&animal("otter "), beaver)
+        .expect_err("duplicate species");
+    assert!(matches!(
+        err.current_context(),
+        AnimalRepositoryError::SpeciesTakenError
+    ));
+    repository
+        .update_animal(&animal("Otter"), otter)
+        .expect("rename in place");
+}
+
+pub fn species_ignore_ascii_case_and_spaces_only(repository: &dyn AnimalRepository) {
+    repository.add_animal(&animal("Ébène")).expect("add");
+
+    assert!(
+        repository
+            .is_species_taken(" ÉBèNE  ", None)
+            .expect("lookup")
+    );
+    assert!(!repository.is_species_taken("ébène", None).expect("lookup"));
+    assert!(
+        !repository
+            .is_species_taken("\tÉbène", None)
+            .expect("lookup")
+    );
+    repository
+        .add_animal(&animal("ébène"))
+        .expect("other case outside ASCII");
+    repository
+        .add_animal(&animal("\tÉbène"))
+        .expect("tab is not trimmed");
+}
+
+pub fn delete_removes_the_animal(repository: &dyn AnimalRepository) {
+    let id = repository.add_animal(&animal("otter")).expect("add");
+    repository.delete_animal(id).expect("delete");
+    let err = repository.fetch_animal_by_id(id).expect_err("deleted");
+    assert!(matches!(
+        err.current_context(),
+        AnimalRepositoryError::NotFoundError
+    ));
+    let err = repository.delete_animal(id).expect_err("deleted twice");
+    assert!(matches!(
+        err.current_context(),
+        AnimalRepositoryError::NotFoundError
+    ));
+}
+
+/// Tables holding rows that depend on an animal, directly or through another table.
+const DEPENDENT_TABLES: [&str; 8] = [
+    "animal_photo",
+    "animal_photo_thumbnail",
+    "animal_tag",
+    "animal_habitat",
+    "animal_habitat_rtree",
+    "animal_sighting",
+    "animal_sighting_rtree",
+    "animal_translation",
+];
+
+fn add_dependents(sqlite_client: &SqliteClient<TenantConnection>, id: i64) {
+    let conn = sqlite_client.get_conn().lock().expect("lock");
+    conn.execute_batch(&format!(
+        "INSERT INTO animal_photo (animal_id, file_key, content_type, width, height, byte_size, created_at)
+         VALUES ({id}, 'photo', 'image/webp', 1, 1, 1, '2024-01-01T00:00:00Z');
+         INSERT INTO animal_photo_thumbnail (photo_id, size, file_key, width, height)
+         VALUES (last_insert_rowid(), 64, 'thumbnail', 1, 1);
+         INSERT INTO tag (name) VALUES ('tag {id}');
+         INSERT INTO animal_tag (animal_id, tag_id) VALUES ({id}, last_insert_rowid());
+         INSERT INTO animal_habitat (animal_id, polygon) VALUES ({id}, '[]');
+         INSERT INTO animal_habitat_rtree VALUES (last_insert_rowid(), 0, 1, 0, 1);
+         INSERT INTO animal_sighting (animal_id, longitude, latitude) VALUES ({id}, 0, 0);
+         INSERT INTO animal_sighting_rtree VALUES (last_insert_rowid(), 0, 0, 0, 0);
+         INSERT INTO animal_translation (animal_id, locale, species, description)
+         VALUES ({id}, 'fr', 'loutre', 'Une loutre');"
+    ))
+    .expect("add dependents");
+}
+
+pub fn delete_removes_the_dependents(
+    repository: &dyn AnimalRepository,
+    sqlite_client: &SqliteClient<TenantConnection>,
+) {
+    let otter = repository.add_animal(&animal("otter")).expect("add");
+    let beaver = repository.add_animal(&animal("beaver")).expect("add");
+    add_dependents(sqlite_client, otter);
+    add_dependents(sqlite_client, beaver);
+
+    repository.delete_animal(otter).expect("delete");
+    let conn = sqlite_client.get_conn().lock().expect("lock");
+    for table in DEPENDENT_TABLES {
+        let rows: i64 = conn
+            .query_row(&format!("SELECT count(*) FROM {table}"), [], |row| {
+                row.get(0)
+            })
+            .expect("count");
+        assert_eq!(rows, 1, "rows left in {table}");
+    }
+}
+
+pub fn transaction_commits_or_rolls_back(repository: &dyn AnimalRepository) {
+    let otter = repository.add_animal(&animal("otter")).expect("add");
+    repository
+        .transaction(&mut |transaction| {
+            let beaver = transaction.add_animal(&animal("beaver"))?;
+            assert!(transaction.is_species_taken("Beaver", None)?);
+            transaction.update_animal(&animal("sea otter"), otter)?;
+            transaction.delete_animal(beaver)
+        })
+        .expect("commit");
+    assert_eq!(
+        repository.fetch_animal_by_id(otter).expect("fetch").species,
+        "sea otter"
+    );
+
+    let err = repository
+        .transaction(&mut |transaction| {
+            transaction.add_animal(&animal("beaver"))?;
+            transaction.delete_animal(otter)?;
+            Err(AnimalRepositoryError::TransactionAbortedError.into())
+        })
+        .expect_err("rolled back");
+    assert!(matches!(
+        err.current_context(),
+        AnimalRepositoryError::TransactionAbortedError
+    ));
+    let species: Vec<String> = repository
+        .fetch_all_animals()
+        .expect("fetch all")
+        .into_iter()
+        .map(|animal| animal.species)
+        .collect();
+    assert_eq!(species, vec!["sea otter"]);
+}
+
+macro_rules! conformance_suite {
+    ($backend:ident, |$sqlite_client:ident| $repository:expr) => {
+        mod $backend {
+            use crate::common::db::{SqliteClient, TenantConnection};
+
+            /// The backend, with the database holding the rows depending on its animals.
+            fn repository() -> (
+                impl crate::animal::repository::AnimalRepository,
+                SqliteClient<TenantConnection>,
+            ) {
+                let $sqlite_client =
+                    SqliteClient::<TenantConnection>::new_in_memory().expect("in-memory database");
+                ($repository, $sqlite_client)
+            }
+
+            #[test]
+            fn add_then_fetch() {
+                super::add_then_fetch(&repository().0);
+            }
+
+            #[test]
+            fn fetch_all_in_id_order() {
+                super::fetch_all_in_id_order(&repository().0);
+            }
+
+            #[test]
+            fn fetch_unknown_is_not_found() {
+                super::fetch_unknown_is_not_found(&repository().0);
+            }
+
+            #[test]
+            fn update_changes_the_animal() {
+                super::update_changes_the_animal(&repository().0);
+            }
+
+            #[test]
+            fn update_keeps_the_taxon_when_left_out() {
+                super::update_keeps_the_taxon_when_left_out(&repository().0);
+            }
+
+            #[test]
+            fn conservation_status_defaults_and_is_kept_when_left_out() {
+                super::conservation_status_defaults_and_is_kept_when_left_out(&repository().0);
+            }
+
+            #[test]
+            fn fetch_by_taxa_filters() {
+                super::fetch_by_taxa_filters(&repository().0);
+            }
+
+            #[test]
+            fn update_unknown_is_not_found() {
+                super::update_unknown_is_not_found(&repository().0);
+            }
+
+            #[test]
+            fn import_keeps_ids_and_replaces() {
+                super::import_keeps_ids_and_replaces(&repository().0);
+            }
+
+            #[test]
+            fn species_are_unique_ignoring_case() {
+                super::species_are_unique_ignoring_case(&repository().0);
+            }
+
+            #[test]
+            fn species_ignore_ascii_case_and_spaces_only() {
+                super::species_ignore_ascii_case_and_spaces_only(&repository().0);
+            }
+
+            #[test]
+            fn delete_removes_the_animal() {
+                super::delete_removes_the_animal(&repository().0);
+            }
+
+            #[test]
+            fn delete_removes_the_dependents() {
+                let (repository, sqlite_client) = repository();
+                super::delete_removes_the_dependents(&repository, &sqlite_client);
+            }
+
+            #[test]
+            fn transaction_commits_or_rolls_back() {
+                super::transaction_commits_or_rolls_back(&repository().0);
+            }
+        }
+    };
+}
+
+conformance_suite!(sqlite, |sqlite_client| {
+    crate::animal::repository::sqlite::SqliteAnimalRepository::new(sqlite_client.clone())
+});
+conformance_suite!(memory, |sqlite_client| {
+    crate::animal::repository::memory::InMemoryAnimalRepository::default()
+        .with_dependents(sqlite_client.clone())
+});
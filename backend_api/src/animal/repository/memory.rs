@@ -0,0 +1,347 @@
+use crate::animal::object::ConservationStatus::{
+    Endangered, NearThreatened, NotEvaluated, Vulnerable,
+};
+use crate::animal::object::{AnimalAddUpdateObject, AnimalObject, ConservationStatus};
+use crate::animal::repository::sqlite::delete_dependents;
+use crate::animal::repository::{
+    AnimalRepository, AnimalRepositoryError, AnimalTransaction, TransactionWrites,
+    normalise_species,
+};
+use crate::common::context::{Context, ContextError, FromContext};
+use crate::common::db::{SqliteClient, TenantConnection};
+use error_stack::{Report, ResultExt};
+use rusqlite::Connection;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+/// Same animals as the `0001_init` migration seeds into a fresh database, with the taxa
+/// `0005_taxonomy` classifies them under and the status `0008_conservation_status` gives them.
+const SAMPLE_ANIMALS: [(&str, &str, i64, ConservationStatus); 12] = [
+    ("dog", "Domestic Dog", 13, NotEvaluated),
+    ("cat", "House Cat", 10, NotEvaluated),
+    ("lion", "King of the Jungle", 7, Vulnerable),
+    ("tiger", "The one with the stripes", 8, Endangered),
+    ("elephant", "Has a trunk", 20, Endangered),
+    ("monkey", "The one with the nose", 37, Endangered),
+    ("horse", "The one with the tail", 24, NotEvaluated),
+    (
+        "zebra",
+        "The one with the stripes, also.",
+        25,
+        NearThreatened,
+    ),
+    ("giraffe", "The one with the trunk, also.", 29, Vulnerable),
+    ("panda", "The one with the nose, also.", 16, Vulnerable),
+    ("llama", "The one with the tail, also.", 32, NotEvaluated),
+    ("koala", "Like a huggable Teddy Bear.", 36, Vulnerable),
+];
+
+#[derive(Default, Clone)]
+struct Animals {
+    animals: BTreeMap<i64, AnimalObject>,
+    /// Last id handed out; like `AUTOINCREMENT`, ids are never reused.
+    last_id: i64,
+}
+
+impl Animals {
+    fn is_species_taken(&self, species: &str, except_id: Option<i64>) -> bool {
+        let species = normalise_species(species);
+        self.animals.values().any(|animal| {
+            Some(animal.id) != except_id && normalise_species(&animal.species) == species
+        })
+    }
+
+    fn check_species(
+        &self,
+        species: &str,
+        except_id: Option<i64>,
+    ) -> Result<(), Report<AnimalRepositoryError>> {
+        if self.is_species_taken(species, except_id) {
+            return Err(AnimalRepositoryError::SpeciesTakenError.into());
+        }
+        Ok(())
+    }
+
+    fn add(
+        &mut self,
+        object: &AnimalAddUpdateObject,
+    ) -> Result<i64, Report<AnimalRepositoryError>> {
+        self.check_species(&object.species, None)?;
+        self.last_id += 1;
+        let id = self.last_id;
+        self.animals.insert(
+            id,
+            AnimalObject {
+                id,
+                species: object.species.clone(),
+                description: object.description.clone(),
+                taxon_id: object.taxon_id,
+                conservation_status: object.conservation_status.unwrap_or_default(),
+            },
+        );
+
+        Ok(id)
+    }
+
+    fn fetch(&self, id: i64) -> Result<AnimalObject, Report<AnimalRepositoryError>> {
+        Ok(self
+            .animals
+            .get(&id)
+            .cloned()
+            .ok_or(AnimalRepositoryError::NotFoundError)?)
+    }
+
+    fn update(
+        &mut self,
+        object: &AnimalAddUpdateObject,
+        id: i64,
+    ) -> Result<(), Report<AnimalRepositoryError>> {
+        if !self.animals.contains_key(&id) {
+            return Err(AnimalRepositoryError::NotFoundError.into());
+        }
+        self.check_species(&object.species, Some(id))?;
+        let animal = self
+            .animals
+            .get_mut(&id)
+            .ok_or(AnimalRepositoryError::NotFoundError)?;
+        animal.species = object.species.clone();
+        animal.description = object.description.clone();
+        if object.taxon_id.is_some() {
+            animal.taxon_id = object.taxon_id;
+        }
+        if let Some(conservation_status) = object.conservation_status {
+            animal.conservation_status = conservation_status;
+        }
+
+        Ok(())
+    }
+
+    fn delete(&mut self, id: i64) -> Result<(), Report<AnimalRepositoryError>> {
+        self.animals
+            .remove(&id)
+            .ok_or(AnimalRepositoryError::NotFoundError)?;
+        Ok(())
+    }
+}
+
+/// Animals kept in process memory, for demos and as a test double. Only the animals are: their
+/// photos, tags, habitat, sightings and translations stay in the SQLite database.
+#[derive(Default)]
+pub struct InMemoryAnimalRepository {
+    animals: Arc<Mutex<Animals>>,
+    /// Database holding the photos, tags, habitat, sightings and translations of the animals.
+    dependents: Option<SqliteClient<TenantConnection>>,
+}
+
+impl InMemoryAnimalRepository {
+    /// The same animals, deleted along with their rows in `sqlite_client`.
+    pub fn with_dependents(&self, sqlite_client: SqliteClient<TenantConnection>) -> Self {
+        Self {
+            animals: Arc::clone(&self.animals),
+            dependents: Some(sqlite_client),
+        }
+    }
+
+    pub fn with_sample_data() -> Self {
+        let repository = Self::default();
+        for (species, description, taxon_id, conservation_status) in SAMPLE_ANIMALS {
+            let _ = repository.add_animal(&AnimalAddUpdateObject {
+                species: species.to_string(),
+                description: description.to_string(),
+                taxon_id: Some(taxon_id),
+                conservation_status: Some(conservation_status),
+            });
+        }
+        repository
+    }
+}
+
+impl AnimalRepository for InMemoryAnimalRepository {
+    fn add_animal(
+        &self,
+        object: &AnimalAddUpdateObject,
+    ) -> Result<i64, Report<AnimalRepositoryError>> {
+        self.animals
+            .lock()
+            .map_err(|_| AnimalRepositoryError::LockError)?
+            .add(object)
+    }
+
+    fn fetch_all_animals(&self) -> Result<Box<[AnimalObject]>, Report<AnimalRepositoryError>> {
+        let animals = self
+            .animals
+            .lock()
+            .map_err(|_| AnimalRepositoryError::LockError)?;
+
+        Ok(animals.animals.values().cloned().collect())
+    }
+
+    fn fetch_animal_by_id(&self, id: i64) -> Result<AnimalObject, Report<AnimalRepositoryError>> {
+        self.animals
+            .lock()
+            .map_err(|_| AnimalRepositoryError::LockError)?
+            .fetch(id)
+    }
+
+    fn fetch_animals_by_taxa(
+        &self,
+        taxon_ids: &[i64],
+    ) -> Result<Box<[AnimalObject]>, Report<AnimalRepositoryError>> {
+        let animals = self
+            .animals
+            .lock()
+            .map_err(|_| AnimalRepositoryError::LockError)?;
+
+        Ok(animals
+            .animals
+            .values()
+            .filter(|animal| {
+                animal
+                    .taxon_id
+                    .is_some_and(|taxon_id| taxon_ids.contains(&taxon_id))
+            })
+            .cloned()
+            .collect())
+    }
+
+    fn update_animal(
+        &self,
+        object: &AnimalAddUpdateObject,
+        id: i64,
+    ) -> Result<(), Report<AnimalRepositoryError>> {
+        self.animals
+            .lock()
+            .map_err(|_| AnimalRepositoryError::LockError)?
+            .update(object, id)
+    }
+
+    fn import_animal(&self, object: &AnimalObject) -> Result<(), Report<AnimalRepositoryError>> {
+        let mut animals = self
+            .animals
+            .lock()
+            .map_err(|_| AnimalRepositoryError::LockError)?;
+
+        animals.check_species(&object.species, Some(object.id))?;
+        animals.last_id = animals.last_id.max(object.id);
+        animals.animals.insert(object.id, object.clone());
+
+        Ok(())
+    }
+
+    fn is_species_taken(
+        &self,
+        species: &str,
+        except_id: Option<i64>,
+    ) -> Result<bool, Report<AnimalRepositoryError>> {
+        let animals = self
+            .animals
+            .lock()
+            .map_err(|_| AnimalRepositoryError::LockError)?;
+
+        Ok(animals.is_species_taken(species, except_id))
+    }
+
+    fn delete_animal(&self, id: i64) -> Result<(), Report<AnimalRepositoryError>> {
+        self.transaction(&mut |transaction| transaction.delete_animal(id))
+    }
+
+    /// Writes go to a copy of the animals, which replaces them on commit, and to a transaction
+    /// on the database of their dependents, committed first.
+    fn transaction(
+        &self,
+        writes: &mut TransactionWrites,
+    ) -> Result<(), Report<AnimalRepositoryError>> {
+        let mut animals = self
+            .animals
+            .lock()
+            .map_err(|_| AnimalRepositoryError::LockError)?;
+        let mut conn = match &self.dependents {
+            Some(sqlite_client) => Some(
+                sqlite_client
+                    .get_conn()
+                    .lock()
+                    .map_err(|_| AnimalRepositoryError::LockError)?,
+            ),
+            None => None,
+        };
+        let tx = conn
+            .as_mut()
+            .map(|conn| conn.transaction())
+            .transpose()
+            .change_context(AnimalRepositoryError::QueryError)?;
+
+        let transaction = InMemoryAnimalTransaction {
+            animals: RefCell::new(animals.clone()),
+            dependents: tx.as_deref(),
+        };
+        writes(&transaction)?;
+        let written = transaction.animals.into_inner();
+        if let Some(tx) = tx {
+            tx.commit()
+                .change_context(AnimalRepositoryError::QueryError)?;
+        }
+        *animals = written;
+
+        Ok(())
+    }
+}
+
+struct InMemoryAnimalTransaction<'a> {
+    animals: RefCell<Animals>,
+    dependents: Option<&'a Connection>,
+}
+
+impl AnimalTransaction for InMemoryAnimalTransaction<'_> {
+    fn add_animal(
+        &self,
+        object: &AnimalAddUpdateObject,
+    ) -> Result<i64, Report<AnimalRepositoryError>> {
+        self.animals.borrow_mut().add(object)
+    }
+
+    fn fetch_animal_by_id(&self, id: i64) -> Result<AnimalObject, Report<AnimalRepositoryError>> {
+        self.animals.borrow().fetch(id)
+    }
+
+    fn update_animal(
+        &self,
+        object: &AnimalAddUpdateObject,
+        id: i64,
+    ) -> Result<(), Report<AnimalRepositoryError>> {
+        self.animals.borrow_mut().update(object, id)
+    }
+
+    fn delete_animal(&self, id: i64) -> Result<(), Report<AnimalRepositoryError>> {
+        self.animals.borrow_mut().delete(id)?;
+        match self.dependents {
+            Some(conn) => delete_dependents(conn, id),
+            None => Ok(()),
+        }
+    }
+
+    fn is_species_taken(
+        &self,
+        species: &str,
+        except_id: Option<i64>,
+    ) -> Result<bool, Report<AnimalRepositoryError>> {
+        Ok(self.animals.borrow().is_species_taken(species, except_id))
+    }
+}
+
+static IN_MEMORY_ANIMAL_REPOSITORY_CACHE: OnceCell<Arc<InMemoryAnimalRepository>> =
+    OnceCell::const_new();
+
+impl FromContext for Arc<InMemoryAnimalRepository> {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        if let Some(repository) = ctx.overridden::<Self>() {
+            return Ok(repository);
+        }
+        Ok(Arc::clone(
+            IN_MEMORY_ANIMAL_REPOSITORY_CACHE
+                .get_or_init(|| async { Arc::new(InMemoryAnimalRepository::with_sample_data()) })
+                .await,
+        ))
+    }
+}
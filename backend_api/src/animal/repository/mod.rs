@@ -0,0 +1,137 @@
+#[cfg(test)]
+mod conformance;
+pub mod memory;
+pub mod sqlite;
+
+use crate::animal::events::{AnimalEvents, EventScope, ObservedAnimalRepository};
+use crate::animal::object::{AnimalAddUpdateObject, AnimalObject};
+use crate::animal::repository::memory::InMemoryAnimalRepository;
+use crate::animal::repository::sqlite::SqliteAnimalRepository;
+use crate::common::config::repository::RepositoryBackend;
+use crate::common::context::{Context, ContextError, FromContext};
+use crate::common::db::{SqliteClient, TenantConnection};
+use error_stack::Report;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum AnimalRepositoryError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Row Value error")]
+    RowValueError,
+    #[error("Lock error")]
+    LockError,
+    #[error("Not found error")]
+    NotFoundError,
+    #[error("Species taken error")]
+    SpeciesTakenError,
+    /// The writes of a transaction were rolled back at its own request.
+    #[error("Transaction aborted error")]
+    TransactionAbortedError,
+}
+
+/// Species are compared as the unique index on them does, SQLite's `trim` and `NOCASE`:
+/// spaces trimmed, but not tabs or other whitespace, and ASCII letters only ignoring case.
+pub fn normalise_species(species: &str) -> String {
+    species.trim_matches(' ').to_ascii_lowercase()
+}
+
+/// Storage for animals. Every backend has to pass the suite in `conformance`. Species are
+/// unique, writes clashing with another animal fail with `SpeciesTakenError`.
+pub trait AnimalRepository: Send + Sync {
+    /// Add the animal and return its new id.
+    fn add_animal(
+        &self,
+        object: &AnimalAddUpdateObject,
+    ) -> Result<i64, Report<AnimalRepositoryError>>;
+
+    fn fetch_all_animals(&self) -> Result<Box<[AnimalObject]>, Report<AnimalRepositoryError>>;
+
+    fn fetch_animal_by_id(&self, id: i64) -> Result<AnimalObject, Report<AnimalRepositoryError>>;
+
+    /// Animals classified directly under any of `taxon_ids`, in id order.
+    fn fetch_animals_by_taxa(
+        &self,
+        taxon_ids: &[i64],
+    ) -> Result<Box<[AnimalObject]>, Report<AnimalRepositoryError>>;
+
+    fn update_animal(
+        &self,
+        object: &AnimalAddUpdateObject,
+        id: i64,
+    ) -> Result<(), Report<AnimalRepositoryError>>;
+
+    /// Insert the animal with its id, replacing any existing animal with the same id.
+    fn import_animal(&self, object: &AnimalObject) -> Result<(), Report<AnimalRepositoryError>>;
+
+    /// Whether an animal other than `except_id` goes by `species`, see [`normalise_species`].
+    fn is_species_taken(
+        &self,
+        species: &str,
+        except_id: Option<i64>,
+    ) -> Result<bool, Report<AnimalRepositoryError>>;
+
+    /// Delete the animal with its photos, tags, habitat, sightings and translations.
+    fn delete_animal(&self, id: i64) -> Result<(), Report<AnimalRepositoryError>>;
+
+    /// Make the writes of `writes` in one transaction, committed when it returns `Ok` and
+    /// rolled back otherwise. Other writers wait until it is over.
+    fn transaction(
+        &self,
+        writes: &mut TransactionWrites,
+    ) -> Result<(), Report<AnimalRepositoryError>>;
+}
+
+/// The writes [`AnimalRepository::transaction`] makes in one transaction.
+pub type TransactionWrites<'a> =
+    dyn FnMut(&dyn AnimalTransaction) -> Result<(), Report<AnimalRepositoryError>> + 'a;
+
+/// Handle on a transaction of an [`AnimalRepository`], whose reads see its earlier writes.
+pub trait AnimalTransaction {
+    fn add_animal(
+        &self,
+        object: &AnimalAddUpdateObject,
+    ) -> Result<i64, Report<AnimalRepositoryError>>;
+
+    fn fetch_animal_by_id(&self, id: i64) -> Result<AnimalObject, Report<AnimalRepositoryError>>;
+
+    fn update_animal(
+        &self,
+        object: &AnimalAddUpdateObject,
+        id: i64,
+    ) -> Result<(), Report<AnimalRepositoryError>>;
+
+    fn delete_animal(&self, id: i64) -> Result<(), Report<AnimalRepositoryError>>;
+
+    fn is_species_taken(
+        &self,
+        species: &str,
+        except_id: Option<i64>,
+    ) -> Result<bool, Report<AnimalRepositoryError>>;
+}
+
+/// The animal repository of the backend picked by `repository.backend`, publishing its writes
+/// to [`AnimalEvents`].
+pub type DynAnimalRepository = Arc<dyn AnimalRepository>;
+
+impl FromContext for DynAnimalRepository {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let config = ctx.config.upgrade().ok_or(ContextError::ConfigError)?;
+        let repository: DynAnimalRepository = match config.repository.backend {
+            RepositoryBackend::Sqlite => Arc::new(SqliteAnimalRepository::new(
+                SqliteClient::<TenantConnection>::from_context(ctx).await?,
+            )),
+            RepositoryBackend::Memory => Arc::new(
+                Arc::<InMemoryAnimalRepository>::from_context(ctx)
+                    .await?
+                    .with_dependents(SqliteClient::<TenantConnection>::from_context(ctx).await?),
+            ),
+        };
+        Ok(Arc::new(ObservedAnimalRepository::new(
+            repository,
+            AnimalEvents::from_context(ctx).await?,
+            EventScope::from_context(ctx).await?,
+        )))
+    }
+}
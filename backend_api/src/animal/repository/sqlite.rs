@@ -0,0 +1,313 @@
+use crate::animal::object::{AnimalAddUpdateObject, AnimalObject};
+use crate::animal::repository::{
+    AnimalRepository, AnimalRepositoryError, AnimalTransaction, TransactionWrites,
+};
+use crate::common::db::{SqliteClient, TenantConnection};
+use error_stack::{Report, ResultExt};
+use rusqlite::{Connection, ErrorCode, named_params};
+
+fn map_write_error(err: rusqlite::Error) -> Report<AnimalRepositoryError> {
+    match err.sqlite_error_code() {
+        Some(ErrorCode::ConstraintViolation) => {
+            Report::new(err).change_context(AnimalRepositoryError::SpeciesTakenError)
+        }
+        _ => Report::new(err).change_context(AnimalRepositoryError::QueryError),
+    }
+}
+
+pub struct SqliteAnimalRepository {
+    sqlite_client: SqliteClient<TenantConnection>,
+}
+
+impl SqliteAnimalRepository {
+    pub fn new(sqlite_client: SqliteClient<TenantConnection>) -> Self {
+        Self { sqlite_client }
+    }
+}
+
+impl AnimalRepository for SqliteAnimalRepository {
+    fn add_animal(
+        &self,
+        object: &AnimalAddUpdateObject,
+    ) -> Result<i64, Report<AnimalRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| AnimalRepositoryError::LockError)?;
+        SqliteAnimalTransaction { conn: &conn }.add_animal(object)
+    }
+
+    fn fetch_all_animals(&self) -> Result<Box<[AnimalObject]>, Report<AnimalRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| AnimalRepositoryError::LockError)?;
+
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_all_animals.sql"))
+            .change_context(AnimalRepositoryError::QueryError)?;
+
+        let item_iter = stmt
+            .query_map([], |row| {
+                Ok(AnimalObject {
+                    id: row.get("id")?,
+                    species: row.get("species")?,
+                    description: row.get("description")?,
+                    taxon_id: row.get("taxon_id")?,
+                    conservation_status: row.get("conservation_status")?,
+                })
+            })
+            .change_context(AnimalRepositoryError::QueryError)?;
+
+        let mut items = Vec::new();
+        for item in item_iter {
+            items.push(item.change_context(AnimalRepositoryError::RowValueError)?);
+        }
+
+        Ok(items.into())
+    }
+
+    fn fetch_animal_by_id(&self, id: i64) -> Result<AnimalObject, Report<AnimalRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| AnimalRepositoryError::LockError)?;
+        SqliteAnimalTransaction { conn: &conn }.fetch_animal_by_id(id)
+    }
+
+    fn fetch_animals_by_taxa(
+        &self,
+        taxon_ids: &[i64],
+    ) -> Result<Box<[AnimalObject]>, Report<AnimalRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| AnimalRepositoryError::LockError)?;
+
+        let taxon_ids =
+            serde_json::to_string(taxon_ids).change_context(AnimalRepositoryError::QueryError)?;
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_animals_by_taxa.sql"))
+            .change_context(AnimalRepositoryError::QueryError)?;
+
+        let item_iter = stmt
+            .query_map(named_params! {":taxon_ids": taxon_ids}, |row| {
+                Ok(AnimalObject {
+                    id: row.get("id")?,
+                    species: row.get("species")?,
+                    description: row.get("description")?,
+                    taxon_id: row.get("taxon_id")?,
+                    conservation_status: row.get("conservation_status")?,
+                })
+            })
+            .change_context(AnimalRepositoryError::QueryError)?;
+
+        let mut items = Vec::new();
+        for item in item_iter {
+            items.push(item.change_context(AnimalRepositoryError::RowValueError)?);
+        }
+
+        Ok(items.into())
+    }
+
+    fn update_animal(
+        &self,
+        object: &AnimalAddUpdateObject,
+        id: i64,
+    ) -> Result<(), Report<AnimalRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| AnimalRepositoryError::LockError)?;
+        SqliteAnimalTransaction { conn: &conn }.update_animal(object, id)
+    }
+
+    fn import_animal(&self, object: &AnimalObject) -> Result<(), Report<AnimalRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| AnimalRepositoryError::LockError)?;
+
+        conn.execute(
+            include_str!("_sql/import_animal.sql"),
+            named_params! {
+                ":id": object.id,
+                ":species": object.species,
+                ":description": object.description,
+                ":taxon_id": object.taxon_id,
+                ":conservation_status": object.conservation_status,
+            },
+        )
+        .map_err(map_write_error)?;
+
+        Ok(())
+    }
+
+    fn is_species_taken(
+        &self,
+        species: &str,
+        except_id: Option<i64>,
+    ) -> Result<bool, Report<AnimalRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| AnimalRepositoryError::LockError)?;
+        SqliteAnimalTransaction { conn: &conn }.is_species_taken(species, except_id)
+    }
+
+    fn delete_animal(&self, id: i64) -> Result<(), Report<AnimalRepositoryError>> {
+        self.transaction(&mut |transaction| transaction.delete_animal(id))
+    }
+
+    fn transaction(
+        &self,
+        writes: &mut TransactionWrites,
+    ) -> Result<(), Report<AnimalRepositoryError>> {
+        let mut conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| AnimalRepositoryError::LockError)?;
+        let tx = conn
+            .transaction()
+            .change_context(AnimalRepositoryError::QueryError)?;
+
+        writes(&SqliteAnimalTransaction { conn: &tx })?;
+        tx.commit()
+            .change_context(AnimalRepositoryError::QueryError)
+    }
+}
+
+/// Delete the photos, tags, habitat, sightings and translations of the animal. Thumbnails
+/// cascade with their photos.
+pub fn delete_dependents(conn: &Connection, id: i64) -> Result<(), Report<AnimalRepositoryError>> {
+    for sql in [
+        include_str!("_sql/delete_animal_photos.sql"),
+        include_str!("_sql/delete_animal_tags.sql"),
+        include_str!("_sql/delete_animal_habitat.sql"),
+        include_str!("_sql/delete_animal_sightings.sql"),
+        include_str!("_sql/delete_animal_translations.sql"),
+    ] {
+        conn.execute(sql, named_params! {":animal_id": id})
+            .change_context(AnimalRepositoryError::QueryError)?;
+    }
+
+    Ok(())
+}
+
+/// Queries shared by the repository and its transactions, on the connection or transaction
+/// they hold.
+struct SqliteAnimalTransaction<'a> {
+    conn: &'a Connection,
+}
+
+impl AnimalTransaction for SqliteAnimalTransaction<'_> {
+    fn add_animal(
+        &self,
+        object: &AnimalAddUpdateObject,
+    ) -> Result<i64, Report<AnimalRepositoryError>> {
+        self.conn
+            .execute(
+                include_str!("_sql/add_animal.sql"),
+                named_params! {
+                    ":species": object.species,
+                    ":description": object.description,
+                    ":taxon_id": object.taxon_id,
+                    ":conservation_status": object.conservation_status,
+                },
+            )
+            .map_err(map_write_error)?;
+
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    fn fetch_animal_by_id(&self, id: i64) -> Result<AnimalObject, Report<AnimalRepositoryError>> {
+        let mut stmt = self
+            .conn
+            .prepare(include_str!("_sql/fetch_animal_by_id.sql"))
+            .change_context(AnimalRepositoryError::QueryError)?;
+
+        let item_iter = stmt.query_map(
+            named_params! {
+                ":id": id,
+            },
+            |row| {
+                Ok(AnimalObject {
+                    id: row.get("id")?,
+                    species: row.get("species")?,
+                    description: row.get("description")?,
+                    taxon_id: row.get("taxon_id")?,
+                    conservation_status: row.get("conservation_status")?,
+                })
+            },
+        );
+
+        let item = item_iter
+            .change_context(AnimalRepositoryError::QueryError)?
+            .next()
+            .ok_or(AnimalRepositoryError::NotFoundError)?;
+
+        Ok(item.change_context(AnimalRepositoryError::RowValueError)?)
+    }
+
+    fn update_animal(
+        &self,
+        object: &AnimalAddUpdateObject,
+        id: i64,
+    ) -> Result<(), Report<AnimalRepositoryError>> {
+        let updated = self
+            .conn
+            .execute(
+                include_str!("_sql/update_animals.sql"),
+                named_params! {
+                    ":species": object.species,
+                    ":description": object.description,
+                    ":taxon_id": object.taxon_id,
+                    ":conservation_status": object.conservation_status,
+                    ":id": id,
+                },
+            )
+            .map_err(map_write_error)?;
+        if updated == 0 {
+            return Err(AnimalRepositoryError::NotFoundError.into());
+        }
+
+        Ok(())
+    }
+
+    fn delete_animal(&self, id: i64) -> Result<(), Report<AnimalRepositoryError>> {
+        let deleted = self
+            .conn
+            .execute(
+                include_str!("_sql/delete_animal.sql"),
+                named_params! {":id": id},
+            )
+            .change_context(AnimalRepositoryError::QueryError)?;
+        if deleted == 0 {
+            return Err(AnimalRepositoryError::NotFoundError.into());
+        }
+
+        delete_dependents(self.conn, id)
+    }
+
+    fn is_species_taken(
+        &self,
+        species: &str,
+        except_id: Option<i64>,
+    ) -> Result<bool, Report<AnimalRepositoryError>> {
+        self.conn
+            .query_row(
+                include_str!("_sql/fetch_species_taken.sql"),
+                named_params! {":species": species, ":except_id": except_id},
+                |row| row.get::<_, bool>("taken"),
+            )
+            .change_context(AnimalRepositoryError::QueryError)
+    }
+}
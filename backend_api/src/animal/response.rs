@@ -1,11 +1,18 @@
-use crate::animal::object::{AnimalErrorObject, AnimalObject};
+use crate::animal::object::{AnimalBatchResultObject, AnimalErrorObject, AnimalObject};
+use crate::common::object::Message;
+use crate::geo::object::GeoQueryErrorObject;
 use poem_openapi::ApiResponse;
 use poem_openapi::payload::Json;
 
 #[derive(ApiResponse)]
 pub enum FetchAllAnimalsResponse {
     #[oai(status = 200)]
-    Ok(Json<Vec<AnimalObject>>),
+    Ok(
+        Json<Vec<AnimalObject>>,
+        #[oai(header = "Content-Language")] String,
+    ),
+    #[oai(status = 400)]
+    BadRequest(Json<GeoQueryErrorObject>),
     #[oai(status = 500)]
     InternalServerError,
 }
@@ -13,9 +20,14 @@ pub enum FetchAllAnimalsResponse {
 #[derive(ApiResponse)]
 pub enum FetchAnimalByIdResponse {
     #[oai(status = 200)]
-    Ok(Json<AnimalObject>),
+    Ok(
+        Json<AnimalObject>,
+        #[oai(header = "Content-Language")] String,
+    ),
     #[oai(status = 404)]
     NotFound,
+    #[oai(status = 500)]
+    InternalServerError,
 }
 
 #[derive(ApiResponse)]
@@ -24,6 +36,9 @@ pub enum AddAnimalResponse {
     Created,
     #[oai(status = 422)]
     UnprocessableEntity(Json<AnimalErrorObject>),
+    /// Another animal took the species in the meantime
+    #[oai(status = 409)]
+    Conflict(Json<AnimalErrorObject>),
     #[oai(status = 400)]
     BadRequest,
 }
@@ -34,6 +49,21 @@ pub enum UpdateAnimalResponse {
     Ok,
     #[oai(status = 422)]
     UnprocessableEntity(Json<AnimalErrorObject>),
+    /// Another animal took the species in the meantime
+    #[oai(status = 409)]
+    Conflict(Json<AnimalErrorObject>),
     #[oai(status = 404)]
     NotFound,
 }
+
+#[derive(ApiResponse)]
+pub enum AnimalBatchResponse {
+    /// Each operation has a result of its own, `committed` tells whether they were kept
+    #[oai(status = 200)]
+    Ok(Json<AnimalBatchResultObject>),
+    /// More operations than `poem.limits.max_batch_operations`
+    #[oai(status = 413)]
+    PayloadTooLarge(Json<Message>),
+    #[oai(status = 500)]
+    InternalServerError,
+}
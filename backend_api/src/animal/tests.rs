@@ -0,0 +1,501 @@
+use crate::common::config::Config;
+use crate::common::config::limits::LimitsConfig;
+use crate::common::config::poem::PoemConfig;
+use crate::common::config::repository::{RepositoryBackend, RepositoryConfig};
+use crate::test_support::TestApp;
+use poem::http::StatusCode;
+use serde_json::json;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn index_lists_every_animal() {
+    let app = TestApp::builder().build();
+
+    let res = app.client.get("/animal").send().await;
+    res.assert_status_is_ok();
+    let json = res.json().await;
+    let animals = json.value().array();
+    animals.assert_len(12);
+    animals.get(0).object().get("species").assert_string("dog");
+}
+
+#[tokio::test]
+async fn fetch_by_id_returns_the_animal() {
+    let app = TestApp::builder().build();
+
+    let res = app.client.get("/animal/fetch/3").send().await;
+    res.assert_status_is_ok();
+    res.assert_json(json!({
+        "id": 3,
+        "species": "lion",
+        "description": "King of the Jungle",
+        "taxon_id": 7,
+        "conservation_status": "VU",
+    }))
+    .await;
+}
+
+#[tokio::test]
+async fn fetch_by_id_unknown_is_not_found() {
+    let app = TestApp::builder().build();
+
+    let res = app.client.get("/animal/fetch/999").send().await;
+    res.assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn add_creates_the_animal() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .post("/animal/add")
+        .body_json(&json!({"species": "otter", "description": "Floats on its back"}))
+        .send()
+        .await;
+    res.assert_status(StatusCode::CREATED);
+
+    let res = app.client.get("/animal/fetch/13").send().await;
+    res.assert_status_is_ok();
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("species")
+        .assert_string("otter");
+}
+
+#[tokio::test]
+async fn add_invalid_is_unprocessable_with_translated_errors() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .post("/animal/add")
+        .header("Accept-Language", "fr-FR")
+        .body_json(&json!({"species": "", "description": "Nameless"}))
+        .send()
+        .await;
+    res.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    let json = res.json().await;
+    let errors = json.value().object();
+    errors
+        .get("species")
+        .assert_string_array(&["Ne peut pas être vide"]);
+    errors.get("description").array().assert_is_empty();
+
+    app.client
+        .get("/animal")
+        .send()
+        .await
+        .json()
+        .await
+        .value()
+        .array()
+        .assert_len(12);
+}
+
+#[tokio::test]
+async fn add_invalid_lists_error_codes_and_arguments() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .post("/animal/add")
+        .header("Accept-Language", "fr-FR")
+        .body_json(&json!({"species": "Greater spotted woodpecker", "description": "Bird"}))
+        .send()
+        .await;
+    res.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    let json = res.json().await;
+    let errors = json.value().object();
+    errors.get("errors").array().assert_len(1);
+    let error = errors.get("errors").array().get(0).object();
+    error.get("field").assert_string("species");
+    error.get("code").assert_string("validate-max-length");
+    error.get("args").object().get("max").assert_i64(20);
+    error
+        .get("message")
+        .assert_string(errors.get("species").array().get(0).string());
+}
+
+#[tokio::test]
+async fn update_changes_the_animal() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .patch("/animal/update/1")
+        .body_json(&json!({"species": "wolf", "description": "Howls at the moon"}))
+        .send()
+        .await;
+    res.assert_status_is_ok();
+
+    let res = app.client.get("/animal/fetch/1").send().await;
+    res.assert_json(json!({
+        "id": 1,
+        "species": "wolf",
+        "description": "Howls at the moon",
+        "taxon_id": 13,
+        "conservation_status": "NE",
+    }))
+    .await;
+}
+
+#[tokio::test]
+async fn update_invalid_is_unprocessable() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .patch("/animal/update/1")
+        .body_json(&json!({"species": "a".repeat(21), "description": "Too long"}))
+        .send()
+        .await;
+    res.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("species")
+        .assert_string_array(&["Must be at most \u{2068}\u{2068}20\u{2069} characters\u{2069}"]);
+}
+
+#[tokio::test]
+async fn update_unknown_is_not_found() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .patch("/animal/update/999")
+        .body_json(&json!({"species": "ghost", "description": "Not there"}))
+        .send()
+        .await;
+    res.assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn add_duplicate_species_is_unprocessable() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .post("/animal/add")
+        .body_json(&json!({"species": " Zebra", "description": "Another one with stripes"}))
+        .send()
+        .await;
+    res.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    let json = res.json().await;
+    let errors = json.value().object();
+    errors
+        .get("species")
+        .assert_string_array(&["Already taken"]);
+    errors.get("description").array().assert_is_empty();
+    errors
+        .get("errors")
+        .array()
+        .get(0)
+        .object()
+        .get("code")
+        .assert_string("validate-species-taken");
+
+    let res = app
+        .client
+        .post("/animal/add")
+        .header("Accept-Language", "fr-FR")
+        .body_json(&json!({"species": "zebra", "description": ""}))
+        .send()
+        .await;
+    res.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("species")
+        .assert_string_array(&["Déjà prise"]);
+}
+
+#[tokio::test]
+async fn update_to_another_animals_species_is_unprocessable() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .patch("/animal/update/1")
+        .body_json(&json!({"species": "CAT", "description": "Domestic Dog"}))
+        .send()
+        .await;
+    res.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("species")
+        .assert_string_array(&["Already taken"]);
+
+    app.client
+        .patch("/animal/update/1")
+        .body_json(&json!({"species": "Dog", "description": "Domestic Dog"}))
+        .send()
+        .await
+        .assert_status_is_ok();
+}
+
+#[tokio::test]
+async fn apps_do_not_share_a_database() {
+    let first = TestApp::builder().build();
+    let second = TestApp::builder().build();
+
+    first
+        .client
+        .post("/animal/add")
+        .body_json(&json!({"species": "otter", "description": "Floats on its back"}))
+        .send()
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    second
+        .client
+        .get("/animal")
+        .send()
+        .await
+        .json()
+        .await
+        .value()
+        .array()
+        .assert_len(12);
+}
+
+#[tokio::test]
+async fn memory_backend_serves_the_sample_animals() {
+    let config = Config {
+        repository: Arc::new(RepositoryConfig {
+            backend: RepositoryBackend::Memory,
+        }),
+        ..Config::default()
+    };
+    let app = TestApp::builder().config(config).build();
+
+    app.client
+        .post("/animal/add")
+        .body_json(&json!({"species": "otter", "description": "Floats on its back"}))
+        .send()
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let res = app.client.get("/animal/fetch/13").send().await;
+    res.assert_status_is_ok();
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("species")
+        .assert_string("otter");
+    let count: i64 = app
+        .sqlite_client
+        .get_conn()
+        .lock()
+        .unwrap()
+        .query_row("SELECT COUNT(*) FROM animal", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(count, 12);
+}
+
+#[tokio::test]
+async fn index_filters_by_conservation_status() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .get("/animal?conservation_status=EN&conservation_status=NT")
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    let json = res.json().await;
+    let species: Vec<&str> = json
+        .value()
+        .array()
+        .iter()
+        .map(|animal| animal.object().get("species").string())
+        .collect();
+    assert_eq!(species, vec!["tiger", "elephant", "monkey", "zebra"]);
+
+    app.client
+        .get("/animal?conservation_status=XX")
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn update_keeps_the_conservation_status_when_left_out() {
+    let app = TestApp::builder().build();
+
+    app.client
+        .patch("/animal/update/3")
+        .body_json(&json!({
+            "species": "lion",
+            "description": "King of the Jungle",
+            "conservation_status": "EN",
+        }))
+        .send()
+        .await
+        .assert_status_is_ok();
+    app.client
+        .patch("/animal/update/3")
+        .body_json(&json!({"species": "lion", "description": "Pride leader"}))
+        .send()
+        .await
+        .assert_status_is_ok();
+
+    let res = app.client.get("/animal/fetch/3").send().await;
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("conservation_status")
+        .assert_string("EN");
+}
+
+#[tokio::test]
+async fn atomic_batch_rolls_back_on_the_first_failure() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .post("/animal/batch")
+        .body_json(&json!({
+            "mode": "atomic",
+            "operations": [
+                {"op": "create", "animal": {"species": "otter", "description": "Floats on its back"}},
+                {"op": "delete", "id": 999},
+                {"op": "delete", "id": 1},
+            ],
+        }))
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    let json = res.json().await;
+    let result = json.value().object();
+    result.get("committed").assert_bool(false);
+    let statuses: Vec<i64> = result
+        .get("results")
+        .array()
+        .iter()
+        .map(|result| result.object().get("status").i64())
+        .collect();
+    assert_eq!(statuses, vec![201, 404, 424]);
+
+    app.client
+        .get("/animal")
+        .send()
+        .await
+        .json()
+        .await
+        .value()
+        .array()
+        .assert_len(12);
+}
+
+#[tokio::test]
+async fn best_effort_batch_keeps_the_operations_that_succeed() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .post("/animal/batch")
+        .body_json(&json!({
+            "mode": "best_effort",
+            "operations": [
+                {"op": "create", "animal": {"species": "otter", "description": "Floats on its back"}},
+                {"op": "create", "animal": {"species": "Otter", "description": "Twice"}},
+                {"op": "update", "id": 3, "animal": {"species": "lion", "description": "Pride leader"}},
+                {"op": "delete", "id": 1},
+                {"op": "delete", "id": 1, "animal": {"species": "dog", "description": "Good boy"}},
+            ],
+        }))
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    let json = res.json().await;
+    let result = json.value().object();
+    result.get("committed").assert_bool(true);
+    let statuses: Vec<i64> = result
+        .get("results")
+        .array()
+        .iter()
+        .map(|result| result.object().get("status").i64())
+        .collect();
+    assert_eq!(statuses, vec![201, 422, 200, 204, 400]);
+    result
+        .get("results")
+        .array()
+        .get(1)
+        .object()
+        .get("error")
+        .object()
+        .get("species")
+        .assert_not_null();
+
+    app.client
+        .get("/animal/fetch/1")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    app.client
+        .get("/animal/fetch/13")
+        .send()
+        .await
+        .assert_status_is_ok();
+    app.client
+        .get("/animal/fetch/3")
+        .send()
+        .await
+        .json()
+        .await
+        .value()
+        .object()
+        .get("description")
+        .assert_string("Pride leader");
+}
+
+#[tokio::test]
+async fn batches_over_the_operation_limit_are_too_large() {
+    let app = TestApp::builder()
+        .config(Config {
+            poem: Arc::new(PoemConfig {
+                limits: LimitsConfig {
+                    max_batch_operations: 2,
+                    ..LimitsConfig::default()
+                },
+                ..PoemConfig::default()
+            }),
+            ..Config::default()
+        })
+        .build();
+
+    let res = app
+        .client
+        .post("/animal/batch")
+        .body_json(&json!({
+            "mode": "best_effort",
+            "operations": [
+                {"op": "delete", "id": 1},
+                {"op": "delete", "id": 2},
+                {"op": "delete", "id": 3},
+            ],
+        }))
+        .send()
+        .await;
+    res.assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+
+    app.client
+        .get("/animal")
+        .send()
+        .await
+        .json()
+        .await
+        .value()
+        .array()
+        .assert_len(12);
+}
@@ -0,0 +1,212 @@
+pub mod object;
+pub mod response;
+#[cfg(test)]
+mod tests;
+
+use crate::ApiTag;
+use crate::animal::object::{AnimalAddUpdateObject, AnimalErrorObject, ConservationStatus};
+use crate::animal::query::{AnimalQuery, AnimalQueryError};
+use crate::animal::repository::{AnimalRepositoryError, DynAnimalRepository};
+use crate::animal::response::FetchAnimalByIdResponse;
+use crate::animal::v2::object::AnimalPageObject;
+use crate::animal::v2::response::{
+    CreateAnimalResponse, ListAnimalsResponse, UpdateAnimalResponse,
+};
+use crate::common::context::Dep;
+use crate::common::results::unified;
+use crate::geo::repository::GeoRepository;
+use crate::tag::object::TagMatch;
+use crate::tag::repository::TagRepository;
+use crate::taxonomy::repository::TaxonRepository;
+use crate::translation::localizer::AnimalLocalizer;
+use crate::version::ApiVersion;
+use poem::i18n::Locale;
+use poem_openapi::OpenApi;
+use poem_openapi::param::{Header, Path, Query};
+use poem_openapi::payload::Json;
+use shared::validation::rules::AnimalRules;
+
+fn default_limit() -> u64 {
+    20
+}
+
+/// Animals as resources: paged listing, one path per animal and writes answering with the
+/// animal.
+pub struct AnimalApiV2;
+
+#[OpenApi(prefix_path = "/animal", tag = "ApiTag::Animal")]
+impl AnimalApiV2 {
+    /// List Animals in the language that best matches `Accept-Language`, a page at a time,
+    /// optionally only those of the given statuses, carrying the given tags or found in an area
+    #[oai(path = "/", method = "get")]
+    #[allow(clippy::too_many_arguments)]
+    async fn list(
+        &self,
+        #[oai(name = "conservation_status", default)] Query(statuses): Query<
+            Vec<ConservationStatus>,
+        >,
+        #[oai(name = "tag", default)] Query(tags): Query<Vec<String>>,
+        #[oai(name = "tag_match")] Query(tag_match): Query<Option<TagMatch>>,
+        /// `west,south,east,north`, animals with a habitat or a sighting in the box
+        #[oai(name = "bbox")]
+        Query(bbox): Query<Option<String>>,
+        /// `longitude,latitude`, animals whose habitat covers the point
+        #[oai(name = "point")]
+        Query(point): Query<Option<String>>,
+        #[oai(name = "offset", default)] Query(offset): Query<u64>,
+        #[oai(
+            name = "limit",
+            default = "default_limit",
+            validator(maximum(value = "100"))
+        )]
+        Query(limit): Query<u64>,
+        #[oai(name = "Accept-Language")] Header(accept_language): Header<Option<String>>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(tag_repository): Dep<TagRepository>,
+        Dep(geo_repository): Dep<GeoRepository>,
+        Dep(localizer): Dep<AnimalLocalizer>,
+        locale: Locale,
+    ) -> ListAnimalsResponse {
+        unified(async {
+            let animals = AnimalQuery {
+                statuses,
+                tags,
+                tag_match,
+                bbox,
+                point,
+            }
+            .animals(
+                animal_repository.as_ref(),
+                &tag_repository,
+                &geo_repository,
+                &locale,
+            )
+            .map_err(|err| match err {
+                AnimalQueryError::GeoQuery(geo_error) => {
+                    ListAnimalsResponse::BadRequest(Json(geo_error))
+                }
+                AnimalQueryError::Repository => ListAnimalsResponse::InternalServerError,
+            })?;
+
+            let total = animals.len() as u64;
+            let mut items: Vec<_> = animals
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .collect();
+            let content_language = localizer
+                .localize(&mut items, accept_language.as_deref())
+                .map_err(|_| ListAnimalsResponse::InternalServerError)?;
+
+            Ok(ListAnimalsResponse::Ok(
+                Json(AnimalPageObject { items, total }),
+                content_language,
+            ))
+        })
+        .await
+    }
+
+    /// Fetch Animal, in the language that best matches `Accept-Language`
+    #[oai(path = "/:id", method = "get")]
+    async fn fetch(
+        &self,
+        Path(id): Path<u64>,
+        #[oai(name = "Accept-Language")] Header(accept_language): Header<Option<String>>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(localizer): Dep<AnimalLocalizer>,
+    ) -> FetchAnimalByIdResponse {
+        unified(async {
+            let mut animal = [animal_repository
+                .fetch_animal_by_id(id as i64)
+                .map_err(|_| FetchAnimalByIdResponse::NotFound)?];
+            let content_language = localizer
+                .localize(&mut animal, accept_language.as_deref())
+                .map_err(|_| FetchAnimalByIdResponse::InternalServerError)?;
+            let [animal] = animal;
+            Ok(FetchAnimalByIdResponse::Ok(Json(animal), content_language))
+        })
+        .await
+    }
+
+    /// Create Animal, answering with it and its path in `Location`
+    #[oai(path = "/", method = "post")]
+    async fn create(
+        &self,
+        Json(animal): Json<AnimalAddUpdateObject>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(taxon_repository): Dep<TaxonRepository>,
+        Dep(rules): Dep<AnimalRules>,
+        locale: Locale,
+    ) -> CreateAnimalResponse {
+        unified(async {
+            animal
+                .to_validate_unique(&rules, animal_repository.as_ref(), None)
+                .await
+                .map_err(|animal_err| {
+                    CreateAnimalResponse::UnprocessableEntity(Json((animal_err, &locale).into()))
+                })?;
+            if let Some(taxon_id) = animal.taxon_id {
+                taxon_repository.fetch_taxon_by_id(taxon_id).map_err(|_| {
+                    CreateAnimalResponse::UnprocessableEntity(Json(
+                        AnimalErrorObject::unknown_taxon(&locale),
+                    ))
+                })?;
+            }
+            let id = animal_repository.add_animal(&animal).map_err(|err| {
+                match err.current_context() {
+                    AnimalRepositoryError::SpeciesTakenError => CreateAnimalResponse::Conflict(
+                        Json(AnimalErrorObject::species_taken(&locale)),
+                    ),
+                    _ => CreateAnimalResponse::BadRequest,
+                }
+            })?;
+            let animal = animal_repository
+                .fetch_animal_by_id(id)
+                .map_err(|_| CreateAnimalResponse::BadRequest)?;
+            let location = format!("{}/animal/{}", ApiVersion::V2.base_path(), id);
+            Ok(CreateAnimalResponse::Created(Json(animal), location))
+        })
+        .await
+    }
+
+    /// Update Animal, answering with it
+    #[oai(path = "/:id", method = "patch")]
+    async fn update(
+        &self,
+        Path(id): Path<u64>,
+        Json(animal): Json<AnimalAddUpdateObject>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(taxon_repository): Dep<TaxonRepository>,
+        Dep(rules): Dep<AnimalRules>,
+        locale: Locale,
+    ) -> UpdateAnimalResponse {
+        unified(async {
+            animal
+                .to_validate_unique(&rules, animal_repository.as_ref(), Some(id as i64))
+                .await
+                .map_err(|animal_error| {
+                    UpdateAnimalResponse::UnprocessableEntity(Json((animal_error, &locale).into()))
+                })?;
+            if let Some(taxon_id) = animal.taxon_id {
+                taxon_repository.fetch_taxon_by_id(taxon_id).map_err(|_| {
+                    UpdateAnimalResponse::UnprocessableEntity(Json(
+                        AnimalErrorObject::unknown_taxon(&locale),
+                    ))
+                })?;
+            }
+            animal_repository
+                .update_animal(&animal, id as i64)
+                .map_err(|err| match err.current_context() {
+                    AnimalRepositoryError::SpeciesTakenError => UpdateAnimalResponse::Conflict(
+                        Json(AnimalErrorObject::species_taken(&locale)),
+                    ),
+                    _ => UpdateAnimalResponse::NotFound,
+                })?;
+            animal_repository
+                .fetch_animal_by_id(id as i64)
+                .map(|animal| UpdateAnimalResponse::Ok(Json(animal)))
+                .map_err(|_| UpdateAnimalResponse::NotFound)
+        })
+        .await
+    }
+}
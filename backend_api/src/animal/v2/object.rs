@@ -0,0 +1,10 @@
+use crate::animal::object::AnimalObject;
+use poem_openapi::Object;
+
+/// A page of the animals matching a query.
+#[derive(Debug, Object)]
+pub struct AnimalPageObject {
+    pub items: Vec<AnimalObject>,
+    /// Animals matching the query, over every page.
+    pub total: u64,
+}
@@ -0,0 +1,44 @@
+use crate::animal::object::{AnimalErrorObject, AnimalObject};
+use crate::animal::v2::object::AnimalPageObject;
+use crate::geo::object::GeoQueryErrorObject;
+use poem_openapi::ApiResponse;
+use poem_openapi::payload::Json;
+
+#[derive(ApiResponse)]
+pub enum ListAnimalsResponse {
+    #[oai(status = 200)]
+    Ok(
+        Json<AnimalPageObject>,
+        #[oai(header = "Content-Language")] String,
+    ),
+    #[oai(status = 400)]
+    BadRequest(Json<GeoQueryErrorObject>),
+    #[oai(status = 500)]
+    InternalServerError,
+}
+
+#[derive(ApiResponse)]
+pub enum CreateAnimalResponse {
+    #[oai(status = 201)]
+    Created(Json<AnimalObject>, #[oai(header = "Location")] String),
+    #[oai(status = 422)]
+    UnprocessableEntity(Json<AnimalErrorObject>),
+    /// Another animal took the species in the meantime
+    #[oai(status = 409)]
+    Conflict(Json<AnimalErrorObject>),
+    #[oai(status = 400)]
+    BadRequest,
+}
+
+#[derive(ApiResponse)]
+pub enum UpdateAnimalResponse {
+    #[oai(status = 200)]
+    Ok(Json<AnimalObject>),
+    #[oai(status = 422)]
+    UnprocessableEntity(Json<AnimalErrorObject>),
+    /// Another animal took the species in the meantime
+    #[oai(status = 409)]
+    Conflict(Json<AnimalErrorObject>),
+    #[oai(status = 404)]
+    NotFound,
+}
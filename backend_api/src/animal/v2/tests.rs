@@ -0,0 +1,85 @@
+use crate::test_support::TestApp;
+use poem::http::StatusCode;
+use serde_json::json;
+
+#[tokio::test]
+async fn list_pages_the_animals() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .get("/v2/animal")
+        .query("offset", &10)
+        .query("limit", &5)
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    let json = res.json().await;
+    let page = json.value().object();
+    page.get("total").assert_i64(12);
+    page.get("items").array().assert_len(2);
+}
+
+#[tokio::test]
+async fn list_limit_is_capped() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .get("/v2/animal")
+        .query("limit", &101)
+        .send()
+        .await;
+    res.assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn fetch_returns_the_animal() {
+    let app = TestApp::builder().build();
+
+    let res = app.client.get("/v2/animal/3").send().await;
+    res.assert_status_is_ok();
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("species")
+        .assert_string("lion");
+}
+
+#[tokio::test]
+async fn create_answers_with_the_animal_and_its_location() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .post("/v2/animal")
+        .body_json(&json!({"species": "otter", "description": "Floats on its back"}))
+        .send()
+        .await;
+    res.assert_status(StatusCode::CREATED);
+    res.assert_header("Location", "/v2/animal/13");
+    let json = res.json().await;
+    let animal = json.value().object();
+    animal.get("id").assert_i64(13);
+    animal.get("species").assert_string("otter");
+}
+
+#[tokio::test]
+async fn update_answers_with_the_animal() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .patch("/v2/animal/1")
+        .body_json(&json!({"species": "wolf", "description": "Howls at the moon"}))
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("species")
+        .assert_string("wolf");
+}
@@ -0,0 +1,2 @@
+INSERT INTO api_key (name, key_hash, tenant_id, created_at)
+VALUES (:name, :key_hash, :tenant_id, :created_at)
\ No newline at end of file
@@ -0,0 +1,3 @@
+SELECT id, name, tenant_id, created_at
+FROM api_key
+WHERE key_hash = :key_hash;
\ No newline at end of file
@@ -0,0 +1,41 @@
+pub mod repository;
+
+use crate::api_key::repository::{ApiKey, ApiKeyRepository};
+use crate::common::context::{Context, ContextError, FromContext};
+use error_stack::Report;
+use poem::http::StatusCode;
+
+pub const API_KEY_HEADER: &str = "X-Api-Key";
+
+/// The key sent in `X-Api-Key`, which must exist.
+pub struct RequestApiKey(pub ApiKey);
+
+impl FromContext for RequestApiKey {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let key = ctx.req.header(API_KEY_HEADER).ok_or_else(|| {
+            ContextError::Status(StatusCode::UNAUTHORIZED, "Missing API key".to_string())
+        })?;
+        let api_key_repository = ApiKeyRepository::from_context(ctx).await?;
+        let api_key = api_key_repository.fetch_api_key(key).map_err(|_| {
+            ContextError::Status(StatusCode::UNAUTHORIZED, "Invalid API key".to_string())
+        })?;
+        Ok(Self(api_key))
+    }
+}
+
+/// A platform key, one that belongs to no tenant; required by the admin endpoints.
+pub struct AdminApiKey(pub ApiKey);
+
+impl FromContext for AdminApiKey {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let RequestApiKey(api_key) = RequestApiKey::from_context(ctx).await?;
+        if api_key.tenant_id.is_some() {
+            return Err(ContextError::Status(
+                StatusCode::FORBIDDEN,
+                "Not a platform API key".to_string(),
+            )
+            .into());
+        }
+        Ok(Self(api_key))
+    }
+}
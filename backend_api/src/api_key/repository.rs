@@ -0,0 +1,116 @@
+use crate::common::context::{Context, ContextError, FromContext};
+use crate::common::db::{DefaultConnection, SqliteClient};
+use chrono::{DateTime, Utc};
+use error_stack::{Report, ResultExt};
+use rusqlite::named_params;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ApiKeyRepositoryError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Row Value error")]
+    RowValueError,
+    #[error("Lock error")]
+    LockError,
+    #[error("Not found error")]
+    NotFoundError,
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiKey {
+    pub id: i64,
+    pub name: String,
+    /// Keys without a tenant are platform keys and may use the admin endpoints.
+    pub tenant_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Only the hash of a key is stored, so a leaked database does not leak usable keys.
+fn hash_key(key: &str) -> String {
+    to_hex(&Sha256::digest(key.as_bytes()))
+}
+
+pub struct ApiKeyRepository {
+    sqlite_client: SqliteClient,
+}
+
+impl ApiKeyRepository {
+    pub fn new(sqlite_client: SqliteClient) -> Self {
+        Self { sqlite_client }
+    }
+
+    /// Create a key and return it in plain text; it cannot be recovered afterwards.
+    pub fn create_api_key(
+        &self,
+        name: &str,
+        tenant_id: Option<i64>,
+    ) -> Result<String, Report<ApiKeyRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| ApiKeyRepositoryError::LockError)?;
+
+        let key = format!("ak_{}", to_hex(&rand::random::<[u8; 32]>()));
+
+        conn.execute(
+            include_str!("_sql/add_api_key.sql"),
+            named_params! {
+                ":name": name,
+                ":key_hash": hash_key(&key),
+                ":tenant_id": tenant_id,
+                ":created_at": Utc::now(),
+            },
+        )
+        .change_context(ApiKeyRepositoryError::QueryError)?;
+
+        Ok(key)
+    }
+
+    pub fn fetch_api_key(&self, key: &str) -> Result<ApiKey, Report<ApiKeyRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| ApiKeyRepositoryError::LockError)?;
+
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_api_key_by_hash.sql"))
+            .change_context(ApiKeyRepositoryError::QueryError)?;
+
+        let item_iter = stmt.query_map(
+            named_params! {
+                ":key_hash": hash_key(key),
+            },
+            |row| {
+                Ok(ApiKey {
+                    id: row.get("id")?,
+                    name: row.get("name")?,
+                    tenant_id: row.get("tenant_id")?,
+                    created_at: row.get("created_at")?,
+                })
+            },
+        );
+
+        let item = item_iter
+            .change_context(ApiKeyRepositoryError::QueryError)?
+            .next()
+            .ok_or(ApiKeyRepositoryError::NotFoundError)?;
+
+        Ok(item.change_context(ApiKeyRepositoryError::RowValueError)?)
+    }
+}
+
+impl FromContext for ApiKeyRepository {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(
+            SqliteClient::<DefaultConnection>::from_context(ctx).await?,
+        ))
+    }
+}
@@ -0,0 +1,78 @@
+pub mod object;
+pub mod response;
+pub mod store;
+#[cfg(test)]
+mod tests;
+
+use crate::ApiTag;
+use crate::api_key::AdminApiKey;
+use crate::backup::response::{CreateBackupResponse, FetchAllBackupsResponse};
+use crate::backup::store::BackupStores;
+use crate::common::context::Dep;
+use crate::common::results::unified;
+use crate::tenant::pool::TenantPool;
+use crate::tenant::repository::TenantRepository;
+use chrono::Utc;
+use poem_openapi::OpenApi;
+use poem_openapi::param::Query;
+use poem_openapi::payload::Json;
+
+pub struct BackupApi;
+
+#[OpenApi(prefix_path = "/admin/backup", tag = "ApiTag::Backup")]
+impl BackupApi {
+    /// Fetch All Backups of the main database, or of the database of `tenant`, newest first
+    #[oai(path = "/", method = "get")]
+    async fn index(
+        &self,
+        #[oai(name = "tenant")] Query(tenant): Query<Option<String>>,
+        Dep(_admin): Dep<AdminApiKey>,
+        Dep(backup_stores): Dep<BackupStores>,
+        Dep(tenant_repository): Dep<TenantRepository>,
+    ) -> FetchAllBackupsResponse {
+        unified(async {
+            if let Some(slug) = &tenant {
+                tenant_repository
+                    .fetch_tenant_by_slug(slug)
+                    .map_err(|_| FetchAllBackupsResponse::NotFound)?;
+            }
+            backup_stores
+                .store(tenant.as_deref())
+                .list()
+                .map(|backups| FetchAllBackupsResponse::Ok(Json(backups)))
+                .map_err(|_| FetchAllBackupsResponse::InternalServerError)
+        })
+        .await
+    }
+
+    /// Back up the main database, or the database of `tenant`, now, removing its oldest
+    /// backups past `backup.kept`
+    #[oai(path = "/", method = "post")]
+    async fn create(
+        &self,
+        #[oai(name = "tenant")] Query(tenant): Query<Option<String>>,
+        Dep(_admin): Dep<AdminApiKey>,
+        Dep(backup_stores): Dep<BackupStores>,
+        Dep(tenant_repository): Dep<TenantRepository>,
+        Dep(tenant_pool): Dep<TenantPool>,
+    ) -> CreateBackupResponse {
+        unified(async {
+            if let Some(slug) = &tenant {
+                tenant_repository
+                    .fetch_tenant_by_slug(slug)
+                    .map_err(|_| CreateBackupResponse::NotFound)?;
+                // Creates the database of a tenant that never had a request.
+                tenant_pool
+                    .client(slug)
+                    .map_err(|_| CreateBackupResponse::InternalServerError)?;
+            }
+            let backup_store = backup_stores.store(tenant.as_deref());
+            tokio::task::spawn_blocking(move || backup_store.create(Utc::now()))
+                .await
+                .map_err(|_| CreateBackupResponse::InternalServerError)?
+                .map(|backup| CreateBackupResponse::Created(Json(backup)))
+                .map_err(|_| CreateBackupResponse::InternalServerError)
+        })
+        .await
+    }
+}
@@ -0,0 +1,13 @@
+use chrono::{DateTime, Utc};
+use poem_openapi::Object;
+
+#[derive(Debug, Object, Clone)]
+pub struct BackupObject {
+    /// Name of the file in `backup.directory`, or in `backup.directory/tenants/<slug>` for a
+    /// tenant's database; the argument `backend_api restore` takes.
+    pub file_name: String,
+    pub byte_size: u64,
+    /// Hex SHA-256 of the file, also kept next to it in `<file_name>.sha256`.
+    pub sha256: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,25 @@
+use crate::backup::object::BackupObject;
+use poem_openapi::ApiResponse;
+use poem_openapi::payload::Json;
+
+#[derive(ApiResponse)]
+pub enum FetchAllBackupsResponse {
+    #[oai(status = 200)]
+    Ok(Json<Vec<BackupObject>>),
+    /// No tenant goes by `tenant`
+    #[oai(status = 404)]
+    NotFound,
+    #[oai(status = 500)]
+    InternalServerError,
+}
+
+#[derive(ApiResponse)]
+pub enum CreateBackupResponse {
+    #[oai(status = 201)]
+    Created(Json<BackupObject>),
+    /// No tenant goes by `tenant`
+    #[oai(status = 404)]
+    NotFound,
+    #[oai(status = 500)]
+    InternalServerError,
+}
@@ -0,0 +1,334 @@
+use crate::backup::object::BackupObject;
+use crate::common::config::Config;
+use crate::common::config::backup::BackupConfig;
+use crate::common::config::tenant::TenantConfig;
+use crate::common::context::{Context, ContextError, FromContext};
+use crate::common::db::migration::{MIGRATIONS, Migration, TENANT_MIGRATIONS, migrate_up};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use error_stack::{Report, ResultExt};
+use rusqlite::backup::Progress;
+use rusqlite::{Connection, MAIN_DB, OpenFlags};
+use sha2::{Digest, Sha256};
+use std::cmp::Reverse;
+use std::fs::{self, File};
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum BackupError {
+    #[error("IO error")]
+    IoError,
+    #[error("Query error")]
+    QueryError,
+    #[error("Checksum mismatch error")]
+    ChecksumMismatchError,
+    #[error("Integrity error")]
+    IntegrityError,
+    #[error("Schema error")]
+    SchemaError,
+}
+
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%3fZ";
+
+/// The `.sha256` file kept next to the backup at `path`.
+fn checksum_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".sha256");
+    PathBuf::from(name)
+}
+
+fn sha256_of(path: &Path) -> Result<String, Report<BackupError>> {
+    let mut file = File::open(path)
+        .change_context(BackupError::IoError)
+        .attach_printable_lazy(|| format!("Opening {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)
+        .change_context(BackupError::IoError)
+        .attach_printable_lazy(|| format!("Reading {}", path.display()))?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Open the database at `path` read-only and check that it is intact and that its schema is
+/// not newer than `migrations` of this build.
+fn check_database(
+    path: &Path,
+    migrations: &'static [Migration],
+) -> Result<(), Report<BackupError>> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .change_context(BackupError::QueryError)
+        .attach_printable_lazy(|| format!("Opening {}", path.display()))?;
+
+    let integrity: String = conn
+        .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .change_context(BackupError::IntegrityError)?;
+    if integrity != "ok" {
+        return Err(Report::new(BackupError::IntegrityError).attach_printable(integrity));
+    }
+
+    let version: i64 = conn
+        .query_row(
+            "SELECT COALESCE(MAX(version), 0) FROM schema_migration",
+            [],
+            |row| row.get(0),
+        )
+        .change_context(BackupError::SchemaError)
+        .attach_printable("Not a database of this server")?;
+    let latest = migrations.last().map_or(0, |migration| migration.version);
+    if version > latest {
+        return Err(
+            Report::new(BackupError::SchemaError).attach_printable(format!(
+                "Schema version {} is newer than the {} of this build",
+                version, latest
+            )),
+        );
+    }
+    Ok(())
+}
+
+/// Backups of a database, kept in a directory as `<stem>-<timestamp>.db`: `backup.directory`
+/// for the main database, `backup.directory/tenants/<slug>` for a tenant's.
+pub struct BackupStore {
+    config: Arc<BackupConfig>,
+    directory: PathBuf,
+    sqlite_path: PathBuf,
+    stem: String,
+    migrations: &'static [Migration],
+}
+
+impl BackupStore {
+    fn with_directory(
+        config: Arc<BackupConfig>,
+        directory: PathBuf,
+        sqlite_path: PathBuf,
+        migrations: &'static [Migration],
+    ) -> Self {
+        let stem = sqlite_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("sqlite")
+            .to_string();
+        Self {
+            config,
+            directory,
+            sqlite_path,
+            stem,
+            migrations,
+        }
+    }
+
+    /// Backups of the main database at `sqlite_path`, named after its file.
+    pub fn new(config: Arc<BackupConfig>, sqlite_path: &str) -> Self {
+        let directory = PathBuf::from(&config.directory);
+        Self::with_directory(config, directory, PathBuf::from(sqlite_path), MIGRATIONS)
+    }
+
+    /// Backups of the database of the tenant `slug`.
+    pub fn for_tenant(config: Arc<BackupConfig>, tenant_config: &TenantConfig, slug: &str) -> Self {
+        let directory = Path::new(&config.directory).join("tenants").join(slug);
+        let sqlite_path = tenant_config.database_path(slug);
+        Self::with_directory(config, directory, sqlite_path, TENANT_MIGRATIONS)
+    }
+
+    fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// When the backup named `file_name` was taken, `None` for other files.
+    fn created_at(&self, file_name: &str) -> Option<DateTime<Utc>> {
+        let timestamp = file_name
+            .strip_prefix(&self.stem)?
+            .strip_prefix('-')?
+            .strip_suffix(".db")?;
+        NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT)
+            .ok()
+            .map(|created_at| created_at.and_utc())
+    }
+
+    fn describe(
+        &self,
+        file_name: String,
+        created_at: DateTime<Utc>,
+    ) -> Result<BackupObject, Report<BackupError>> {
+        let path = self.directory().join(&file_name);
+        let byte_size = fs::metadata(&path)
+            .change_context(BackupError::IoError)
+            .attach_printable_lazy(|| format!("Reading {}", path.display()))?
+            .len();
+        let sha256 = fs::read_to_string(checksum_path(&path))
+            .change_context(BackupError::IoError)
+            .attach_printable_lazy(|| format!("Reading the checksum of {}", path.display()))?
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        Ok(BackupObject {
+            file_name,
+            byte_size,
+            sha256,
+            created_at,
+        })
+    }
+
+    /// Copy the database `conn` is open on with the online backup API, which reads a
+    /// consistent snapshot while other connections keep writing. The copy is written under a
+    /// temporary name and only renamed into place once checked, next to its checksum.
+    fn write(
+        &self,
+        conn: &Connection,
+        now: DateTime<Utc>,
+    ) -> Result<BackupObject, Report<BackupError>> {
+        let directory = self.directory();
+        fs::create_dir_all(directory)
+            .change_context(BackupError::IoError)
+            .attach_printable_lazy(|| format!("Creating {}", directory.display()))?;
+
+        let file_name = format!("{}-{}.db", self.stem, now.format(TIMESTAMP_FORMAT));
+        let path = directory.join(&file_name);
+        let partial = directory.join(format!("{}.partial", file_name));
+        conn.backup(MAIN_DB, &partial, None)
+            .and_then(|()| {
+                // The copy is in write-ahead log mode like the database, which would leave a
+                // log and shared memory file next to it once read.
+                Connection::open(&partial)?.pragma_update(None, "journal_mode", "DELETE")
+            })
+            .change_context(BackupError::QueryError)
+            .attach_printable_lazy(|| format!("Backing up to {}", partial.display()))?;
+        if let Err(report) = check_database(&partial, self.migrations) {
+            let _ = fs::remove_file(&partial);
+            return Err(report);
+        }
+
+        let sha256 = sha256_of(&partial)?;
+        fs::write(checksum_path(&path), format!("{}  {}\n", sha256, file_name))
+            .change_context(BackupError::IoError)
+            .attach_printable_lazy(|| format!("Writing the checksum of {}", path.display()))?;
+        fs::rename(&partial, &path)
+            .change_context(BackupError::IoError)
+            .attach_printable_lazy(|| format!("Renaming to {}", path.display()))?;
+        self.describe(file_name, now)
+    }
+
+    /// Remove all but the newest `backup.kept` backups, with their checksums.
+    fn rotate(&self) -> Result<(), Report<BackupError>> {
+        let backups = self.list()?;
+        for backup in backups.iter().skip(self.config.kept) {
+            let path = self.directory().join(&backup.file_name);
+            for path in [checksum_path(&path), path] {
+                fs::remove_file(&path)
+                    .change_context(BackupError::IoError)
+                    .attach_printable_lazy(|| format!("Removing {}", path.display()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Back up the database from a read-only connection of its own, which leaves the shared
+    /// one free for requests, then rotate the backups.
+    pub fn create(&self, now: DateTime<Utc>) -> Result<BackupObject, Report<BackupError>> {
+        let conn = Connection::open_with_flags(&self.sqlite_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .change_context(BackupError::QueryError)
+            .attach_printable_lazy(|| format!("Opening {}", self.sqlite_path.display()))?;
+        let backup = self.write(&conn, now)?;
+        self.rotate()?;
+        Ok(backup)
+    }
+
+    /// Every backup of the database, newest first.
+    pub fn list(&self) -> Result<Vec<BackupObject>, Report<BackupError>> {
+        let entries = match fs::read_dir(self.directory()) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(Report::new(err).change_context(BackupError::IoError)),
+        };
+
+        let mut backups = Vec::new();
+        for entry in entries {
+            let file_name = entry
+                .change_context(BackupError::IoError)?
+                .file_name()
+                .to_string_lossy()
+                .to_string();
+            if let Some(created_at) = self.created_at(&file_name) {
+                backups.push(self.describe(file_name, created_at)?);
+            }
+        }
+        backups.sort_by_key(|backup| Reverse(backup.created_at));
+        Ok(backups)
+    }
+
+    /// Check the backup at `path` against its checksum file, then check it as a database.
+    pub fn verify(&self, path: &Path) -> Result<(), Report<BackupError>> {
+        let expected = fs::read_to_string(checksum_path(path))
+            .change_context(BackupError::ChecksumMismatchError)
+            .attach_printable_lazy(|| format!("No checksum file for {}", path.display()))?;
+        let expected = expected.split_whitespace().next().unwrap_or_default();
+        let actual = sha256_of(path)?;
+        if actual != expected {
+            return Err(
+                Report::new(BackupError::ChecksumMismatchError).attach_printable(format!(
+                    "{} has SHA-256 {}, expected {}",
+                    path.display(),
+                    actual,
+                    expected
+                )),
+            );
+        }
+        check_database(path, self.migrations)
+    }
+
+    /// Replace the contents of the database `conn` is open on with the backup at `path`,
+    /// once verified, and bring its schema up to date. The contents replaced are backed up
+    /// first; that backup is returned.
+    pub fn restore(
+        &self,
+        conn: &mut Connection,
+        path: &Path,
+        now: DateTime<Utc>,
+    ) -> Result<BackupObject, Report<BackupError>> {
+        self.verify(path)?;
+        // Rotated only once restored, so the backup being restored is not removed first.
+        let previous = self.write(conn, now)?;
+        conn.restore(MAIN_DB, path, None::<fn(Progress)>)
+            .change_context(BackupError::QueryError)
+            .attach_printable_lazy(|| format!("Restoring {}", path.display()))?;
+        migrate_up(conn, self.migrations).change_context(BackupError::SchemaError)?;
+        self.rotate()?;
+        Ok(previous)
+    }
+}
+
+/// The backups of the main database and of the tenant databases.
+pub struct BackupStores {
+    config: Arc<BackupConfig>,
+    tenant_config: Arc<TenantConfig>,
+    sqlite_path: String,
+}
+
+impl BackupStores {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            config: Arc::clone(&config.backup),
+            tenant_config: Arc::clone(&config.tenant),
+            sqlite_path: config.sqlite.path.clone(),
+        }
+    }
+
+    /// Backups of the database of the tenant `slug`, of the main database without one.
+    pub fn store(&self, slug: Option<&str>) -> BackupStore {
+        match slug {
+            Some(slug) => {
+                BackupStore::for_tenant(Arc::clone(&self.config), &self.tenant_config, slug)
+            }
+            None => BackupStore::new(Arc::clone(&self.config), &self.sqlite_path),
+        }
+    }
+}
+
+impl FromContext for BackupStores {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let config = ctx.config.upgrade().ok_or(ContextError::ConfigError)?;
+        Ok(Self::new(&config))
+    }
+}
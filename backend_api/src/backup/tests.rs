@@ -0,0 +1,230 @@
+use crate::api_key::repository::ApiKeyRepository;
+use crate::backup::store::{BackupError, BackupStore};
+use crate::common::config::Config;
+use crate::common::config::backup::BackupConfig;
+use crate::common::config::sqlite::SqliteConfig;
+use crate::common::config::tenant::TenantConfig;
+use crate::common::db::{DefaultConnection, SqliteClient};
+use crate::test_support::TestApp;
+use chrono::{TimeDelta, Utc};
+use poem::http::StatusCode;
+use serde_json::json;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+
+fn api_key_count(sqlite_client: &SqliteClient) -> i64 {
+    sqlite_client
+        .get_conn()
+        .lock()
+        .expect("lock")
+        .query_row("SELECT COUNT(*) FROM api_key", [], |row| row.get(0))
+        .expect("count")
+}
+
+#[tokio::test]
+async fn backup_endpoints_create_and_list_for_platform_keys() {
+    let temp_dir = TempDir::new().expect("directory");
+    let directory = temp_dir.path();
+    let sqlite_path = directory.join("sqlite.db").to_string_lossy().to_string();
+    let app = TestApp::builder()
+        .config(Config {
+            sqlite: Arc::new(SqliteConfig {
+                path: sqlite_path.clone(),
+            }),
+            backup: Arc::new(BackupConfig {
+                directory: directory.join("backups").to_string_lossy().to_string(),
+                kept: 7,
+            }),
+            ..Config::default()
+        })
+        .sqlite_client(SqliteClient::new(sqlite_path).expect("database"))
+        .build();
+
+    app.client
+        .post("/admin/backup")
+        .send()
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    let admin_key = ApiKeyRepository::new(app.sqlite_client.clone())
+        .create_api_key("admin", None)
+        .expect("admin key");
+    let res = app
+        .client
+        .post("/admin/backup")
+        .header("X-Api-Key", &admin_key)
+        .send()
+        .await;
+    res.assert_status(StatusCode::CREATED);
+    let json = res.json().await;
+    let created = json.value().object();
+    let file_name = created.get("file_name").string().to_string();
+    let sha256 = created.get("sha256").string().to_string();
+    assert!(file_name.starts_with("sqlite-"));
+    let checksum = std::fs::read_to_string(
+        directory
+            .join("backups")
+            .join(format!("{}.sha256", file_name)),
+    )
+    .expect("checksum file");
+    assert_eq!(checksum, format!("{}  {}\n", sha256, file_name));
+
+    let res = app
+        .client
+        .get("/admin/backup")
+        .header("X-Api-Key", &admin_key)
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    let json = res.json().await;
+    let backups = json.value().array();
+    backups.assert_len(1);
+    backups
+        .get(0)
+        .object()
+        .get("file_name")
+        .assert_string(&file_name);
+}
+
+#[tokio::test]
+async fn tenant_backups_go_into_a_directory_of_their_own() {
+    let temp_dir = TempDir::new().expect("directory");
+    let directory = temp_dir.path();
+    let app = TestApp::builder()
+        .config(Config {
+            backup: Arc::new(BackupConfig {
+                directory: directory.join("backups").to_string_lossy().to_string(),
+                kept: 7,
+            }),
+            tenant: Arc::new(TenantConfig {
+                enabled: true,
+                directory: directory.join("tenants").to_string_lossy().to_string(),
+                ..TenantConfig::default()
+            }),
+            ..Config::default()
+        })
+        .build();
+    let admin_key = ApiKeyRepository::new(app.sqlite_client.clone())
+        .create_api_key("admin", None)
+        .expect("admin key");
+    app.client
+        .post("/admin/tenant/add")
+        .header("X-Api-Key", &admin_key)
+        .body_json(&json!({"slug": "zoo-a", "name": "Zoo A"}))
+        .send()
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    app.client
+        .post("/admin/backup")
+        .query("tenant", &"zoo-b")
+        .header("X-Api-Key", &admin_key)
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+
+    let res = app
+        .client
+        .post("/admin/backup")
+        .query("tenant", &"zoo-a")
+        .header("X-Api-Key", &admin_key)
+        .send()
+        .await;
+    res.assert_status(StatusCode::CREATED);
+    let json = res.json().await;
+    let file_name = json.value().object().get("file_name").string().to_string();
+    assert!(file_name.starts_with("zoo-a-"));
+    assert!(
+        directory
+            .join("backups")
+            .join("tenants")
+            .join("zoo-a")
+            .join(&file_name)
+            .exists()
+    );
+
+    let res = app
+        .client
+        .get("/admin/backup")
+        .query("tenant", &"zoo-a")
+        .header("X-Api-Key", &admin_key)
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    res.json().await.value().array().assert_len(1);
+}
+
+#[test]
+fn restore_brings_back_the_backed_up_database() {
+    let temp_dir = TempDir::new().expect("directory");
+    let directory = temp_dir.path();
+    let sqlite_path = directory.join("live.db").to_string_lossy().to_string();
+    let sqlite_client = SqliteClient::new(sqlite_path.clone()).expect("database");
+    let backup_store = BackupStore::new(
+        Arc::new(BackupConfig {
+            directory: directory.join("backups").to_string_lossy().to_string(),
+            kept: 7,
+        }),
+        &sqlite_path,
+    );
+    let api_key_repository = ApiKeyRepository::new(sqlite_client.clone());
+    api_key_repository
+        .create_api_key("kept", None)
+        .expect("api key");
+    let now = Utc::now();
+    let backup = backup_store.create(now).expect("backup");
+    assert!(backup.file_name.starts_with("live-"));
+    api_key_repository
+        .create_api_key("lost", None)
+        .expect("api key");
+    assert_eq!(api_key_count(&sqlite_client), 2);
+
+    let previous = backup_store
+        .restore(
+            &mut sqlite_client.get_conn().lock().expect("lock"),
+            &directory.join("backups").join(&backup.file_name),
+            now + TimeDelta::seconds(1),
+        )
+        .expect("restore");
+
+    assert_eq!(api_key_count(&sqlite_client), 1);
+    let backups = backup_store.list().expect("backups");
+    assert_eq!(backups.len(), 2);
+    assert_eq!(backups[0].file_name, previous.file_name);
+}
+
+#[test]
+fn tampered_backups_are_not_restored() {
+    let temp_dir = TempDir::new().expect("directory");
+    let directory = temp_dir.path();
+    let sqlite_path = directory.join("sqlite.db").to_string_lossy().to_string();
+    SqliteClient::<DefaultConnection>::new(sqlite_path.clone()).expect("database");
+    let backup_store = BackupStore::new(
+        Arc::new(BackupConfig {
+            directory: directory.to_string_lossy().to_string(),
+            kept: 7,
+        }),
+        &sqlite_path,
+    );
+    let backup = backup_store.create(Utc::now()).expect("backup");
+    let path = directory.join(&backup.file_name);
+    backup_store.verify(&path).expect("intact backup");
+
+    let mut bytes = std::fs::read(&path).expect("backup");
+    bytes.push(0);
+    std::fs::write(&path, bytes).expect("tamper");
+    let err = backup_store.verify(&path).expect_err("tampered backup");
+    assert!(matches!(
+        err.current_context(),
+        BackupError::ChecksumMismatchError
+    ));
+
+    std::fs::remove_file(directory.join(format!("{}.sha256", backup.file_name)))
+        .expect("remove checksum");
+    let err = backup_store.verify(&path).expect_err("no checksum");
+    assert!(matches!(
+        err.current_context(),
+        BackupError::ChecksumMismatchError
+    ));
+}
@@ -0,0 +1,84 @@
+use crate::animal::object::{AnimalAddUpdateObject, AnimalObject};
+use crate::animal::repository::AnimalRepository;
+use crate::animal::repository::sqlite::SqliteAnimalRepository;
+use crate::cli::{CliError, animal_sqlite_client, config};
+use error_stack::{Report, ResultExt};
+use std::path::{Path, PathBuf};
+
+fn read_json<T: serde::de::DeserializeOwned>(file: &Path) -> Result<T, Report<CliError>> {
+    let content = std::fs::read_to_string(file)
+        .change_context(CliError::IoError)
+        .attach_printable_lazy(|| format!("Unable to read {}", file.display()))?;
+    serde_json::from_str(&content)
+        .change_context(CliError::DataError)
+        .attach_printable_lazy(|| format!("Unable to parse {}", file.display()))
+}
+
+/// Add every animal in `file`, a JSON array of `{ "species", "description" }`, after
+/// validating all of them.
+pub async fn seed(file: PathBuf, tenant: Option<String>) -> Result<(), Report<CliError>> {
+    let animals: Vec<AnimalAddUpdateObject> = read_json(&file)?;
+    let rules = config().await?.validation.animal_rules();
+    for (index, animal) in animals.iter().enumerate() {
+        animal.to_validate(&rules).map_err(|err| {
+            Report::new(CliError::DataError)
+                .attach_printable(format!("Entry {} did not validate: {:?}", index, err))
+        })?;
+    }
+
+    let animal_repository = SqliteAnimalRepository::new(animal_sqlite_client(&tenant).await?);
+    for animal in animals.iter() {
+        animal_repository
+            .add_animal(animal)
+            .change_context(CliError::DatabaseError)?;
+    }
+    println!("Seeded {} animals", animals.len());
+    Ok(())
+}
+
+pub async fn export(
+    output: Option<PathBuf>,
+    tenant: Option<String>,
+) -> Result<(), Report<CliError>> {
+    let animal_repository = SqliteAnimalRepository::new(animal_sqlite_client(&tenant).await?);
+    let animals = animal_repository
+        .fetch_all_animals()
+        .change_context(CliError::DatabaseError)?;
+    let json = serde_json::to_string_pretty(&animals).change_context(CliError::DataError)?;
+
+    match output {
+        Some(output) => std::fs::write(&output, json)
+            .change_context(CliError::IoError)
+            .attach_printable_lazy(|| format!("Unable to write {}", output.display()))?,
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+/// Restore the animals of an `export`, replacing any animal with the same id.
+pub async fn import(file: PathBuf, tenant: Option<String>) -> Result<(), Report<CliError>> {
+    let animals: Vec<AnimalObject> = read_json(&file)?;
+    let rules = config().await?.validation.animal_rules();
+    for animal in animals.iter() {
+        AnimalAddUpdateObject {
+            species: animal.species.clone(),
+            description: animal.description.clone(),
+            taxon_id: animal.taxon_id,
+            conservation_status: Some(animal.conservation_status),
+        }
+        .to_validate(&rules)
+        .map_err(|err| {
+            Report::new(CliError::DataError)
+                .attach_printable(format!("Animal {} did not validate: {:?}", animal.id, err))
+        })?;
+    }
+
+    let animal_repository = SqliteAnimalRepository::new(animal_sqlite_client(&tenant).await?);
+    for animal in animals.iter() {
+        animal_repository
+            .import_animal(animal)
+            .change_context(CliError::DatabaseError)?;
+    }
+    println!("Imported {} animals", animals.len());
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use crate::api_key::repository::ApiKeyRepository;
+use crate::cli::{CliError, find_tenant, sqlite_client};
+use error_stack::{Report, ResultExt};
+
+/// Create a key for `tenant`, or a platform key that can use the admin endpoints.
+pub async fn create_api_key(name: String, tenant: Option<String>) -> Result<(), Report<CliError>> {
+    let tenant_id = match tenant {
+        Some(slug) => Some(find_tenant(&slug).await?.id),
+        None => None,
+    };
+    let api_key_repository = ApiKeyRepository::new(sqlite_client().await?);
+    let key = api_key_repository
+        .create_api_key(&name, tenant_id)
+        .change_context(CliError::DatabaseError)?;
+    println!("Created API key '{}': {}", name, key);
+    println!("Store it now, it cannot be shown again.");
+    Ok(())
+}
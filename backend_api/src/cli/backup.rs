@@ -0,0 +1,56 @@
+use crate::backup::object::BackupObject;
+use crate::backup::store::{BackupStore, BackupStores};
+use crate::cli::{CliError, config, find_tenant, sqlite_client};
+use crate::common::db::{ConnectionMarker, SqliteClient};
+use crate::tenant::pool::TenantPool;
+use chrono::Utc;
+use error_stack::{Report, ResultExt};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+fn restore_into<T: ConnectionMarker>(
+    sqlite_client: &SqliteClient<T>,
+    backup_store: &BackupStore,
+    file: &Path,
+) -> Result<BackupObject, Report<CliError>> {
+    let mut conn = sqlite_client
+        .get_conn()
+        .lock()
+        .map_err(|_| CliError::DatabaseError)?;
+    backup_store
+        .restore(&mut conn, file, Utc::now())
+        .change_context(CliError::DatabaseError)
+}
+
+/// Restore the database of `tenant`, or the main database without one, from the backup at
+/// `file`, backing up what it replaces.
+pub async fn restore(file: PathBuf, tenant: Option<String>) -> Result<(), Report<CliError>> {
+    let config = config().await?;
+    let backup_store = BackupStores::new(&config).store(tenant.as_deref());
+    let (previous, database) = match &tenant {
+        None => (
+            restore_into(&sqlite_client().await?, &backup_store, &file)?,
+            config.sqlite.path.clone(),
+        ),
+        Some(slug) => {
+            if !config.tenant.enabled {
+                return Err(Report::new(CliError::ConfigError)
+                    .attach_printable("--tenant needs multi-tenant mode, set tenant.enabled"));
+            }
+            find_tenant(slug).await?;
+            let tenant_client = TenantPool::new(Arc::clone(&config.tenant))
+                .client(slug)
+                .change_context(CliError::DatabaseError)?;
+            (
+                restore_into(&tenant_client, &backup_store, &file)?,
+                config.tenant.database_path(slug).display().to_string(),
+            )
+        }
+    };
+    println!("Restored {} from {}", database, file.display());
+    println!(
+        "The replaced database was backed up as {}",
+        previous.file_name
+    );
+    Ok(())
+}
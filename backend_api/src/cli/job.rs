@@ -0,0 +1,14 @@
+use crate::cli::{CliError, sqlite_client};
+use crate::jobs::object::JobKind;
+use crate::jobs::repository::JobRepository;
+use chrono::Utc;
+use error_stack::{Report, ResultExt};
+
+/// Queue a one-shot job, run by the next server scheduler that polls the queue.
+pub async fn queue_job(kind: JobKind) -> Result<(), Report<CliError>> {
+    let id = JobRepository::new(sqlite_client().await?)
+        .queue_job(kind, Utc::now())
+        .change_context(CliError::DatabaseError)?;
+    println!("Queued job {} ({})", id, kind.as_str());
+    Ok(())
+}
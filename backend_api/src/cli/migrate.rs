@@ -0,0 +1,83 @@
+use crate::cli::{CliError, MigrateCommand, config, find_tenant};
+use crate::common::db::migration::{migrate_down, migrate_up, migration_status};
+use crate::common::db::{ConnectionMarker, DefaultConnection, SqliteClient, TenantConnection};
+use error_stack::{Report, ResultExt};
+
+/// Migrate the main database, or the database of `tenant` in multi-tenant mode.
+pub async fn migrate(
+    action: MigrateCommand,
+    tenant: Option<String>,
+) -> Result<(), Report<CliError>> {
+    let config = config().await?;
+    match tenant {
+        None => run(
+            SqliteClient::<DefaultConnection>::open(config.sqlite.path.clone())
+                .change_context(CliError::DatabaseError)?,
+            action,
+        ),
+        Some(slug) => {
+            find_tenant(&slug).await?;
+            std::fs::create_dir_all(&config.tenant.directory).change_context(CliError::IoError)?;
+            run(
+                SqliteClient::<TenantConnection>::open(
+                    config
+                        .tenant
+                        .database_path(&slug)
+                        .to_string_lossy()
+                        .to_string(),
+                )
+                .change_context(CliError::DatabaseError)?,
+                action,
+            )
+        }
+    }
+}
+
+fn run<T: ConnectionMarker>(
+    sqlite_client: SqliteClient<T>,
+    action: MigrateCommand,
+) -> Result<(), Report<CliError>> {
+    let mut conn = sqlite_client
+        .get_conn()
+        .lock()
+        .map_err(|_| CliError::DatabaseError)?;
+
+    match action {
+        MigrateCommand::Up => {
+            let applied =
+                migrate_up(&mut conn, T::migrations()).change_context(CliError::DatabaseError)?;
+            if applied.is_empty() {
+                println!("Nothing to apply");
+            }
+            for migration in applied {
+                println!("Applied {:04} {}", migration.version, migration.name);
+            }
+        }
+        MigrateCommand::Down { steps } => {
+            let reverted = migrate_down(&mut conn, T::migrations(), steps)
+                .change_context(CliError::DatabaseError)?;
+            if reverted.is_empty() {
+                println!("Nothing to revert");
+            }
+            for migration in reverted {
+                println!("Reverted {:04} {}", migration.version, migration.name);
+            }
+        }
+        MigrateCommand::Status => {
+            for status in
+                migration_status(&conn, T::migrations()).change_context(CliError::DatabaseError)?
+            {
+                let applied = status
+                    .applied_at
+                    .map(|at| format!("applied {}", at.to_rfc3339()))
+                    .unwrap_or_else(|| "pending".to_string());
+                println!(
+                    "{:04} {:<20} {}",
+                    status.migration.version, status.migration.name, applied
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
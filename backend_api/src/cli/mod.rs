@@ -0,0 +1,169 @@
+use crate::common::config::Config;
+use crate::common::db::{SqliteClient, TenantConnection};
+use crate::jobs::object::JobKind;
+use crate::tenant::object::TenantObject;
+use crate::tenant::pool::TenantPool;
+use crate::tenant::repository::TenantRepository;
+use crate::validation::spec::spec_with_rules;
+use crate::version::ApiVersion;
+use clap::{Parser, Subcommand, ValueEnum};
+use error_stack::{Report, ResultExt};
+use std::path::PathBuf;
+use std::sync::Arc;
+use thiserror::Error;
+
+pub mod animal;
+pub mod api_key;
+pub mod backup;
+pub mod job;
+pub mod migrate;
+
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("Config error")]
+    ConfigError,
+    #[error("Database error")]
+    DatabaseError,
+    #[error("IO error")]
+    IoError,
+    #[error("Data error")]
+    DataError,
+}
+
+#[derive(Parser)]
+#[command(name = "backend_api", about = "Animal API server and admin tools")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Tenant to work on, by slug, when multi-tenant mode is enabled
+    #[arg(long, global = true)]
+    pub tenant: Option<String>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Run the HTTP server (default)
+    Serve,
+    /// Manage the database schema
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateCommand,
+    },
+    /// Add the animals listed in a JSON file
+    Seed { file: PathBuf },
+    /// Write every animal as JSON
+    Export {
+        /// File to write to, stdout when omitted
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Restore animals from an export, keeping their ids
+    Import { file: PathBuf },
+    /// Parse and validate the config, then print it
+    CheckConfig,
+    /// Print the OpenAPI spec
+    Openapi {
+        #[arg(long, value_enum, default_value_t = SpecFormat::Json)]
+        format: SpecFormat,
+        /// Version of the API to describe
+        #[arg(long, value_enum, default_value_t = ApiVersion::V1)]
+        api: ApiVersion,
+    },
+    /// Create an API key and print it, it is only shown once
+    CreateApiKey { name: String },
+    /// Queue a one-shot maintenance job for the server to run
+    QueueJob {
+        #[arg(value_enum)]
+        kind: JobKind,
+    },
+    /// Replace the main database, or that of --tenant, with a backup, after verifying it; stop
+    /// the server first
+    Restore { file: PathBuf },
+}
+
+#[derive(Subcommand)]
+pub enum MigrateCommand {
+    /// Apply every pending migration
+    Up,
+    /// Revert the latest migrations
+    Down {
+        #[arg(long, default_value_t = 1)]
+        steps: usize,
+    },
+    /// List migrations and whether they are applied
+    Status,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum SpecFormat {
+    Json,
+    Yaml,
+}
+
+async fn config() -> Result<Arc<Config>, Report<CliError>> {
+    Config::current()
+        .await
+        .change_context(CliError::ConfigError)
+}
+
+/// Open the configured database and bring it up to date, as the server would.
+async fn sqlite_client() -> Result<SqliteClient, Report<CliError>> {
+    SqliteClient::new(config().await?.sqlite.path.clone()).change_context(CliError::DatabaseError)
+}
+
+fn require_tenant_mode(config: &Config, tenant: &Option<String>) -> Result<(), Report<CliError>> {
+    match (config.tenant.enabled, tenant) {
+        (true, None) => Err(Report::new(CliError::ConfigError)
+            .attach_printable("Multi-tenant mode is enabled, pass --tenant <slug>")),
+        (false, Some(_)) => Err(Report::new(CliError::ConfigError)
+            .attach_printable("--tenant needs multi-tenant mode, set tenant.enabled")),
+        _ => Ok(()),
+    }
+}
+
+/// Registry entry of the tenant, which must exist.
+async fn find_tenant(slug: &str) -> Result<TenantObject, Report<CliError>> {
+    TenantRepository::new(sqlite_client().await?)
+        .fetch_tenant_by_slug(slug)
+        .change_context(CliError::DataError)
+        .attach_printable_lazy(|| format!("Unknown tenant '{}'", slug))
+}
+
+/// Database holding the animals of `tenant`, or of the main database in single-tenant mode.
+async fn animal_sqlite_client(
+    tenant: &Option<String>,
+) -> Result<SqliteClient<TenantConnection>, Report<CliError>> {
+    let config = config().await?;
+    require_tenant_mode(&config, tenant)?;
+    match tenant {
+        None => Ok(SqliteClient::single_tenant(&sqlite_client().await?)),
+        Some(slug) => {
+            find_tenant(slug).await?;
+            TenantPool::new(Arc::clone(&config.tenant))
+                .client(slug)
+                .change_context(CliError::DatabaseError)
+        }
+    }
+}
+
+pub async fn check_config() -> Result<(), Report<CliError>> {
+    let config = config().await?;
+    println!("Config OK\n{:#?}", config);
+    Ok(())
+}
+
+/// The spec of `api` as served at `/<api>/openapi.json`, with the configured validation rules.
+pub async fn openapi(format: SpecFormat, api: ApiVersion) -> Result<(), Report<CliError>> {
+    let spec = spec_with_rules(api, &config().await?.validation.animal_rules());
+    match format {
+        SpecFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&spec).change_context(CliError::DataError)?
+        ),
+        SpecFormat::Yaml => print!(
+            "{}",
+            serde_yaml::to_string(&spec).change_context(CliError::DataError)?
+        ),
+    }
+    Ok(())
+}
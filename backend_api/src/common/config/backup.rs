@@ -0,0 +1,34 @@
+use crate::common::config::ConfigError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BackupConfig {
+    /// Where backups of `sqlite.path` go, each next to a `.sha256` file of its checksum.
+    pub directory: String,
+    /// Backups left in `directory`, the oldest ones are removed.
+    pub kept: usize,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            directory: "./backups".to_string(),
+            kept: 7,
+        }
+    }
+}
+
+impl BackupConfig {
+    pub fn validate(&self) -> Result<(), Report<ConfigError>> {
+        if self.directory.is_empty() {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("backup.directory cannot be empty"));
+        }
+        if self.kept == 0 {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("backup.kept must be greater than 0"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use crate::common::config::ConfigError;
+use error_stack::Report;
+use poem::http::{HeaderName, HeaderValue, Method};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CorsConfig {
+    /// Origins browsers may call the API from, such as `https://zoo.example`. One `*` stands
+    /// for a port or a subdomain label, as in `http://localhost:*` for the ports `dx serve`
+    /// picks; `*` alone allows every origin. An empty list allows none.
+    pub allowed_origins: Vec<String>,
+    /// Methods allowed; an empty list allows any.
+    pub allowed_methods: Vec<String>,
+    /// Request headers allowed on top of the CORS-safelisted ones; an empty list allows any.
+    pub allowed_headers: Vec<String>,
+    /// Response headers scripts may read on top of the CORS-safelisted ones.
+    pub exposed_headers: Vec<String>,
+    /// Whether requests may carry cookies; cannot be combined with the `*` origin.
+    pub allow_credentials: bool,
+    /// How long browsers may cache a preflight response, in seconds.
+    pub max_age_secs: u32,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec![
+                "http://localhost:*".to_string(),
+                "http://127.0.0.1:*".to_string(),
+            ],
+            allowed_methods: ["GET", "POST", "PUT", "PATCH", "DELETE"]
+                .map(String::from)
+                .to_vec(),
+            allowed_headers: [
+                "Content-Type",
+                "Accept-Language",
+                "X-Api-Key",
+                "X-Tenant",
+                "Idempotency-Key",
+            ]
+            .map(String::from)
+            .to_vec(),
+            exposed_headers: [
+                "Content-Language",
+                "Deprecation",
+                "Sunset",
+                "Idempotent-Replayed",
+                "Retry-After",
+            ]
+            .map(String::from)
+            .to_vec(),
+            allow_credentials: false,
+            max_age_secs: 2 * 60 * 60,
+        }
+    }
+}
+
+/// Whether `origin` is allowed by `pattern`, whose `*` stands for letters, digits and dashes.
+pub fn origin_matches(pattern: &str, origin: &str) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => origin
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_suffix(suffix))
+            .is_some_and(|wildcard| {
+                !wildcard.is_empty()
+                    && wildcard
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-')
+            }),
+        None => pattern.eq_ignore_ascii_case(origin),
+    }
+}
+
+impl CorsConfig {
+    pub fn validate(&self) -> Result<(), Report<ConfigError>> {
+        if let Some(origin) = self.allowed_origins.iter().find(|origin| {
+            *origin != "*"
+                && ((!origin.starts_with("http://") && !origin.starts_with("https://"))
+                    || origin.matches('*').count() > 1
+                    || HeaderValue::from_str(origin).is_err())
+        }) {
+            return Err(
+                Report::new(ConfigError::ValidationError).attach_printable(format!(
+                    "cors.allowed_origins entry '{}' must be an http:// or https:// origin with at most one *",
+                    origin
+                )),
+            );
+        }
+        if self.allow_credentials && self.allowed_origins.iter().any(|origin| origin == "*") {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("cors.allow_credentials cannot be combined with the * origin"));
+        }
+        if let Some(method) = self
+            .allowed_methods
+            .iter()
+            .find(|method| Method::from_str(method).is_err())
+        {
+            return Err(
+                Report::new(ConfigError::ValidationError).attach_printable(format!(
+                    "cors.allowed_methods entry '{}' is not a method",
+                    method
+                )),
+            );
+        }
+        if let Some(header) = self
+            .allowed_headers
+            .iter()
+            .chain(&self.exposed_headers)
+            .find(|header| HeaderName::from_str(header).is_err())
+        {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable(format!("cors header '{}' is not a header name", header)));
+        }
+        if self.max_age_secs > i32::MAX as u32 {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("cors.max_age_secs is too large"));
+        }
+        Ok(())
+    }
+}
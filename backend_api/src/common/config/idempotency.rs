@@ -0,0 +1,41 @@
+use crate::common::config::ConfigError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IdempotencyConfig {
+    /// How long a response is replayed to retries carrying the same `Idempotency-Key`, in
+    /// seconds.
+    pub ttl_secs: u64,
+    /// How long a request holds its key while it is processed, in seconds. Retries get 409
+    /// until then, and are processed afresh once a server that died half-way lets it lapse.
+    pub lease_secs: u64,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            ttl_secs: 24 * 60 * 60,
+            lease_secs: 60,
+        }
+    }
+}
+
+impl IdempotencyConfig {
+    pub fn validate(&self) -> Result<(), Report<ConfigError>> {
+        if self.ttl_secs == 0 {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("idempotency.ttl_secs must be greater than 0"));
+        }
+        if self.ttl_secs > 365 * 24 * 60 * 60 {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("idempotency.ttl_secs cannot be more than a year"));
+        }
+        if self.lease_secs == 0 || self.lease_secs > self.ttl_secs {
+            return Err(Report::new(ConfigError::ValidationError).attach_printable(
+                "idempotency.lease_secs must be greater than 0 and at most ttl_secs",
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use crate::common::config::ConfigError;
+use crate::jobs::object::JobKind;
+use cron::Schedule;
+use error_stack::{Report, ResultExt};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const MAX_DELAY_SECS: u64 = 24 * 60 * 60;
+
+/// Cron expressions of the periodic jobs, with a leading seconds field. Jobs left out or set
+/// to an empty string only run when queued.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JobSchedulesConfig {
+    pub vacuum: Option<String>,
+    pub analyze: Option<String>,
+    pub wal_checkpoint: Option<String>,
+    pub backup: Option<String>,
+}
+
+impl JobSchedulesConfig {
+    pub fn schedule(&self, kind: JobKind) -> Option<&str> {
+        let schedule = match kind {
+            JobKind::Vacuum => &self.vacuum,
+            JobKind::Analyze => &self.analyze,
+            JobKind::WalCheckpoint => &self.wal_checkpoint,
+            JobKind::Backup => &self.backup,
+        };
+        schedule.as_deref().filter(|schedule| !schedule.is_empty())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JobsConfig {
+    /// Whether the server runs the scheduler; other servers sharing the database may.
+    pub enabled: bool,
+    /// How often the scheduler looks for due jobs, in seconds.
+    pub poll_interval_secs: u64,
+    /// How long a job is reserved for the scheduler running it, in seconds. A job still
+    /// running past its lease, as when the server stopped midway, is run again.
+    pub lease_secs: u64,
+    /// Runs a job gets before a one-shot job fails or a periodic one waits for its next time.
+    pub max_attempts: u32,
+    /// Wait before retrying a failed run, in seconds, doubled on each further retry.
+    pub retry_delay_secs: u64,
+    pub schedules: JobSchedulesConfig,
+}
+
+impl Default for JobsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            poll_interval_secs: 5,
+            lease_secs: 10 * 60,
+            max_attempts: 3,
+            retry_delay_secs: 60,
+            schedules: JobSchedulesConfig {
+                vacuum: Some("0 0 3 * * Sun".to_string()),
+                analyze: Some("0 30 3 * * *".to_string()),
+                wal_checkpoint: Some("0 */15 * * * *".to_string()),
+                backup: Some("0 0 2 * * *".to_string()),
+            },
+        }
+    }
+}
+
+impl JobsConfig {
+    /// Parsed schedule of every periodic job.
+    pub fn periodic_jobs(&self) -> Vec<(JobKind, &str, Schedule)> {
+        JobKind::ALL
+            .into_iter()
+            .filter_map(|kind| {
+                let expression = self.schedules.schedule(kind)?;
+                let schedule = Schedule::from_str(expression).ok()?;
+                Some((kind, expression, schedule))
+            })
+            .collect()
+    }
+
+    pub fn validate(&self) -> Result<(), Report<ConfigError>> {
+        if self.poll_interval_secs == 0 {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("jobs.poll_interval_secs must be greater than 0"));
+        }
+        if self.lease_secs == 0 {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("jobs.lease_secs must be greater than 0"));
+        }
+        if self.lease_secs > MAX_DELAY_SECS {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("jobs.lease_secs cannot be more than a day"));
+        }
+        if self.retry_delay_secs > MAX_DELAY_SECS {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("jobs.retry_delay_secs cannot be more than a day"));
+        }
+        if self.max_attempts == 0 {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("jobs.max_attempts must be greater than 0"));
+        }
+        for kind in JobKind::ALL {
+            if let Some(expression) = self.schedules.schedule(kind) {
+                Schedule::from_str(expression)
+                    .change_context(ConfigError::ValidationError)
+                    .attach_printable_lazy(|| {
+                        format!("jobs.schedules.{} is not a cron expression", kind.as_str())
+                    })?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,155 @@
+use crate::common::config::ConfigError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+const ALGORITHMS: [&str; 3] = ["br", "zstd", "gzip"];
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CompressionConfig {
+    pub enabled: bool,
+    /// Codings offered, out of `br`, `zstd` and `gzip`; the first one wins when the client
+    /// accepts several equally.
+    pub algorithms: Vec<String>,
+    /// Responses with a smaller body are sent as they are, in bytes.
+    pub min_size_bytes: usize,
+    /// Media types compressed; an entry ending in `/`, such as `text/`, covers the whole type.
+    pub content_types: Vec<String>,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            algorithms: ALGORITHMS.map(String::from).to_vec(),
+            min_size_bytes: 1024,
+            content_types: vec![
+                "application/json".to_string(),
+                "application/javascript".to_string(),
+                "text/".to_string(),
+            ],
+        }
+    }
+}
+
+impl CompressionConfig {
+    pub fn compresses(&self, content_type: &str) -> bool {
+        let media_type = content_type.split(';').next().unwrap_or_default().trim();
+        self.content_types.iter().any(|allowed| {
+            if allowed.ends_with('/') {
+                media_type.starts_with(allowed.as_str())
+            } else {
+                media_type.eq_ignore_ascii_case(allowed)
+            }
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RateLimitConfig {
+    /// Requests a client may make per window; 0 turns rate limiting off. Clients are told
+    /// apart by their API key, or by their address when they send none.
+    pub requests: u32,
+    /// Length of the window, in seconds.
+    pub window_secs: u64,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            requests: 0,
+            window_secs: 60,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LimitsConfig {
+    /// Largest request body accepted, in bytes.
+    pub max_body_bytes: usize,
+    /// Time a request gets to be answered, in seconds.
+    pub timeout_secs: u64,
+    /// Timeouts of the routes under the given paths, in seconds, matched without the `/v1`
+    /// or `/v2` prefix. The longest matching path wins over `timeout_secs`.
+    pub route_timeout_secs: BTreeMap<String, u64>,
+    /// Most operations `POST /animal/batch` takes in one request.
+    pub max_batch_operations: usize,
+    pub compression: CompressionConfig,
+    pub rate_limit: RateLimitConfig,
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: 16 * 1024 * 1024,
+            timeout_secs: 30,
+            route_timeout_secs: BTreeMap::new(),
+            max_batch_operations: 100,
+            compression: CompressionConfig::default(),
+            rate_limit: RateLimitConfig::default(),
+        }
+    }
+}
+
+impl LimitsConfig {
+    /// Timeout of the route at `path`, given without its version prefix.
+    pub fn timeout(&self, path: &str) -> Duration {
+        let secs = self
+            .route_timeout_secs
+            .iter()
+            .filter(|(prefix, _)| {
+                path.strip_prefix(prefix.as_str()).is_some_and(|rest| {
+                    rest.is_empty() || rest.starts_with('/') || prefix.ends_with('/')
+                })
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.timeout_secs, |(_, secs)| *secs);
+        Duration::from_secs(secs)
+    }
+
+    /// `max_upload_bytes` is `storage.max_upload_bytes`, which has to fit in a request body.
+    pub fn validate(&self, max_upload_bytes: usize) -> Result<(), Report<ConfigError>> {
+        if self.max_body_bytes < max_upload_bytes {
+            return Err(Report::new(ConfigError::ValidationError).attach_printable(
+                "poem.limits.max_body_bytes cannot be less than storage.max_upload_bytes",
+            ));
+        }
+        if self.timeout_secs == 0 {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("poem.limits.timeout_secs must be greater than 0"));
+        }
+        for (path, secs) in &self.route_timeout_secs {
+            if !path.starts_with('/') || *secs == 0 {
+                return Err(
+                    Report::new(ConfigError::ValidationError).attach_printable(format!(
+                        "poem.limits.route_timeout_secs entry '{}' must be a path starting with / and a timeout greater than 0",
+                        path
+                    )),
+                );
+            }
+        }
+        if self.max_batch_operations == 0 {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("poem.limits.max_batch_operations must be greater than 0"));
+        }
+        if self.rate_limit.window_secs == 0 {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("poem.limits.rate_limit.window_secs must be greater than 0"));
+        }
+        if let Some(algorithm) = self
+            .compression
+            .algorithms
+            .iter()
+            .find(|algorithm| !ALGORITHMS.contains(&algorithm.as_str()))
+        {
+            return Err(
+                Report::new(ConfigError::ValidationError).attach_printable(format!(
+                    "poem.limits.compression.algorithms entry '{}' must be br, zstd or gzip",
+                    algorithm
+                )),
+            );
+        }
+        Ok(())
+    }
+}
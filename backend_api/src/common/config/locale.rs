@@ -0,0 +1,58 @@
+use crate::common::config::ConfigError;
+use crate::common::locale::negotiation::{best_match, lookup_chain};
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+use shared::i18n::bundle_languages;
+use shared::validation::types::locale_tag::LocaleTag;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LocaleConfig {
+    /// Language the species and description of the animals themselves are written in.
+    pub source: String,
+    /// Languages tried, in order, when no translation matches `Accept-Language`, before
+    /// falling back to `source`.
+    pub fallback: Vec<String>,
+    /// Language of messages when neither `?lang=`, `Accept-Language` nor the fallback
+    /// languages have a bundle, one of `en`, `fr`, `de` and `es`.
+    pub default: String,
+}
+
+impl Default for LocaleConfig {
+    fn default() -> Self {
+        Self {
+            source: "en".to_string(),
+            fallback: Vec::new(),
+            default: "en".to_string(),
+        }
+    }
+}
+
+impl LocaleConfig {
+    pub fn validate(&self) -> Result<(), Report<ConfigError>> {
+        if LocaleTag::parse(&self.source).is_err() {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("locale.source must be a language tag"));
+        }
+        if self
+            .fallback
+            .iter()
+            .any(|locale| LocaleTag::parse(locale).is_err())
+        {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("locale.fallback must only contain language tags"));
+        }
+        let bundles = bundle_languages();
+        let has_bundle = LocaleTag::parse(&self.default).is_ok_and(|default| {
+            best_match(
+                &lookup_chain(&[], &[], &default),
+                &bundles.iter().collect::<Vec<_>>(),
+            )
+            .is_some()
+        });
+        if !has_bundle {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("locale.default must be a language with a message bundle"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+use crate::common::config::ConfigError;
+use error_stack::{Report, ResultExt};
+use serde::{Deserialize, Serialize};
+use tracing_subscriber::EnvFilter;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LogConfig {
+    /// What gets logged, as a `RUST_LOG` filter such as `info` or `warn,backend_api=debug`.
+    pub level: String,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+        }
+    }
+}
+
+impl LogConfig {
+    pub fn filter(&self) -> Result<EnvFilter, Report<ConfigError>> {
+        EnvFilter::try_new(&self.level)
+            .change_context(ConfigError::ValidationError)
+            .attach_printable_lazy(|| format!("log.level '{}' is not a log filter", self.level))
+    }
+
+    pub fn validate(&self) -> Result<(), Report<ConfigError>> {
+        self.filter().map(|_| ())
+    }
+}
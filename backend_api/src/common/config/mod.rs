@@ -1,69 +1,220 @@
 use crate::common::config::poem::PoemConfig;
+use backup::BackupConfig;
+use cors::CorsConfig;
 use error_stack::{Report, ResultExt};
 use figment::providers::{Format, Serialized, Toml};
 use figment::{Figment, Profile};
+use idempotency::IdempotencyConfig;
+use jobs::JobsConfig;
+use locale::LocaleConfig;
+use log::LogConfig;
+use repository::RepositoryConfig;
+use security_headers::SecurityHeadersConfig;
 use serde::{Deserialize, Serialize};
 use sqlite::SqliteConfig;
 use std::env::var;
 use std::sync::{Arc, Weak};
+use storage::StorageConfig;
+use tenant::TenantConfig;
 use thiserror::Error;
-use tokio::sync::OnceCell;
+use tokio::sync::{OnceCell, watch};
+use validation::ValidationConfig;
+use versions::VersionsConfig;
 
+pub mod backup;
+pub mod cors;
+pub mod idempotency;
+pub mod jobs;
+pub mod limits;
+pub mod locale;
+pub mod log;
 pub mod poem;
+pub mod reload;
+pub mod repository;
+pub mod security_headers;
 pub mod sqlite;
+pub mod storage;
+pub mod tenant;
+pub mod validation;
+pub mod versions;
 
 #[derive(Debug, Error)]
 pub enum ConfigError {
     #[error("Config did not parse")]
     ParseError,
+    #[error("Config did not validate")]
+    ValidationError,
+    #[error("Config change requires a restart")]
+    RestartRequired,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Config {
     pub poem: Arc<PoemConfig>,
+    pub cors: Arc<CorsConfig>,
+    pub security_headers: Arc<SecurityHeadersConfig>,
     pub sqlite: Arc<SqliteConfig>,
+    pub tenant: Arc<TenantConfig>,
+    pub repository: Arc<RepositoryConfig>,
+    pub storage: Arc<StorageConfig>,
+    pub locale: Arc<LocaleConfig>,
+    pub validation: Arc<ValidationConfig>,
+    pub versions: Arc<VersionsConfig>,
+    pub idempotency: Arc<IdempotencyConfig>,
+    pub jobs: Arc<JobsConfig>,
+    pub backup: Arc<BackupConfig>,
+    pub log: Arc<LogConfig>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             poem: Arc::new(PoemConfig::default()),
+            cors: Arc::new(CorsConfig::default()),
+            security_headers: Arc::new(SecurityHeadersConfig::default()),
             sqlite: Arc::new(SqliteConfig::default()),
+            tenant: Arc::new(TenantConfig::default()),
+            repository: Arc::new(RepositoryConfig::default()),
+            storage: Arc::new(StorageConfig::default()),
+            locale: Arc::new(LocaleConfig::default()),
+            validation: Arc::new(ValidationConfig::default()),
+            versions: Arc::new(VersionsConfig::default()),
+            idempotency: Arc::new(IdempotencyConfig::default()),
+            jobs: Arc::new(JobsConfig::default()),
+            backup: Arc::new(BackupConfig::default()),
+            log: Arc::new(LogConfig::default()),
         }
     }
 }
 
-static CONFIG_CACHE: OnceCell<Arc<Config>> = OnceCell::const_new();
+static CONFIG_CACHE: OnceCell<watch::Sender<Arc<Config>>> = OnceCell::const_new();
 
 impl Config {
+    fn file_paths() -> [String; 2] {
+        [
+            "little_poem.toml".to_string(),
+            var("LITTLE_POEM_CONFIG_PATH").unwrap_or_else(|_| "little_poem.local.toml".to_string()),
+        ]
+    }
+
     fn build_figment() -> Figment {
+        let [main_path, local_path] = Self::file_paths();
         Figment::new()
             .merge(Serialized::defaults(Config::default()))
-            .merge(Toml::file("little_poem.toml").nested())
-            .merge(
-                Toml::file(
-                    var("LITTLE_POEM_CONFIG_PATH")
-                        .unwrap_or_else(|_| "little_poem.local.toml".to_string()),
-                )
-                .nested(),
-            )
+            .merge(Toml::file(main_path).nested())
+            .merge(Toml::file(local_path).nested())
             .select(Profile::from_env_or("LITTLE_POEM_PROFILE", "default"))
     }
 
     fn parse() -> Result<Self, Report<ConfigError>> {
-        Self::build_figment()
+        let config = Self::build_figment()
             .extract::<Self>()
-            .change_context(ConfigError::ParseError)
+            .change_context(ConfigError::ParseError)?;
+        config.validate()?;
+        Ok(config)
     }
 
-    pub async fn fetch() -> Result<Weak<Config>, Report<ConfigError>> {
-        let config: Result<&Arc<Config>, Report<ConfigError>> = CONFIG_CACHE
+    fn validate(&self) -> Result<(), Report<ConfigError>> {
+        self.poem.validate(self.storage.max_upload_bytes)?;
+        self.cors.validate()?;
+        self.security_headers.validate()?;
+        self.sqlite.validate()?;
+        self.tenant.validate()?;
+        self.repository.validate(self.tenant.enabled)?;
+        self.storage.validate()?;
+        self.locale.validate()?;
+        self.validation.validate()?;
+        self.versions.validate()?;
+        self.idempotency.validate()?;
+        self.jobs.validate()?;
+        self.backup.validate()?;
+        self.log.validate()?;
+        Ok(())
+    }
+
+    /// Names of the settings that differ between `self` and `other` and can only be applied
+    /// by restarting the server.
+    fn restart_required_changes(&self, other: &Self) -> Vec<&'static str> {
+        let mut changes = Vec::new();
+        if self.poem.address != other.poem.address {
+            changes.push("poem.address");
+        }
+        if self.poem.port != other.poem.port {
+            changes.push("poem.port");
+        }
+        if self.sqlite.path != other.sqlite.path {
+            changes.push("sqlite.path");
+        }
+        if self.tenant.enabled != other.tenant.enabled {
+            changes.push("tenant.enabled");
+        }
+        if self.tenant.directory != other.tenant.directory {
+            changes.push("tenant.directory");
+        }
+        if self.tenant.cache_size != other.tenant.cache_size {
+            changes.push("tenant.cache_size");
+        }
+        if self.repository.backend != other.repository.backend {
+            changes.push("repository.backend");
+        }
+        if self.storage.directory != other.storage.directory {
+            changes.push("storage.directory");
+        }
+        if self.jobs.enabled != other.jobs.enabled {
+            changes.push("jobs.enabled");
+        }
+        changes
+    }
+
+    async fn sender() -> Result<&'static watch::Sender<Arc<Config>>, Report<ConfigError>> {
+        CONFIG_CACHE
             .get_or_try_init(|| async {
                 let config = Self::parse()?;
-                Ok(Arc::new(config))
+                Ok(watch::Sender::new(Arc::new(config)))
             })
-            .await;
+            .await
+    }
+
+    /// Strong reference to the config that is live right now; hold it for as long as a
+    /// consistent view is needed, as a reload may swap it out at any time.
+    pub async fn current() -> Result<Arc<Config>, Report<ConfigError>> {
+        Ok(Self::sender().await?.borrow().clone())
+    }
+
+    /// Config for a request. An `Arc<Config>` added to the endpoint with `.data(...)` takes
+    /// precedence over the live config, which lets tests run the app with their own settings.
+    pub async fn for_request(req: &::poem::Request) -> Result<Arc<Config>, Report<ConfigError>> {
+        match req.data::<Arc<Config>>() {
+            Some(config) => Ok(Arc::clone(config)),
+            None => Self::current().await,
+        }
+    }
+
+    pub async fn fetch() -> Result<Weak<Config>, Report<ConfigError>> {
+        Ok(Arc::downgrade(&Self::current().await?))
+    }
+
+    pub async fn subscribe() -> Result<watch::Receiver<Arc<Config>>, Report<ConfigError>> {
+        Ok(Self::sender().await?.subscribe())
+    }
+
+    /// Re-parse and validate the config files, then atomically swap the result in.
+    ///
+    /// The live config is left untouched if the new one fails to parse, fails to validate or
+    /// changes a setting that needs a restart.
+    pub async fn reload() -> Result<Arc<Config>, Report<ConfigError>> {
+        let sender = Self::sender().await?;
+        let config = Self::parse()?;
+
+        let current = sender.borrow().clone();
+        let changes = current.restart_required_changes(&config);
+        if !changes.is_empty() {
+            return Err(Report::new(ConfigError::RestartRequired)
+                .attach_printable(format!("Changed settings: {}", changes.join(", "))));
+        }
 
-        Ok(Arc::downgrade(config?))
+        let config = Arc::new(config);
+        sender.send_replace(Arc::clone(&config));
+        Ok(config)
     }
 }
@@ -1,9 +1,13 @@
+use crate::common::config::ConfigError;
+use crate::common::config::limits::LimitsConfig;
+use error_stack::Report;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct PoemConfig {
     pub address: String,
     pub port: u16,
+    pub limits: LimitsConfig,
 }
 
 impl Default for PoemConfig {
@@ -11,6 +15,7 @@ impl Default for PoemConfig {
         Self {
             address: "127.0.0.1".to_string(),
             port: 8000,
+            limits: LimitsConfig::default(),
         }
     }
 }
@@ -19,4 +24,13 @@ impl PoemConfig {
     pub fn parse_address(&self) -> String {
         format!("{}:{}", self.address, self.port)
     }
+
+    /// `max_upload_bytes` is `storage.max_upload_bytes`.
+    pub fn validate(&self, max_upload_bytes: usize) -> Result<(), Report<ConfigError>> {
+        if self.address.is_empty() {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("poem.address cannot be empty"));
+        }
+        self.limits.validate(max_upload_bytes)
+    }
 }
@@ -0,0 +1,65 @@
+use crate::common::config::{Config, ConfigError};
+use std::future::pending;
+use std::time::{Duration, SystemTime};
+use tokio::time::interval;
+use tracing::{error, info, warn};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+fn modified_times() -> Vec<Option<SystemTime>> {
+    Config::file_paths()
+        .iter()
+        .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+async fn reload(trigger: &str) {
+    match Config::reload().await {
+        Ok(_) => info!("Config reloaded ({})", trigger),
+        Err(report) => match report.current_context() {
+            ConfigError::RestartRequired => warn!(
+                "Config reload ({}) rejected, restart the server to apply it: {:?}",
+                trigger, report
+            ),
+            _ => error!("Config reload ({}) failed: {:?}", trigger, report),
+        },
+    }
+}
+
+/// Reload the config whenever SIGHUP arrives or one of the config files changes on disk.
+pub async fn watch_for_reload() {
+    #[cfg(unix)]
+    let mut hangup = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+        Ok(signal) => Some(signal),
+        Err(err) => {
+            warn!("Unable to listen for SIGHUP, config reload on signal is disabled: {err}");
+            None
+        }
+    };
+
+    let mut poll = interval(POLL_INTERVAL);
+    let mut last_modified = modified_times();
+
+    loop {
+        #[cfg(unix)]
+        let hangup_recv = async {
+            match hangup.as_mut() {
+                Some(signal) => signal.recv().await,
+                None => pending().await,
+            }
+        };
+        #[cfg(not(unix))]
+        let hangup_recv = pending::<Option<()>>();
+
+        tokio::select! {
+            _ = hangup_recv => reload("SIGHUP").await,
+            _ = poll.tick() => {
+                let modified = modified_times();
+                if modified != last_modified {
+                    last_modified = modified;
+                    reload("file change").await;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::common::config::ConfigError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RepositoryBackend {
+    /// Animals stored in the SQLite database, or the tenant's database in multi-tenant mode.
+    Sqlite,
+    /// Animals kept in process memory, seeded with the sample animals and lost on restart.
+    Memory,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RepositoryConfig {
+    pub backend: RepositoryBackend,
+}
+
+impl Default for RepositoryConfig {
+    fn default() -> Self {
+        Self {
+            backend: RepositoryBackend::Sqlite,
+        }
+    }
+}
+
+impl RepositoryConfig {
+    pub fn validate(&self, tenant_enabled: bool) -> Result<(), Report<ConfigError>> {
+        if self.backend == RepositoryBackend::Memory && tenant_enabled {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("repository.backend = \"memory\" cannot be used with tenants"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use crate::common::config::ConfigError;
+use error_stack::Report;
+use poem::http::HeaderValue;
+use serde::{Deserialize, Serialize};
+
+const REFERRER_POLICIES: [&str; 8] = [
+    "no-referrer",
+    "no-referrer-when-downgrade",
+    "origin",
+    "origin-when-cross-origin",
+    "same-origin",
+    "strict-origin",
+    "strict-origin-when-cross-origin",
+    "unsafe-url",
+];
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SecurityHeadersConfig {
+    /// Whether clients reach the server over HTTPS, through a proxy terminating TLS;
+    /// `Strict-Transport-Security` is only sent then.
+    pub tls: bool,
+    /// `max-age` of `Strict-Transport-Security`, in seconds.
+    pub hsts_max_age_secs: u64,
+    pub referrer_policy: String,
+    /// `Content-Security-Policy` of the Swagger UI under `/docs`, whose page inlines its
+    /// script and style.
+    pub docs_content_security_policy: String,
+}
+
+impl Default for SecurityHeadersConfig {
+    fn default() -> Self {
+        Self {
+            tls: false,
+            hsts_max_age_secs: 365 * 24 * 60 * 60,
+            referrer_policy: "no-referrer".to_string(),
+            docs_content_security_policy: "default-src 'none'; script-src 'unsafe-inline'; \
+                style-src 'unsafe-inline'; img-src 'self' data:; font-src 'self' data:; \
+                connect-src 'self'; base-uri 'none'; form-action 'self'; frame-ancestors 'none'"
+                .to_string(),
+        }
+    }
+}
+
+impl SecurityHeadersConfig {
+    pub fn validate(&self) -> Result<(), Report<ConfigError>> {
+        if !REFERRER_POLICIES.contains(&self.referrer_policy.as_str()) {
+            return Err(
+                Report::new(ConfigError::ValidationError).attach_printable(format!(
+                    "security_headers.referrer_policy '{}' is not a referrer policy",
+                    self.referrer_policy
+                )),
+            );
+        }
+        if self.docs_content_security_policy.is_empty()
+            || HeaderValue::from_str(&self.docs_content_security_policy).is_err()
+        {
+            return Err(Report::new(ConfigError::ValidationError).attach_printable(
+                "security_headers.docs_content_security_policy must be a non-empty header value",
+            ));
+        }
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use crate::common::config::ConfigError;
+use error_stack::Report;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -12,3 +14,13 @@ impl Default for SqliteConfig {
         }
     }
 }
+
+impl SqliteConfig {
+    pub fn validate(&self) -> Result<(), Report<ConfigError>> {
+        if self.path.is_empty() {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("sqlite.path cannot be empty"));
+        }
+        Ok(())
+    }
+}
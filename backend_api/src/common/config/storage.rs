@@ -0,0 +1,41 @@
+use crate::common::config::ConfigError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct StorageConfig {
+    /// Root of the content-addressed files, stored as `<ab>/<cd>/<sha256>.<ext>`.
+    pub directory: String,
+    /// Largest accepted upload, in bytes.
+    pub max_upload_bytes: usize,
+    /// Longest edge, in pixels, of each thumbnail generated for an upload.
+    pub thumbnail_sizes: Vec<u32>,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            directory: "./storage".to_string(),
+            max_upload_bytes: 10 * 1024 * 1024,
+            thumbnail_sizes: vec![128, 512],
+        }
+    }
+}
+
+impl StorageConfig {
+    pub fn validate(&self) -> Result<(), Report<ConfigError>> {
+        if self.directory.is_empty() {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("storage.directory cannot be empty"));
+        }
+        if self.max_upload_bytes == 0 {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("storage.max_upload_bytes must be greater than 0"));
+        }
+        if self.thumbnail_sizes.contains(&0) {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("storage.thumbnail_sizes cannot contain 0"));
+        }
+        Ok(())
+    }
+}
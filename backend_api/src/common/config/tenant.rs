@@ -0,0 +1,62 @@
+use crate::common::config::ConfigError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TenantResolver {
+    /// Tenant slug taken from the `header_name` header.
+    Header,
+    /// Tenant slug taken from the first label of `<slug>.<base_domain>`.
+    Subdomain,
+    /// Tenant the `X-Api-Key` key belongs to.
+    ApiKey,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TenantConfig {
+    /// When off, animals live in the main database and no tenant is resolved.
+    pub enabled: bool,
+    pub resolver: TenantResolver,
+    pub header_name: String,
+    pub base_domain: String,
+    /// Directory holding one `<slug>.db` file per tenant.
+    pub directory: String,
+    /// Number of tenant databases kept open at once.
+    pub cache_size: usize,
+}
+
+impl Default for TenantConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            resolver: TenantResolver::Header,
+            header_name: "X-Tenant".to_string(),
+            base_domain: "localhost".to_string(),
+            directory: "./tenants".to_string(),
+            cache_size: 16,
+        }
+    }
+}
+
+impl TenantConfig {
+    pub fn database_path(&self, slug: &str) -> PathBuf {
+        Path::new(&self.directory).join(format!("{}.db", slug))
+    }
+
+    pub fn validate(&self) -> Result<(), Report<ConfigError>> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.directory.is_empty() {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("tenant.directory cannot be empty"));
+        }
+        if self.cache_size == 0 {
+            return Err(Report::new(ConfigError::ValidationError)
+                .attach_printable("tenant.cache_size must be at least 1"));
+        }
+        Ok(())
+    }
+}
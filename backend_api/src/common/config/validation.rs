@@ -0,0 +1,98 @@
+use crate::common::config::ConfigError;
+use crate::common::context::{Context, ContextError, FromContext};
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+use shared::validation::rules::{AnimalRules, FieldRules};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct FieldRulesConfig {
+    pub required: bool,
+    #[serde(default)]
+    pub min_length: Option<usize>,
+    #[serde(default)]
+    pub max_length: Option<usize>,
+}
+
+impl From<FieldRules> for FieldRulesConfig {
+    fn from(rules: FieldRules) -> Self {
+        Self {
+            required: rules.is_mandatory,
+            min_length: rules.min_length,
+            max_length: rules.max_length,
+        }
+    }
+}
+
+impl From<FieldRulesConfig> for FieldRules {
+    fn from(config: FieldRulesConfig) -> Self {
+        Self {
+            is_mandatory: config.required,
+            min_length: config.min_length,
+            max_length: config.max_length,
+        }
+    }
+}
+
+impl FieldRulesConfig {
+    fn validate(&self, name: &str) -> Result<(), Report<ConfigError>> {
+        if self.max_length == Some(0) {
+            return Err(
+                Report::new(ConfigError::ValidationError).attach_printable(format!(
+                    "validation.{}.max_length must be greater than 0",
+                    name
+                )),
+            );
+        }
+        if matches!(
+            (self.min_length, self.max_length),
+            (Some(min_length), Some(max_length)) if min_length > max_length
+        ) {
+            return Err(
+                Report::new(ConfigError::ValidationError).attach_printable(format!(
+                    "validation.{}.min_length cannot exceed max_length",
+                    name
+                )),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Rules of the animal fields, published at `GET /validation/rules` for clients.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ValidationConfig {
+    pub species: FieldRulesConfig,
+    pub description: FieldRulesConfig,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        let rules = AnimalRules::default();
+        Self {
+            species: rules.species.into(),
+            description: rules.description.into(),
+        }
+    }
+}
+
+impl ValidationConfig {
+    pub fn validate(&self) -> Result<(), Report<ConfigError>> {
+        self.species.validate("species")?;
+        self.description.validate("description")?;
+        Ok(())
+    }
+
+    pub fn animal_rules(&self) -> AnimalRules {
+        AnimalRules {
+            species: self.species.into(),
+            description: self.description.into(),
+        }
+    }
+}
+
+impl FromContext for AnimalRules {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let config = ctx.config.upgrade().ok_or(ContextError::ConfigError)?;
+        Ok(config.validation.animal_rules())
+    }
+}
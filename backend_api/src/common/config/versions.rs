@@ -0,0 +1,50 @@
+use crate::common::config::ConfigError;
+use crate::version::ApiVersion;
+use chrono::{DateTime, Utc};
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+/// Retirement schedule of a version of the API, announced in the headers of its responses.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+pub struct VersionPolicyConfig {
+    /// When the version was deprecated, sent as `Deprecation`.
+    #[serde(default)]
+    pub deprecated_at: Option<DateTime<Utc>>,
+    /// When the version is expected to stop being served, sent as `Sunset`.
+    #[serde(default)]
+    pub sunset_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct VersionsConfig {
+    pub v1: VersionPolicyConfig,
+    pub v2: VersionPolicyConfig,
+}
+
+impl VersionsConfig {
+    pub fn policy(&self, version: ApiVersion) -> VersionPolicyConfig {
+        match version {
+            ApiVersion::V1 => self.v1,
+            ApiVersion::V2 => self.v2,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), Report<ConfigError>> {
+        for version in ApiVersion::ALL {
+            let policy = self.policy(version);
+            if policy
+                .deprecated_at
+                .zip(policy.sunset_at)
+                .is_some_and(|(deprecated_at, sunset_at)| sunset_at < deprecated_at)
+            {
+                return Err(
+                    Report::new(ConfigError::ValidationError).attach_printable(format!(
+                        "versions.{}.sunset_at cannot be before its deprecated_at",
+                        version.as_str()
+                    )),
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@ use crate::common::config::Config;
 use error_stack::Report;
 use poem::http::StatusCode;
 use poem::{FromRequest, Request, RequestBody};
-use std::sync::Weak;
+use std::sync::{Arc, Weak};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -46,6 +46,12 @@ pub struct Context<'a> {
 }
 
 impl Context<'_> {
+    /// A `T` added to the endpoint with `.data(...)`. Dependencies return it in place of
+    /// their shared instance, so a test app can swap in its own.
+    pub fn overridden<T: Clone + Send + Sync + 'static>(&self) -> Option<T> {
+        self.req.data::<T>().cloned()
+    }
+
     pub async fn inject<T: FromContext>(&self) -> Result<T, Report<ContextError>> {
         T::from_context(self).await
     }
@@ -55,11 +61,16 @@ pub struct Dep<T: FromContext>(pub T);
 
 impl<'a, T: FromContext> FromRequest<'a> for Dep<T> {
     async fn from_request(req: &'a Request, _body: &mut RequestBody) -> poem::Result<Self> {
-        let config = match Config::fetch().await {
+        let config = match Config::for_request(req).await {
             Ok(config) => config,
             Err(_) => return Err(poem::Error::from_status(StatusCode::INTERNAL_SERVER_ERROR)),
         };
-        let context = Box::pin(Context { config, req });
+        // `config` keeps the snapshot alive while dependencies are built, even if a reload
+        // swaps it out in the meantime.
+        let context = Box::pin(Context {
+            config: Arc::downgrade(&config),
+            req,
+        });
         Ok(Self(T::from_context(&context).await.map_err(|e| {
             let status_code = e.current_context().status_code();
             poem::Error::from_string(status_code.1, status_code.0)
@@ -0,0 +1,38 @@
+use crate::common::config::Config;
+use crate::common::config::cors::{CorsConfig, origin_matches};
+use poem::middleware::{Cors, Middleware};
+use poem::{Endpoint, IntoResponse, Request, Response};
+use std::sync::Arc;
+
+#[cfg(test)]
+mod tests;
+
+fn build_cors(config: &CorsConfig) -> Cors {
+    let allowed_origins = config.allowed_origins.clone();
+    // Set even for an empty list, which Cors would otherwise take as allowing every origin.
+    Cors::new()
+        .allow_origins_fn(move |origin| {
+            allowed_origins
+                .iter()
+                .any(|pattern| origin_matches(pattern, origin))
+        })
+        .allow_methods(config.allowed_methods.iter().map(String::as_str))
+        .allow_headers(config.allowed_headers.iter().map(String::as_str))
+        .expose_headers(config.exposed_headers.iter().map(String::as_str))
+        .allow_credentials(config.allow_credentials)
+        .max_age(config.max_age_secs as i32)
+}
+
+/// CORS built from the live config on every request, so a config reload takes effect
+/// immediately.
+pub async fn reloadable_cors<E: Endpoint>(next: Arc<E>, req: Request) -> poem::Result<Response> {
+    let config = Config::for_request(&req)
+        .await
+        .map_err(|_| poem::Error::from_status(poem::http::StatusCode::INTERNAL_SERVER_ERROR))?;
+
+    build_cors(&config.cors)
+        .transform(next)
+        .call(req)
+        .await
+        .map(IntoResponse::into_response)
+}
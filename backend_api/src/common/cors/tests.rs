@@ -0,0 +1,95 @@
+use crate::common::config::Config;
+use crate::common::config::cors::{CorsConfig, origin_matches};
+use crate::test_support::TestApp;
+use poem::http::StatusCode;
+use std::sync::Arc;
+
+#[test]
+fn wildcards_stand_for_a_port_or_a_label() {
+    assert!(origin_matches(
+        "http://localhost:*",
+        "http://localhost:8080"
+    ));
+    assert!(origin_matches(
+        "https://*.zoo.example",
+        "https://north.zoo.example"
+    ));
+    assert!(origin_matches("*", "https://anywhere.example"));
+    assert!(!origin_matches("http://localhost:*", "http://localhost:"));
+    assert!(!origin_matches(
+        "http://localhost:*",
+        "http://localhost:80.evil.example"
+    ));
+    assert!(!origin_matches(
+        "https://*.zoo.example",
+        "https://a.b.zoo.example"
+    ));
+    assert!(!origin_matches(
+        "https://zoo.example",
+        "https://zoo.example.evil"
+    ));
+}
+
+#[tokio::test]
+async fn only_configured_origins_are_allowed() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .get("/")
+        .header("Origin", "http://localhost:8080")
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    res.assert_header("Access-Control-Allow-Origin", "http://localhost:8080");
+
+    app.client
+        .get("/")
+        .header("Origin", "https://evil.example")
+        .send()
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn preflights_follow_the_config() {
+    let app = TestApp::builder()
+        .config(Config {
+            cors: Arc::new(CorsConfig {
+                allowed_origins: vec!["https://zoo.example".to_string()],
+                allow_credentials: true,
+                max_age_secs: 600,
+                ..CorsConfig::default()
+            }),
+            ..Config::default()
+        })
+        .build();
+
+    let res = app
+        .client
+        .options("/animal/add")
+        .header("Origin", "https://zoo.example")
+        .header("Access-Control-Request-Method", "POST")
+        .header("Access-Control-Request-Headers", "content-type")
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    res.assert_header("Access-Control-Allow-Origin", "https://zoo.example");
+    res.assert_header("Access-Control-Allow-Credentials", "true");
+    res.assert_header("Access-Control-Max-Age", "600");
+
+    app.client
+        .options("/animal/add")
+        .header("Origin", "https://zoo.example")
+        .header("Access-Control-Request-Method", "TRACE")
+        .send()
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+    app.client
+        .options("/animal/add")
+        .header("Origin", "http://localhost:8080")
+        .header("Access-Control-Request-Method", "POST")
+        .send()
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+}
@@ -0,0 +1 @@
+DROP TABLE animal;
\ No newline at end of file
@@ -1,5 +1,3 @@
-PRAGMA foreign_keys = ON;
-
 CREATE TABLE animal
 (
     id          INTEGER PRIMARY KEY AUTOINCREMENT,
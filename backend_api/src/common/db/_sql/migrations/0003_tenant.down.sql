@@ -0,0 +1,18 @@
+CREATE TABLE api_key_without_tenant
+(
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    name       TEXT NOT NULL,
+    key_hash   TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL
+);
+
+INSERT INTO api_key_without_tenant (id, name, key_hash, created_at)
+SELECT id, name, key_hash, created_at
+FROM api_key;
+
+DROP TABLE api_key;
+
+ALTER TABLE api_key_without_tenant
+    RENAME TO api_key;
+
+DROP TABLE tenant;
\ No newline at end of file
@@ -0,0 +1,11 @@
+CREATE TABLE tenant
+(
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    slug        TEXT NOT NULL UNIQUE,
+    name        TEXT NOT NULL,
+    created_at  TEXT NOT NULL,
+    archived_at TEXT
+);
+
+ALTER TABLE api_key
+    ADD COLUMN tenant_id INTEGER REFERENCES tenant (id);
\ No newline at end of file
@@ -0,0 +1,2 @@
+DROP TABLE animal_photo_thumbnail;
+DROP TABLE animal_photo;
\ No newline at end of file
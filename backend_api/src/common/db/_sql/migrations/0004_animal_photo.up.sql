@@ -0,0 +1,24 @@
+-- No foreign key to animal, as animals may live in the memory repository backend.
+CREATE TABLE animal_photo
+(
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    animal_id    INTEGER NOT NULL,
+    file_key     TEXT    NOT NULL,
+    content_type TEXT    NOT NULL,
+    width        INTEGER NOT NULL,
+    height       INTEGER NOT NULL,
+    byte_size    INTEGER NOT NULL,
+    created_at   TEXT    NOT NULL
+);
+
+CREATE INDEX animal_photo_animal_id ON animal_photo (animal_id);
+
+CREATE TABLE animal_photo_thumbnail
+(
+    photo_id INTEGER NOT NULL REFERENCES animal_photo (id) ON DELETE CASCADE,
+    size     INTEGER NOT NULL,
+    file_key TEXT    NOT NULL,
+    width    INTEGER NOT NULL,
+    height   INTEGER NOT NULL,
+    PRIMARY KEY (photo_id, size)
+);
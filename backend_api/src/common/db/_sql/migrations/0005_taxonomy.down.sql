@@ -0,0 +1,4 @@
+DROP INDEX animal_taxon_id;
+ALTER TABLE animal
+    DROP COLUMN taxon_id;
+DROP TABLE taxon;
\ No newline at end of file
@@ -0,0 +1,74 @@
+CREATE TABLE taxon
+(
+    id        INTEGER PRIMARY KEY AUTOINCREMENT,
+    parent_id INTEGER REFERENCES taxon (id),
+    rank      TEXT NOT NULL,
+    name      TEXT NOT NULL,
+    UNIQUE (parent_id, name)
+);
+
+CREATE INDEX taxon_parent_id ON taxon (parent_id);
+
+-- No foreign key to taxon, as animals may live in the memory repository backend.
+ALTER TABLE animal
+    ADD COLUMN taxon_id INTEGER;
+
+CREATE INDEX animal_taxon_id ON animal (taxon_id);
+
+-- The sample animals of 0001_init, classified. Ids are fixed as the memory repository
+-- backend refers to them.
+INSERT INTO taxon (id, parent_id, rank, name)
+VALUES (1, NULL, 'kingdom', 'Animalia'),
+       (2, 1, 'phylum', 'Chordata'),
+       (3, 2, 'class', 'Mammalia'),
+       (4, 3, 'order', 'Carnivora'),
+       (5, 4, 'family', 'Felidae'),
+       (6, 5, 'genus', 'Panthera'),
+       (7, 6, 'species', 'Panthera leo'),
+       (8, 6, 'species', 'Panthera tigris'),
+       (9, 5, 'genus', 'Felis'),
+       (10, 9, 'species', 'Felis catus'),
+       (11, 4, 'family', 'Canidae'),
+       (12, 11, 'genus', 'Canis'),
+       (13, 12, 'species', 'Canis familiaris'),
+       (14, 4, 'family', 'Ursidae'),
+       (15, 14, 'genus', 'Ailuropoda'),
+       (16, 15, 'species', 'Ailuropoda melanoleuca'),
+       (17, 3, 'order', 'Proboscidea'),
+       (18, 17, 'family', 'Elephantidae'),
+       (19, 18, 'genus', 'Loxodonta'),
+       (20, 19, 'species', 'Loxodonta africana'),
+       (21, 3, 'order', 'Perissodactyla'),
+       (22, 21, 'family', 'Equidae'),
+       (23, 22, 'genus', 'Equus'),
+       (24, 23, 'species', 'Equus caballus'),
+       (25, 23, 'species', 'Equus quagga'),
+       (26, 3, 'order', 'Artiodactyla'),
+       (27, 26, 'family', 'Giraffidae'),
+       (28, 27, 'genus', 'Giraffa'),
+       (29, 28, 'species', 'Giraffa camelopardalis'),
+       (30, 26, 'family', 'Camelidae'),
+       (31, 30, 'genus', 'Lama'),
+       (32, 31, 'species', 'Lama glama'),
+       (33, 3, 'order', 'Diprotodontia'),
+       (34, 33, 'family', 'Phascolarctidae'),
+       (35, 34, 'genus', 'Phascolarctos'),
+       (36, 35, 'species', 'Phascolarctos cinereus'),
+       (37, 3, 'order', 'Primates');
+
+UPDATE animal
+SET taxon_id = CASE species
+                   WHEN 'dog' THEN 13
+                   WHEN 'cat' THEN 10
+                   WHEN 'lion' THEN 7
+                   WHEN 'tiger' THEN 8
+                   WHEN 'elephant' THEN 20
+                   WHEN 'monkey' THEN 37
+                   WHEN 'horse' THEN 24
+                   WHEN 'zebra' THEN 25
+                   WHEN 'giraffe' THEN 29
+                   WHEN 'panda' THEN 16
+                   WHEN 'llama' THEN 32
+                   WHEN 'koala' THEN 36
+    END
+WHERE taxon_id IS NULL;
\ No newline at end of file
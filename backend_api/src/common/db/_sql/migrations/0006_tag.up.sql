@@ -0,0 +1,15 @@
+CREATE TABLE tag
+(
+    id   INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE
+);
+
+-- No foreign key to animal, as animals may live in the memory repository backend.
+CREATE TABLE animal_tag
+(
+    animal_id INTEGER NOT NULL,
+    tag_id    INTEGER NOT NULL REFERENCES tag (id) ON DELETE CASCADE,
+    PRIMARY KEY (animal_id, tag_id)
+);
+
+CREATE INDEX animal_tag_tag_id ON animal_tag (tag_id);
\ No newline at end of file
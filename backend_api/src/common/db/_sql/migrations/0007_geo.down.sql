@@ -0,0 +1,4 @@
+DROP TABLE animal_sighting_rtree;
+DROP TABLE animal_sighting;
+DROP TABLE animal_habitat_rtree;
+DROP TABLE animal_habitat;
\ No newline at end of file
@@ -0,0 +1,49 @@
+-- No foreign key to animal, as animals may live in the memory repository backend.
+CREATE TABLE animal_habitat
+(
+    id        INTEGER PRIMARY KEY AUTOINCREMENT,
+    animal_id INTEGER NOT NULL,
+    -- GeoJSON polygon coordinates, validated on the way in.
+    polygon   TEXT    NOT NULL
+);
+
+CREATE INDEX animal_habitat_animal_id ON animal_habitat (animal_id);
+
+CREATE VIRTUAL TABLE animal_habitat_rtree USING rtree
+(
+    id,
+    min_lon, max_lon,
+    min_lat, max_lat
+);
+
+CREATE TRIGGER animal_habitat_rtree_delete
+    AFTER DELETE
+    ON animal_habitat
+BEGIN
+    DELETE FROM animal_habitat_rtree WHERE id = old.id;
+END;
+
+CREATE TABLE animal_sighting
+(
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    animal_id   INTEGER NOT NULL,
+    longitude   REAL    NOT NULL,
+    latitude    REAL    NOT NULL,
+    observed_at TEXT
+);
+
+CREATE INDEX animal_sighting_animal_id ON animal_sighting (animal_id);
+
+CREATE VIRTUAL TABLE animal_sighting_rtree USING rtree
+(
+    id,
+    min_lon, max_lon,
+    min_lat, max_lat
+);
+
+CREATE TRIGGER animal_sighting_rtree_delete
+    AFTER DELETE
+    ON animal_sighting
+BEGIN
+    DELETE FROM animal_sighting_rtree WHERE id = old.id;
+END;
\ No newline at end of file
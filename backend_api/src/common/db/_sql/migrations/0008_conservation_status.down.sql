@@ -0,0 +1,3 @@
+DROP INDEX animal_conservation_status;
+ALTER TABLE animal
+    DROP COLUMN conservation_status;
\ No newline at end of file
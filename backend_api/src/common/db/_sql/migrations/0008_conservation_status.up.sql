@@ -0,0 +1,26 @@
+ALTER TABLE animal
+    ADD COLUMN conservation_status TEXT NOT NULL DEFAULT 'NE'
+        CHECK (conservation_status IN ('LC', 'NT', 'VU', 'EN', 'CR', 'EW', 'EX', 'DD', 'NE'));
+
+CREATE INDEX animal_conservation_status ON animal (conservation_status);
+
+-- Move the status out of descriptions such as "Big cat (endangered)".
+UPDATE animal
+SET conservation_status = 'EN',
+    description         = TRIM(REPLACE(description, '(endangered)', ''))
+WHERE description LIKE '%(endangered)%'
+  AND TRIM(REPLACE(description, '(endangered)', '')) != '';
+
+UPDATE animal
+SET conservation_status = CASE species
+                              WHEN 'lion' THEN 'VU'
+                              WHEN 'tiger' THEN 'EN'
+                              WHEN 'elephant' THEN 'EN'
+                              WHEN 'monkey' THEN 'EN'
+                              WHEN 'zebra' THEN 'NT'
+                              WHEN 'giraffe' THEN 'VU'
+                              WHEN 'panda' THEN 'VU'
+                              WHEN 'koala' THEN 'VU'
+                              ELSE conservation_status
+    END
+WHERE conservation_status = 'NE';
\ No newline at end of file
@@ -0,0 +1 @@
+DROP TABLE animal_translation;
\ No newline at end of file
@@ -0,0 +1,9 @@
+-- No foreign key to animal, as animals may live in the memory repository backend.
+CREATE TABLE animal_translation
+(
+    animal_id   INTEGER NOT NULL,
+    locale      TEXT    NOT NULL,
+    species     TEXT    NOT NULL,
+    description TEXT    NOT NULL,
+    PRIMARY KEY (animal_id, locale)
+);
\ No newline at end of file
@@ -0,0 +1 @@
+DROP INDEX animal_species_unique;
\ No newline at end of file
@@ -0,0 +1,6 @@
+-- Species that clash, with the ids of the animals going by them.
+SELECT 'animals ' || group_concat(id, ', ') || ' share the species ' || quote(MIN(species))
+FROM animal
+GROUP BY trim(species) COLLATE NOCASE
+HAVING COUNT(*) > 1
+ORDER BY MIN(id);
@@ -0,0 +1 @@
+CREATE UNIQUE INDEX animal_species_unique ON animal (trim(species) COLLATE NOCASE);
\ No newline at end of file
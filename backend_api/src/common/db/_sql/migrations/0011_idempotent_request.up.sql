@@ -0,0 +1,15 @@
+-- Requests sent with an `Idempotency-Key`, their response is NULL while they are in flight.
+CREATE TABLE idempotent_request
+(
+    scope           TEXT NOT NULL,
+    idempotency_key TEXT NOT NULL,
+    fingerprint     TEXT NOT NULL,
+    status          INTEGER,
+    headers         TEXT,
+    body            BLOB,
+    created_at      TEXT NOT NULL,
+    expires_at      TEXT NOT NULL,
+    PRIMARY KEY (scope, idempotency_key)
+);
+
+CREATE INDEX idempotent_request_expires_at ON idempotent_request (expires_at);
@@ -0,0 +1 @@
+DROP TABLE job;
\ No newline at end of file
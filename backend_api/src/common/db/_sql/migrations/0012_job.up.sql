@@ -0,0 +1,19 @@
+-- Maintenance jobs, periodic ones carry their cron expression in `schedule`.
+CREATE TABLE job
+(
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind             TEXT    NOT NULL,
+    schedule         TEXT,
+    status           TEXT    NOT NULL,
+    attempts         INTEGER NOT NULL DEFAULT 0,
+    run_at           TEXT    NOT NULL,
+    lease_owner      TEXT,
+    lease_expires_at TEXT,
+    started_at       TEXT,
+    finished_at      TEXT,
+    last_error       TEXT,
+    created_at       TEXT    NOT NULL
+);
+
+CREATE UNIQUE INDEX job_periodic_kind ON job (kind) WHERE schedule IS NOT NULL;
+CREATE INDEX job_run_at ON job (status, run_at);
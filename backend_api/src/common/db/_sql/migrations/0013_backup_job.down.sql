@@ -0,0 +1 @@
+UPDATE job SET kind = 'copy_database' WHERE kind = 'backup';
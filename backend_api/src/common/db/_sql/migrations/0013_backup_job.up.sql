@@ -0,0 +1 @@
+UPDATE job SET kind = 'backup' WHERE kind = 'copy_database';
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS schema_migration
+(
+    version    INTEGER PRIMARY KEY,
+    name       TEXT NOT NULL,
+    applied_at TEXT NOT NULL
+);
\ No newline at end of file
@@ -0,0 +1,6 @@
+CREATE TABLE animal
+(
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    species     TEXT NOT NULL,
+    description TEXT NOT NULL
+);
\ No newline at end of file
@@ -0,0 +1,16 @@
+CREATE TABLE taxon
+(
+    id        INTEGER PRIMARY KEY AUTOINCREMENT,
+    parent_id INTEGER REFERENCES taxon (id),
+    rank      TEXT NOT NULL,
+    name      TEXT NOT NULL,
+    UNIQUE (parent_id, name)
+);
+
+CREATE INDEX taxon_parent_id ON taxon (parent_id);
+
+-- No foreign key to taxon, as animals may live in the memory repository backend.
+ALTER TABLE animal
+    ADD COLUMN taxon_id INTEGER;
+
+CREATE INDEX animal_taxon_id ON animal (taxon_id);
\ No newline at end of file
@@ -0,0 +1,102 @@
+use rusqlite::Connection;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+#[derive(Default)]
+struct HolderState {
+    timed_out: AtomicBool,
+    interrupted: AtomicBool,
+}
+
+/// A request's claim on the connections it locks. Once it times out, the statements it runs
+/// are aborted with `SQLITE_INTERRUPT`, while those of others sharing a connection are not.
+#[derive(Clone, Default)]
+pub struct ConnectionHolder(Arc<HolderState>);
+
+impl ConnectionHolder {
+    /// Abort the statement the request is running, and any it runs later.
+    pub fn time_out(&self) {
+        self.0.timed_out.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether a statement of the request was aborted since it timed out.
+    pub fn interrupted(&self) -> bool {
+        self.0.interrupted.load(Ordering::Relaxed)
+    }
+}
+
+/// The holder of a connection while it is locked, asked by its progress handler whether to
+/// abort the running statement.
+#[derive(Default)]
+pub(super) struct CurrentHolder(Mutex<Option<ConnectionHolder>>);
+
+impl CurrentHolder {
+    /// VM instructions between checks of the holder.
+    pub(super) const PROGRESS_OPS: i32 = 1000;
+
+    pub(super) fn should_interrupt(&self) -> bool {
+        let Ok(holder) = self.0.lock() else {
+            return false;
+        };
+        match holder.as_ref() {
+            Some(holder) if holder.0.timed_out.load(Ordering::Relaxed) => {
+                holder.0.interrupted.store(true, Ordering::Relaxed);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn set(&self, holder: Option<ConnectionHolder>) {
+        if let Ok(mut current) = self.0.lock() {
+            *current = holder;
+        }
+    }
+}
+
+/// The connection of a client, to be locked by whoever the client is lent to.
+pub struct ConnectionLock<'a> {
+    pub(super) conn: &'a Mutex<Connection>,
+    pub(super) current_holder: &'a CurrentHolder,
+    pub(super) holder: Option<&'a ConnectionHolder>,
+}
+
+impl<'a> ConnectionLock<'a> {
+    pub fn lock(self) -> Result<ConnectionGuard<'a>, PoisonError<MutexGuard<'a, Connection>>> {
+        let conn = self.conn.lock()?;
+        self.current_holder.set(self.holder.cloned());
+        Ok(ConnectionGuard {
+            conn,
+            current_holder: self.current_holder,
+        })
+    }
+}
+
+/// A locked connection, held by the request its client is lent to until dropped.
+pub struct ConnectionGuard<'a> {
+    conn: MutexGuard<'a, Connection>,
+    current_holder: &'a CurrentHolder,
+}
+
+impl Deref for ConnectionGuard<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        &self.conn
+    }
+}
+
+impl DerefMut for ConnectionGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Connection {
+        &mut self.conn
+    }
+}
+
+impl Drop for ConnectionGuard<'_> {
+    fn drop(&mut self) {
+        // Cleared before the connection is unlocked, so the next holder is never mistaken
+        // for this one.
+        self.current_holder.set(None);
+    }
+}
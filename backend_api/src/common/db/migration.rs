@@ -0,0 +1,354 @@
+use crate::common::error::FromIntoStackError;
+use chrono::{DateTime, Utc};
+use error_stack::{Report, ResultExt};
+use rusqlite::{Connection, OptionalExtension, named_params};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum MigrationError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Migration failed")]
+    MigrationFailed,
+}
+
+impl FromIntoStackError for MigrationError {}
+
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub up: &'static str,
+    pub down: &'static str,
+    /// Query for rows `up` cannot migrate, one line of text each. Any row fails the migration
+    /// before it runs, listing them.
+    pub guard: Option<&'static str>,
+}
+
+/// Migrations of the main database, which holds everything in single-tenant mode.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "init",
+        up: include_str!("_sql/migrations/0001_init.up.sql"),
+        down: include_str!("_sql/migrations/0001_init.down.sql"),
+        guard: None,
+    },
+    Migration {
+        version: 2,
+        name: "api_key",
+        up: include_str!("_sql/migrations/0002_api_key.up.sql"),
+        down: include_str!("_sql/migrations/0002_api_key.down.sql"),
+        guard: None,
+    },
+    Migration {
+        version: 3,
+        name: "tenant",
+        up: include_str!("_sql/migrations/0003_tenant.up.sql"),
+        down: include_str!("_sql/migrations/0003_tenant.down.sql"),
+        guard: None,
+    },
+    Migration {
+        version: 4,
+        name: "animal_photo",
+        up: include_str!("_sql/migrations/0004_animal_photo.up.sql"),
+        down: include_str!("_sql/migrations/0004_animal_photo.down.sql"),
+        guard: None,
+    },
+    Migration {
+        version: 5,
+        name: "taxonomy",
+        up: include_str!("_sql/migrations/0005_taxonomy.up.sql"),
+        down: include_str!("_sql/migrations/0005_taxonomy.down.sql"),
+        guard: None,
+    },
+    Migration {
+        version: 6,
+        name: "tag",
+        up: include_str!("_sql/migrations/0006_tag.up.sql"),
+        down: include_str!("_sql/migrations/0006_tag.down.sql"),
+        guard: None,
+    },
+    Migration {
+        version: 7,
+        name: "geo",
+        up: include_str!("_sql/migrations/0007_geo.up.sql"),
+        down: include_str!("_sql/migrations/0007_geo.down.sql"),
+        guard: None,
+    },
+    Migration {
+        version: 8,
+        name: "conservation_status",
+        up: include_str!("_sql/migrations/0008_conservation_status.up.sql"),
+        down: include_str!("_sql/migrations/0008_conservation_status.down.sql"),
+        guard: None,
+    },
+    Migration {
+        version: 9,
+        name: "animal_translation",
+        up: include_str!("_sql/migrations/0009_animal_translation.up.sql"),
+        down: include_str!("_sql/migrations/0009_animal_translation.down.sql"),
+        guard: None,
+    },
+    Migration {
+        version: 10,
+        name: "unique_species",
+        up: include_str!("_sql/migrations/0010_unique_species.up.sql"),
+        down: include_str!("_sql/migrations/0010_unique_species.down.sql"),
+        guard: Some(include_str!(
+            "_sql/migrations/0010_unique_species.guard.sql"
+        )),
+    },
+    Migration {
+        version: 11,
+        name: "idempotent_request",
+        up: include_str!("_sql/migrations/0011_idempotent_request.up.sql"),
+        down: include_str!("_sql/migrations/0011_idempotent_request.down.sql"),
+        guard: None,
+    },
+    Migration {
+        version: 12,
+        name: "job",
+        up: include_str!("_sql/migrations/0012_job.up.sql"),
+        down: include_str!("_sql/migrations/0012_job.down.sql"),
+        guard: None,
+    },
+    Migration {
+        version: 13,
+        name: "backup_job",
+        up: include_str!("_sql/migrations/0013_backup_job.up.sql"),
+        down: include_str!("_sql/migrations/0013_backup_job.down.sql"),
+        guard: None,
+    },
+];
+
+/// Migrations of a tenant database, which only holds that tenant's animal data. They run the
+/// files of the main migrations, bar those adding the sample animals.
+pub const TENANT_MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "init",
+        up: include_str!("_sql/tenant_migrations/0001_init.up.sql"),
+        down: include_str!("_sql/migrations/0001_init.down.sql"),
+        guard: None,
+    },
+    Migration {
+        version: 2,
+        name: "animal_photo",
+        up: include_str!("_sql/migrations/0004_animal_photo.up.sql"),
+        down: include_str!("_sql/migrations/0004_animal_photo.down.sql"),
+        guard: None,
+    },
+    Migration {
+        version: 3,
+        name: "taxonomy",
+        up: include_str!("_sql/tenant_migrations/0003_taxonomy.up.sql"),
+        down: include_str!("_sql/migrations/0005_taxonomy.down.sql"),
+        guard: None,
+    },
+    Migration {
+        version: 4,
+        name: "tag",
+        up: include_str!("_sql/migrations/0006_tag.up.sql"),
+        down: include_str!("_sql/migrations/0006_tag.down.sql"),
+        guard: None,
+    },
+    Migration {
+        version: 5,
+        name: "geo",
+        up: include_str!("_sql/migrations/0007_geo.up.sql"),
+        down: include_str!("_sql/migrations/0007_geo.down.sql"),
+        guard: None,
+    },
+    Migration {
+        version: 6,
+        name: "conservation_status",
+        up: include_str!("_sql/tenant_migrations/0006_conservation_status.up.sql"),
+        down: include_str!("_sql/migrations/0008_conservation_status.down.sql"),
+        guard: None,
+    },
+    Migration {
+        version: 7,
+        name: "animal_translation",
+        up: include_str!("_sql/migrations/0009_animal_translation.up.sql"),
+        down: include_str!("_sql/migrations/0009_animal_translation.down.sql"),
+        guard: None,
+    },
+    Migration {
+        version: 8,
+        name: "unique_species",
+        up: include_str!("_sql/migrations/0010_unique_species.up.sql"),
+        down: include_str!("_sql/migrations/0010_unique_species.down.sql"),
+        guard: Some(include_str!(
+            "_sql/migrations/0010_unique_species.guard.sql"
+        )),
+    },
+];
+
+pub struct MigrationStatus {
+    pub migration: &'static Migration,
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+fn table_exists(conn: &Connection, table: &str) -> Result<bool, Report<MigrationError>> {
+    conn.query_row(
+        "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = :name",
+        named_params! {":name": table},
+        |_| Ok(()),
+    )
+    .optional()
+    .map(|row| row.is_some())
+    .change_context(MigrationError::QueryError)
+}
+
+/// Create the bookkeeping table. Databases created before migrations existed already have
+/// the `init` schema, so it is recorded as applied rather than run again.
+fn ensure_migration_table(
+    conn: &Connection,
+    migrations: &'static [Migration],
+) -> Result<(), Report<MigrationError>> {
+    if table_exists(conn, "schema_migration")? {
+        return Ok(());
+    }
+    let legacy = table_exists(conn, "animal")?;
+
+    conn.execute_batch(include_str!("_sql/schema_migration.sql"))
+        .change_context(MigrationError::QueryError)?;
+    if legacy {
+        record_applied(conn, &migrations[0])?;
+    }
+    Ok(())
+}
+
+fn record_applied(conn: &Connection, migration: &Migration) -> Result<(), Report<MigrationError>> {
+    conn.execute(
+        "INSERT INTO schema_migration (version, name, applied_at) VALUES (:version, :name, :applied_at)",
+        named_params! {
+            ":version": migration.version,
+            ":name": migration.name,
+            ":applied_at": Utc::now(),
+        },
+    )
+    .change_context(MigrationError::QueryError)?;
+    Ok(())
+}
+
+fn applied_version(conn: &Connection) -> Result<i64, Report<MigrationError>> {
+    conn.query_row(
+        "SELECT COALESCE(MAX(version), 0) FROM schema_migration",
+        [],
+        |row| row.get(0),
+    )
+    .change_context(MigrationError::QueryError)
+}
+
+fn check_guard(conn: &Connection, migration: &Migration) -> Result<(), Report<MigrationError>> {
+    let Some(guard) = migration.guard else {
+        return Ok(());
+    };
+    let mut stmt = conn
+        .prepare(guard)
+        .change_context(MigrationError::QueryError)?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .change_context(MigrationError::QueryError)?
+        .collect::<Result<Vec<_>, _>>()
+        .change_context(MigrationError::QueryError)?;
+    if rows.is_empty() {
+        return Ok(());
+    }
+    Err(
+        Report::new(MigrationError::MigrationFailed).attach_printable(format!(
+            "Migration {} {} cannot migrate, fix these first:\n{}",
+            migration.version,
+            migration.name,
+            rows.join("\n")
+        )),
+    )
+}
+
+/// Apply every pending migration, each in its own transaction.
+pub fn migrate_up(
+    conn: &mut Connection,
+    migrations: &'static [Migration],
+) -> Result<Vec<&'static Migration>, Report<MigrationError>> {
+    ensure_migration_table(conn, migrations)?;
+    let current = applied_version(conn)?;
+
+    let mut applied = Vec::new();
+    for migration in migrations.iter().filter(|m| m.version > current) {
+        let tx = conn
+            .transaction()
+            .change_context(MigrationError::QueryError)?;
+        check_guard(&tx, migration)?;
+        tx.execute_batch(migration.up)
+            .change_context(MigrationError::MigrationFailed)
+            .attach_printable_lazy(|| {
+                format!("Migration {} {}", migration.version, migration.name)
+            })?;
+        record_applied(&tx, migration)?;
+        tx.commit().change_context(MigrationError::QueryError)?;
+        applied.push(migration);
+    }
+    Ok(applied)
+}
+
+/// Revert the latest `steps` applied migrations, newest first.
+pub fn migrate_down(
+    conn: &mut Connection,
+    migrations: &'static [Migration],
+    steps: usize,
+) -> Result<Vec<&'static Migration>, Report<MigrationError>> {
+    ensure_migration_table(conn, migrations)?;
+    let current = applied_version(conn)?;
+
+    let mut reverted = Vec::new();
+    for migration in migrations
+        .iter()
+        .rev()
+        .filter(|m| m.version <= current)
+        .take(steps)
+    {
+        let tx = conn
+            .transaction()
+            .change_context(MigrationError::QueryError)?;
+        tx.execute_batch(migration.down)
+            .change_context(MigrationError::MigrationFailed)
+            .attach_printable_lazy(|| {
+                format!("Migration {} {}", migration.version, migration.name)
+            })?;
+        tx.execute(
+            "DELETE FROM schema_migration WHERE version = :version",
+            named_params! {":version": migration.version},
+        )
+        .change_context(MigrationError::QueryError)?;
+        tx.commit().change_context(MigrationError::QueryError)?;
+        reverted.push(migration);
+    }
+    Ok(reverted)
+}
+
+pub fn migration_status(
+    conn: &Connection,
+    migrations: &'static [Migration],
+) -> Result<Vec<MigrationStatus>, Report<MigrationError>> {
+    ensure_migration_table(conn, migrations)?;
+
+    let mut stmt = conn
+        .prepare("SELECT applied_at FROM schema_migration WHERE version = :version")
+        .change_context(MigrationError::QueryError)?;
+
+    let mut statuses = Vec::new();
+    for migration in migrations {
+        let applied_at = stmt
+            .query_row(named_params! {":version": migration.version}, |row| {
+                row.get::<_, DateTime<Utc>>(0)
+            })
+            .optional()
+            .change_context(MigrationError::QueryError)?;
+        statuses.push(MigrationStatus {
+            migration,
+            applied_at,
+        });
+    }
+    Ok(statuses)
+}
@@ -1,17 +1,42 @@
 use crate::common::context::{Context, ContextError, FromContext};
+use crate::common::db::holder::{ConnectionHolder, ConnectionLock, CurrentHolder};
+use crate::common::db::migration::{MIGRATIONS, Migration, TENANT_MIGRATIONS, migrate_up};
 use crate::common::error::{ExtraResultExt, FromIntoStackError};
 use error_stack::{Report, ResultExt};
+use poem::Request;
 use rusqlite::Connection;
 use std::marker::PhantomData;
 use std::sync::{Arc, Mutex};
 use thiserror::Error;
 use tokio::sync::OnceCell;
 
-pub trait ConnectionMarker: Send + Sync {}
+pub mod holder;
+pub mod migration;
+#[cfg(test)]
+mod tests;
 
+pub trait ConnectionMarker: Send + Sync + 'static {
+    fn migrations() -> &'static [Migration];
+}
+
+/// The main database, from `sqlite.path`.
 pub struct DefaultConnection;
 
-impl ConnectionMarker for DefaultConnection {}
+impl ConnectionMarker for DefaultConnection {
+    fn migrations() -> &'static [Migration] {
+        MIGRATIONS
+    }
+}
+
+/// The database holding a tenant's animal data. In single-tenant mode that is the main
+/// database.
+pub struct TenantConnection;
+
+impl ConnectionMarker for TenantConnection {
+    fn migrations() -> &'static [Migration] {
+        TENANT_MIGRATIONS
+    }
+}
 
 #[derive(Error, Debug)]
 pub enum SqliteClientError {
@@ -25,54 +50,141 @@ pub enum SqliteClientError {
 
 impl FromIntoStackError for SqliteClientError {}
 
-pub struct SqliteClient<T = DefaultConnection>(Arc<Mutex<Connection>>, PhantomData<T>)
+/// A shared connection, lent to a request when it has a [`ConnectionHolder`].
+pub struct SqliteClient<T = DefaultConnection>(
+    Arc<Mutex<Connection>>,
+    Arc<CurrentHolder>,
+    Option<ConnectionHolder>,
+    PhantomData<T>,
+)
 where
     T: ConnectionMarker;
 
 impl<T: ConnectionMarker> SqliteClient<T> {
-    pub fn new(sqlite_path: String) -> Result<Self, Report<SqliteClientError>> {
+    /// Write-ahead logging lets the connections of backups and jobs read while requests
+    /// write; the `wal_checkpoint` job keeps the log short.
+    fn from_connection(conn: Connection) -> Result<Self, Report<SqliteClientError>> {
+        conn.execute_batch("PRAGMA foreign_keys = ON; PRAGMA journal_mode = WAL;")
+            .change_context(SqliteClientError::Connection)
+            .attach_critical("Sqlite Connection failed".to_string())?;
+
+        let current_holder = Arc::new(CurrentHolder::default());
+        let progress_holder = Arc::clone(&current_holder);
+        conn.progress_handler(
+            CurrentHolder::PROGRESS_OPS,
+            Some(move || progress_holder.should_interrupt()),
+        );
+        Ok(SqliteClient(
+            Arc::new(Mutex::new(conn)),
+            current_holder,
+            None,
+            PhantomData,
+        ))
+    }
+
+    fn migrated(self) -> Result<Self, Report<SqliteClientError>> {
+        {
+            let mut conn = self
+                .get_conn()
+                .lock()
+                .map_err(|_| SqliteClientError::InitFailed)?;
+            migrate_up(&mut conn, T::migrations())
+                .change_context(SqliteClientError::InitFailed)
+                .attach_critical("Init failed".to_string())?;
+        }
+        Ok(self)
+    }
+
+    /// Open the database without touching its schema.
+    pub fn open(sqlite_path: String) -> Result<Self, Report<SqliteClientError>> {
         if sqlite_path.is_empty() {
             return Err(SqliteClientError::SqliteFileEmpty
                 .into_stack_error_critical("Sqlite file path is empty".to_string()));
         }
-        let file_exist = std::fs::metadata(&sqlite_path).is_ok();
 
         let conn = Connection::open(sqlite_path)
             .change_context(SqliteClientError::Connection)
             .attach_critical("Sqlite Connection failed".to_string())?;
-        if !file_exist {
-            conn.execute_batch(include_str!("_sql/init.sql"))
-                .change_context(SqliteClientError::InitFailed)
-                .attach_critical("Init failed".to_string())?;
+        Self::from_connection(conn)
+    }
+
+    /// Open the database and bring its schema up to date.
+    pub fn new(sqlite_path: String) -> Result<Self, Report<SqliteClientError>> {
+        Self::open(sqlite_path)?.migrated()
+    }
+
+    /// Private, fully migrated database that lives as long as the client.
+    pub fn new_in_memory() -> Result<Self, Report<SqliteClientError>> {
+        let conn = Connection::open_in_memory()
+            .change_context(SqliteClientError::Connection)
+            .attach_critical("Sqlite Connection failed".to_string())?;
+        Self::from_connection(conn)?.migrated()
+    }
+
+    pub fn get_conn(&self) -> ConnectionLock<'_> {
+        ConnectionLock {
+            conn: &self.0,
+            current_holder: &self.1,
+            holder: self.2.as_ref(),
         }
+    }
 
-        Ok(SqliteClient(Arc::new(Mutex::new(conn)), PhantomData))
+    /// The client, lent to `req` if it has a holder.
+    pub fn for_request(self, req: &Request) -> Self {
+        match req.data::<ConnectionHolder>() {
+            Some(holder) => Self(self.0, self.1, Some(holder.clone()), PhantomData),
+            None => self,
+        }
     }
+}
 
-    pub fn get_conn(&self) -> &Mutex<Connection> {
-        self.0.as_ref()
+impl SqliteClient<TenantConnection> {
+    /// Use the main database for animal data, as single-tenant mode does.
+    pub fn single_tenant(sqlite_client: &SqliteClient) -> Self {
+        Self(
+            Arc::clone(&sqlite_client.0),
+            Arc::clone(&sqlite_client.1),
+            sqlite_client.2.clone(),
+            PhantomData,
+        )
     }
 }
 
 impl<T: ConnectionMarker> Clone for SqliteClient<T> {
     fn clone(&self) -> Self {
-        Self(Arc::clone(&self.0), PhantomData)
+        Self(
+            Arc::clone(&self.0),
+            Arc::clone(&self.1),
+            self.2.clone(),
+            PhantomData,
+        )
     }
 }
 
 static SQLITE_CLIENT_CACHE: OnceCell<SqliteClient> = OnceCell::const_new();
 
+impl SqliteClient {
+    /// The client the server shares between requests and background jobs, opened and
+    /// migrated on first use.
+    pub async fn shared(sqlite_path: &str) -> Result<Self, Report<SqliteClientError>> {
+        let sqlite_client = SQLITE_CLIENT_CACHE
+            .get_or_try_init(|| async { Self::new(sqlite_path.to_string()) })
+            .await?;
+        Ok(sqlite_client.clone())
+    }
+}
+
 impl FromContext for SqliteClient {
     async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
-        let sqlite_client: Result<&Self, Report<ContextError>> = SQLITE_CLIENT_CACHE
-            .get_or_try_init(|| async {
-                match ctx.config.upgrade() {
-                    None => Err(ContextError::Other.into()),
-                    Some(config) => Ok(Self::new(config.sqlite.path.clone())
-                        .change_context(ContextError::Other)?),
-                }
-            })
-            .await;
-        Ok(sqlite_client?.clone())
+        if let Some(sqlite_client) = ctx.overridden::<Self>() {
+            return Ok(sqlite_client.for_request(ctx.req));
+        }
+        match ctx.config.upgrade() {
+            None => Err(ContextError::Other.into()),
+            Some(config) => Ok(Self::shared(&config.sqlite.path)
+                .await
+                .change_context(ContextError::Other)?
+                .for_request(ctx.req)),
+        }
     }
 }
@@ -0,0 +1,37 @@
+use crate::common::db::migration::{MIGRATIONS, migrate_up};
+use rusqlite::Connection;
+
+#[test]
+fn clashing_species_fail_the_unique_species_migration() {
+    let mut conn = Connection::open_in_memory().expect("open");
+    migrate_up(&mut conn, &MIGRATIONS[..9]).expect("migrate to 9");
+    conn.execute_batch(
+        "INSERT INTO animal (id, species, description)
+         VALUES (100, 'Otter', 'An otter'), (101, ' otter', 'Another otter'), (102, 'Ébène', 'E')",
+    )
+    .expect("insert");
+
+    let report = match migrate_up(&mut conn, MIGRATIONS) {
+        Ok(_) => panic!("clashing species migrated"),
+        Err(err) => format!("{:?}", err),
+    };
+    assert!(
+        report.contains("animals 100, 101 share the species"),
+        "{}",
+        report
+    );
+    assert!(!report.contains("102"), "{}", report);
+
+    let species: Vec<String> = conn
+        .prepare("SELECT species FROM animal WHERE id >= 100 ORDER BY id")
+        .expect("prepare")
+        .query_map([], |row| row.get(0))
+        .expect("query")
+        .collect::<Result<_, _>>()
+        .expect("rows");
+    assert_eq!(species, vec!["Otter", " otter", "Ébène"]);
+
+    conn.execute("DELETE FROM animal WHERE id = 101", [])
+        .expect("delete");
+    migrate_up(&mut conn, MIGRATIONS).expect("migrate once the clash is gone");
+}
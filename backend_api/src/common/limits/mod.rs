@@ -0,0 +1,225 @@
+#[cfg(test)]
+mod tests;
+
+use crate::api_key::API_KEY_HEADER;
+use crate::common::config::Config;
+use crate::common::config::limits::RateLimitConfig;
+use crate::common::db::holder::ConnectionHolder;
+use crate::version::route_path;
+use lru::LruCache;
+use poem::http::header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, RETRY_AFTER, VARY};
+use poem::http::{HeaderValue, StatusCode};
+use poem::web::{Compress, CompressionAlgo};
+use poem::{Endpoint, IntoResponse, Request, Response};
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::OnceCell;
+use tokio::time::{sleep, timeout};
+
+/// Clients whose windows are kept, least recently seen first out.
+const RATE_LIMITED_CLIENTS: usize = 10_000;
+
+fn internal_error<T>(_: T) -> poem::Error {
+    poem::Error::from_status(StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+fn timed_out() -> poem::Error {
+    poem::Error::from_string("Request timed out", StatusCode::SERVICE_UNAVAILABLE)
+}
+
+/// Requests counted per client in its current window.
+#[derive(Clone)]
+pub struct RateLimiter(Arc<Mutex<LruCache<String, (Instant, u32)>>>);
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(LruCache::new(
+            NonZeroUsize::new(RATE_LIMITED_CLIENTS).unwrap_or(NonZeroUsize::MIN),
+        ))))
+    }
+}
+
+impl RateLimiter {
+    /// Count a request of `client`, or return how long it has to wait once it is over the
+    /// limit.
+    fn hit(&self, client: &str, rate_limit: &RateLimitConfig) -> Result<(), Duration> {
+        let Ok(mut clients) = self.0.lock() else {
+            return Ok(());
+        };
+        let window = Duration::from_secs(rate_limit.window_secs);
+        let now = Instant::now();
+        let (started, count) = clients.get_or_insert_mut(client.to_string(), || (now, 0));
+        if now.duration_since(*started) >= window {
+            *started = now;
+            *count = 0;
+        }
+        if *count >= rate_limit.requests {
+            return Err(window.saturating_sub(now.duration_since(*started)));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+static RATE_LIMITER_CACHE: OnceCell<RateLimiter> = OnceCell::const_new();
+
+/// A rate limiter added to the endpoint with `.data(...)`, or the shared one.
+async fn rate_limiter(req: &Request) -> RateLimiter {
+    match req.data::<RateLimiter>() {
+        Some(rate_limiter) => rate_limiter.clone(),
+        None => RATE_LIMITER_CACHE
+            .get_or_init(|| async { RateLimiter::default() })
+            .await
+            .clone(),
+    }
+}
+
+/// Address of the client without its port, which changes with every connection.
+pub fn client_ip(req: &Request) -> String {
+    match req.remote_addr().as_socket_addr() {
+        Some(addr) => addr.ip().to_string(),
+        None => req.remote_addr().to_string(),
+    }
+}
+
+/// Requests are counted per API key, or per address without one.
+fn rate_limit_client(req: &Request) -> String {
+    match req.header(API_KEY_HEADER) {
+        Some(api_key) => format!("key:{}", api_key),
+        None => format!("addr:{}", client_ip(req)),
+    }
+}
+
+/// The configured coding the client accepts with the highest quality, the first configured
+/// one on a tie.
+fn negotiate(accept_encoding: &str, algorithms: &[String]) -> Option<CompressionAlgo> {
+    let codings: Vec<(&str, f32)> = accept_encoding
+        .split(',')
+        .filter_map(|coding| {
+            let mut params = coding.split(';');
+            let name = params.next()?.trim();
+            let quality = match params.find_map(|param| param.trim().strip_prefix("q=")) {
+                Some(quality) => quality.trim().parse().ok()?,
+                None => 1.0,
+            };
+            Some((name, quality))
+        })
+        .collect();
+    let quality = |name: &str| {
+        codings
+            .iter()
+            .find(|(coding, _)| coding.eq_ignore_ascii_case(name))
+            .or_else(|| codings.iter().find(|(coding, _)| *coding == "*"))
+            .map_or(0.0, |(_, quality)| *quality)
+    };
+
+    let mut best = None;
+    let mut best_quality = 0.0;
+    for name in algorithms {
+        let quality = quality(name);
+        if quality > best_quality {
+            best = name.parse().ok();
+            best_quality = quality;
+        }
+    }
+    best
+}
+
+/// Answer clients over `poem.limits.rate_limit` with 429, reject request bodies over
+/// `poem.limits.max_body_bytes` with 413, and answer requests still running once their
+/// timeout is up with 503. Handlers run their queries in place, so a timed out request also
+/// aborts the queries it runs on the main or tenant database, which it is most likely stuck
+/// in. Queries of other requests on the same connection run on.
+pub async fn limited<E: Endpoint>(next: Arc<E>, mut req: Request) -> poem::Result<Response> {
+    let config = Config::for_request(&req).await.map_err(internal_error)?;
+    let limits = &config.poem.limits;
+
+    if limits.rate_limit.requests > 0 {
+        let client = rate_limit_client(&req);
+        if let Err(wait) = rate_limiter(&req).await.hit(&client, &limits.rate_limit) {
+            return Ok(Response::builder()
+                .status(StatusCode::TOO_MANY_REQUESTS)
+                .header(RETRY_AFTER, wait.as_secs_f64().ceil().max(1.0).to_string())
+                .body("Too many requests"));
+        }
+    }
+
+    match req
+        .header(CONTENT_LENGTH)
+        .and_then(|length| length.parse::<usize>().ok())
+    {
+        Some(length) if length > limits.max_body_bytes => {
+            return Err(poem::Error::from_status(StatusCode::PAYLOAD_TOO_LARGE));
+        }
+        Some(_) => {}
+        None => {
+            let body = req
+                .take_body()
+                .into_bytes_limit(limits.max_body_bytes)
+                .await?;
+            req.set_body(body);
+        }
+    }
+
+    let duration = limits.timeout(route_path(req.uri().path()));
+    let holder = ConnectionHolder::default();
+    req.set_data(holder.clone());
+    let watchdog = tokio::spawn({
+        let holder = holder.clone();
+        async move {
+            sleep(duration).await;
+            holder.time_out();
+        }
+    });
+
+    let result = timeout(duration, next.call(req)).await;
+    watchdog.abort();
+    let resp = match result {
+        Ok(Ok(resp)) => resp.into_response(),
+        Ok(Err(err)) => err.into_response(),
+        Err(_) => return Err(timed_out()),
+    };
+    // A query aborted at the deadline fails the request with a server error.
+    if resp.status().is_server_error() && holder.interrupted() {
+        return Err(timed_out());
+    }
+    Ok(resp)
+}
+
+/// Compress response bodies of the media types in `poem.limits.compression.content_types`
+/// and of at least `min_size_bytes`, with the coding the client prefers.
+pub async fn compressed<E: Endpoint>(next: Arc<E>, req: Request) -> poem::Result<Response> {
+    let config = Config::for_request(&req).await.map_err(internal_error)?;
+    let compression = &config.poem.limits.compression;
+    let algorithm = negotiate(
+        req.header(ACCEPT_ENCODING).unwrap_or_default(),
+        &compression.algorithms,
+    );
+
+    let mut resp = match next.call(req).await {
+        Ok(resp) => resp.into_response(),
+        Err(err) => err.into_response(),
+    };
+    if !compression.enabled
+        || resp.headers().contains_key(CONTENT_ENCODING)
+        || !resp
+            .content_type()
+            .is_some_and(|content_type| compression.compresses(content_type))
+    {
+        return Ok(resp);
+    }
+    resp.headers_mut()
+        .append(VARY, HeaderValue::from_static("accept-encoding"));
+    let Some(algorithm) = algorithm else {
+        return Ok(resp);
+    };
+
+    let body = resp.take_body().into_bytes().await?;
+    let small = body.len() < compression.min_size_bytes;
+    resp.set_body(body);
+    if small {
+        return Ok(resp);
+    }
+    Ok(Compress::new(resp, algorithm).into_response())
+}
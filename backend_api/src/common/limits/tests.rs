@@ -0,0 +1,255 @@
+use crate::common::config::Config;
+use crate::common::config::limits::{CompressionConfig, LimitsConfig, RateLimitConfig};
+use crate::common::config::poem::PoemConfig;
+use crate::common::db::SqliteClient;
+use crate::common::limits::limited;
+use crate::test_support::TestApp;
+use poem::endpoint::make;
+use poem::http::StatusCode;
+use poem::test::TestClient;
+use poem::{EndpointExt, Request};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn limits_app(limits: LimitsConfig) -> TestApp {
+    TestApp::builder()
+        .config(Config {
+            poem: Arc::new(PoemConfig {
+                limits,
+                ..PoemConfig::default()
+            }),
+            ..Config::default()
+        })
+        .build()
+}
+
+#[tokio::test]
+async fn bodies_over_the_limit_are_rejected() {
+    let app = limits_app(LimitsConfig {
+        max_body_bytes: 16,
+        ..LimitsConfig::default()
+    });
+    let body = r#"{"species": "otter", "description": "Floats on its back"}"#;
+
+    app.client
+        .post("/animal/add")
+        .header("Content-Type", "application/json")
+        .header("Content-Length", body.len())
+        .body(body)
+        .send()
+        .await
+        .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+    app.client
+        .post("/animal/add")
+        .header("Content-Type", "application/json")
+        .body(body)
+        .send()
+        .await
+        .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn responses_are_compressed_with_the_preferred_coding() {
+    let app = limits_app(LimitsConfig {
+        compression: CompressionConfig {
+            min_size_bytes: 0,
+            ..CompressionConfig::default()
+        },
+        ..LimitsConfig::default()
+    });
+
+    for (accept_encoding, coding) in [
+        ("gzip, br;q=0.5", "gzip"),
+        ("gzip, zstd, br", "br"),
+        ("*", "br"),
+    ] {
+        let res = app
+            .client
+            .get("/animal")
+            .header("Accept-Encoding", accept_encoding)
+            .send()
+            .await;
+        res.assert_status_is_ok();
+        res.assert_header("Content-Encoding", coding);
+        res.assert_header("Vary", "accept-encoding");
+    }
+
+    let res = app
+        .client
+        .get("/animal")
+        .header("Accept-Encoding", "gzip;q=0, deflate")
+        .send()
+        .await;
+    res.assert_header_is_not_exist("Content-Encoding");
+}
+
+#[tokio::test]
+async fn small_responses_are_not_compressed() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .get("/")
+        .header("Accept-Encoding", "gzip")
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    res.assert_header_is_not_exist("Content-Encoding");
+    res.assert_json(serde_json::json!({"message": "Hello world"}))
+        .await;
+}
+
+#[tokio::test]
+async fn clients_over_the_rate_limit_are_told_when_to_retry() {
+    let app = limits_app(LimitsConfig {
+        rate_limit: RateLimitConfig {
+            requests: 2,
+            window_secs: 60,
+        },
+        ..LimitsConfig::default()
+    });
+
+    for _ in 0..2 {
+        app.client
+            .get("/")
+            .header("X-Api-Key", "rate-limited-first")
+            .send()
+            .await
+            .assert_status_is_ok();
+    }
+    let res = app
+        .client
+        .get("/")
+        .header("X-Api-Key", "rate-limited-first")
+        .send()
+        .await;
+    res.assert_status(StatusCode::TOO_MANY_REQUESTS);
+    res.assert_header("Retry-After", "60");
+
+    app.client
+        .get("/")
+        .header("X-Api-Key", "rate-limited-second")
+        .send()
+        .await
+        .assert_status_is_ok();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn timed_out_requests_interrupt_their_query() {
+    let sqlite_client: SqliteClient = SqliteClient::new_in_memory().expect("in-memory database");
+    let config = Config {
+        poem: Arc::new(PoemConfig {
+            limits: LimitsConfig {
+                route_timeout_secs: BTreeMap::from([("/slow".to_string(), 1)]),
+                ..LimitsConfig::default()
+            },
+            ..PoemConfig::default()
+        }),
+        ..Config::default()
+    };
+    let query_client = sqlite_client.clone();
+    let endpoint = make(move |req: Request| {
+        let sqlite_client = query_client.clone().for_request(&req);
+        async move {
+            let conn = sqlite_client.get_conn().lock().expect("lock");
+            conn.query_row(
+                "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n) \
+                 SELECT COUNT(*) FROM n",
+                [],
+                |row| row.get::<_, i64>(0),
+            )
+            .map(|_| "done")
+            .map_err(|_| poem::Error::from_status(StatusCode::INTERNAL_SERVER_ERROR))
+        }
+    })
+    .around(limited)
+    .data(sqlite_client)
+    .data(Arc::new(config));
+
+    TestClient::new(endpoint)
+        .get("/v1/slow")
+        .send()
+        .await
+        .assert_status(StatusCode::SERVICE_UNAVAILABLE);
+}
+
+fn slow_config() -> Config {
+    Config {
+        poem: Arc::new(PoemConfig {
+            limits: LimitsConfig {
+                route_timeout_secs: BTreeMap::from([("/slow".to_string(), 1)]),
+                ..LimitsConfig::default()
+            },
+            ..PoemConfig::default()
+        }),
+        ..Config::default()
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn timed_out_requests_leave_queries_of_others_alone() {
+    let sqlite_client: SqliteClient = SqliteClient::new_in_memory().expect("in-memory database");
+    let other_client = sqlite_client.clone();
+    let (locked, wait_for_lock) = std::sync::mpsc::channel();
+    // Another user of the connection keeps it busy past the timeout of the request below.
+    let other = std::thread::spawn(move || {
+        let conn = other_client.get_conn().lock().expect("lock");
+        locked.send(()).expect("send");
+        let started = Instant::now();
+        while started.elapsed() < Duration::from_secs(2) {
+            conn.query_row(
+                "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 100000) \
+                 SELECT COUNT(*) FROM n",
+                [],
+                |row| row.get::<_, i64>(0),
+            )?;
+        }
+        Ok::<_, rusqlite::Error>(())
+    });
+    wait_for_lock.recv().expect("locked");
+
+    let query_client = sqlite_client.clone();
+    let endpoint = make(move |req: Request| {
+        let sqlite_client = query_client.clone().for_request(&req);
+        async move {
+            let conn = sqlite_client.get_conn().lock().expect("lock");
+            conn.query_row(
+                "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n) \
+                 SELECT COUNT(*) FROM n",
+                [],
+                |row| row.get::<_, i64>(0),
+            )
+            .map(|_| "done")
+            .map_err(|_| poem::Error::from_status(StatusCode::INTERNAL_SERVER_ERROR))
+        }
+    })
+    .around(limited)
+    .data(Arc::new(slow_config()));
+
+    TestClient::new(endpoint)
+        .get("/v1/slow")
+        .send()
+        .await
+        .assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    other
+        .join()
+        .expect("other user")
+        .expect("queries not interrupted");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn slow_server_errors_are_not_timeouts() {
+    let endpoint = make(|_: Request| async {
+        std::thread::sleep(Duration::from_millis(1500));
+        Err::<&str, _>(poem::Error::from_status(StatusCode::INTERNAL_SERVER_ERROR))
+    })
+    .around(limited)
+    .data(Arc::new(slow_config()));
+
+    TestClient::new(endpoint)
+        .get("/v1/slow")
+        .send()
+        .await
+        .assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+}
@@ -0,0 +1,75 @@
+use crate::common::config::Config;
+use crate::common::locale::negotiation::{accepted_languages, best_match, lookup_chain};
+use poem::http::header::{ACCEPT_LANGUAGE, CONTENT_LANGUAGE};
+use poem::http::{HeaderValue, StatusCode};
+use poem::{Endpoint, IntoResponse, Request, Response};
+use shared::i18n::bundle_languages;
+use shared::validation::types::locale_tag::LocaleTag;
+use std::sync::Arc;
+
+/// Language asked for with `?lang=`, which overrides `Accept-Language`.
+fn lang_query(req: &Request) -> Option<LocaleTag> {
+    req.uri()
+        .query()?
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("lang="))
+        .and_then(|lang| LocaleTag::parse(lang).ok())
+}
+
+/// Replace `Accept-Language` with the whole lookup chain, `fr-CA` becoming
+/// `fr-CA, fr;q=0.999, en;q=0.998` through the configured fallback and default languages, so
+/// messages and animals are negotiated alike. Responses without a `Content-Language` of their
+/// own get the language of their messages.
+pub async fn negotiated_language<E: Endpoint>(
+    next: Arc<E>,
+    mut req: Request,
+) -> poem::Result<Response> {
+    let config = Config::for_request(&req)
+        .await
+        .map_err(|_| poem::Error::from_status(StatusCode::INTERNAL_SERVER_ERROR))?;
+
+    let accepted = match lang_query(&req) {
+        Some(lang) => vec![lang],
+        None => accepted_languages(
+            req.headers()
+                .get(ACCEPT_LANGUAGE)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default(),
+        ),
+    };
+    let fallback: Vec<LocaleTag> = config
+        .locale
+        .fallback
+        .iter()
+        .filter_map(|locale| LocaleTag::parse(locale).ok())
+        .collect();
+    let default = LocaleTag::parse(&config.locale.default).unwrap_or_default();
+    let chain = lookup_chain(&accepted, &fallback, &default);
+
+    let header = chain
+        .iter()
+        .enumerate()
+        .map(|(index, tag)| match index {
+            0 => tag.as_str().to_string(),
+            _ => format!(
+                "{};q={:.3}",
+                tag.as_str(),
+                1.0 - index.min(999) as f32 / 1000.0
+            ),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    if let Ok(header) = HeaderValue::from_str(&header) {
+        req.headers_mut().insert(ACCEPT_LANGUAGE, header);
+    }
+
+    let bundles = bundle_languages();
+    let language = best_match(&chain, &bundles.iter().collect::<Vec<_>>())
+        .and_then(|tag| HeaderValue::from_str(tag.as_str()).ok());
+
+    let mut resp = next.call(req).await?.into_response();
+    if let Some(language) = language.filter(|_| !resp.headers().contains_key(CONTENT_LANGUAGE)) {
+        resp.headers_mut().insert(CONTENT_LANGUAGE, language);
+    }
+    Ok(resp)
+}
@@ -1,61 +1,67 @@
-use cjtoolkit_structured_validator::common::locale::{LocaleData, LocaleValue, ValidateErrorStore};
+use crate::common::object::ValidationErrorObject;
+use cjtoolkit_structured_validator::common::locale::{LocaleValue, ValidateErrorStore};
 use poem::error::I18NError;
-use poem::i18n::{I18NArgs, I18NResources, Locale};
-use std::sync::Arc;
+use poem::i18n::{I18NResources, Locale};
+use serde_json::Value;
+use shared::i18n::{BUNDLES, LocaleForData};
 
-pub fn build_resources() -> Result<I18NResources, I18NError> {
-    let english = include_str!("_locale/english.ftl");
-    let french = include_str!("_locale/french.ftl");
+pub mod middleware;
+pub mod negotiation;
+#[cfg(test)]
+mod tests;
 
-    I18NResources::builder()
-        .add_ftl("en-GB", english)
-        .add_ftl("en-US", english)
-        .add_ftl("fr-FR", french)
+pub fn build_resources() -> Result<I18NResources, I18NError> {
+    BUNDLES
+        .iter()
+        .fold(I18NResources::builder(), |builder, (language, ftl)| {
+            builder.add_ftl(*language, *ftl)
+        })
         .build()
 }
 
-pub trait LocaleForData {
-    fn get_translation(&self, locale: &Locale, original: String) -> String;
-}
-
-impl LocaleForData for LocaleData {
-    fn get_translation(&self, locale: &Locale, original: String) -> String {
-        if !self.args.is_empty() {
-            let mut values = I18NArgs::default();
-            for (key, value) in self.args.iter() {
-                match value {
-                    LocaleValue::String(string) => {
-                        values = values.set::<String, String>(key.clone(), string.clone());
-                    }
-                    LocaleValue::Uint(unit) => {
-                        values = values.set::<String, usize>(key.clone(), *unit);
-                    }
-                    LocaleValue::Int(int) => {
-                        values = values.set::<String, isize>(key.clone(), *int);
-                    }
-                    LocaleValue::Float(float) => {
-                        values = values.set::<String, f64>(key.clone(), *float);
-                    }
-                }
-            }
-            locale
-                .text_with_args(self.name.clone(), values)
-                .unwrap_or(original)
-        } else {
-            locale.text(self.name.clone()).unwrap_or(original)
-        }
+fn locale_value_to_json(value: &LocaleValue) -> Value {
+    match value {
+        LocaleValue::String(string) => Value::from(string.clone()),
+        LocaleValue::Uint(unit) => Value::from(*unit),
+        LocaleValue::Int(int) => Value::from(*int),
+        LocaleValue::Float(float) => Value::from(*float),
     }
 }
 
-pub trait LocaleForStore {
-    fn as_translated_message(&self, locale: &Locale) -> Arc<[String]>;
+pub trait LocaleForErrors {
+    /// The errors of `field` with their code and arguments, the messages left in English
+    /// without a locale.
+    fn as_validation_errors(
+        &self,
+        field: &str,
+        locale: Option<&Locale>,
+    ) -> Vec<ValidationErrorObject>;
 }
 
-impl LocaleForStore for ValidateErrorStore {
-    fn as_translated_message(&self, locale: &Locale) -> Arc<[String]> {
+impl LocaleForErrors for ValidateErrorStore {
+    fn as_validation_errors(
+        &self,
+        field: &str,
+        locale: Option<&Locale>,
+    ) -> Vec<ValidationErrorObject> {
         self.0
             .iter()
-            .map(|e| e.1.get_locale_data().get_translation(locale, e.0.clone()))
+            .map(|e| {
+                let data = e.1.get_locale_data();
+                ValidationErrorObject {
+                    field: field.to_string(),
+                    code: data.name.clone(),
+                    args: data
+                        .args
+                        .iter()
+                        .map(|(key, value)| (key.clone(), locale_value_to_json(value)))
+                        .collect(),
+                    message: match locale {
+                        Some(locale) => data.get_translation(locale, e.0.clone()),
+                        None => e.0.clone(),
+                    },
+                }
+            })
             .collect()
     }
 }
@@ -0,0 +1,62 @@
+use shared::validation::types::locale_tag::LocaleTag;
+
+/// Language ranges of an `Accept-Language` header, most preferred first. The wildcard,
+/// malformed ranges and those with `q=0` are left out.
+pub fn accepted_languages(header: &str) -> Vec<LocaleTag> {
+    let mut ranges: Vec<(LocaleTag, f32)> = header
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';');
+            let tag = LocaleTag::parse(parts.next()?).ok()?;
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+            (quality > 0.0).then_some((tag, quality))
+        })
+        .collect();
+    // Stable, so ranges of equal quality keep the order they were sent in.
+    ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranges.into_iter().map(|(tag, _)| tag).collect()
+}
+
+/// Languages to look for, in order: each accepted range followed by its shorter forms,
+/// `fr-CA` then `fr`, then the fallback languages and finally the source language.
+pub fn lookup_chain(
+    accepted: &[LocaleTag],
+    fallback: &[LocaleTag],
+    source: &LocaleTag,
+) -> Vec<LocaleTag> {
+    let mut chain: Vec<LocaleTag> = Vec::new();
+    for tag in accepted.iter().chain(fallback).chain([source]) {
+        let subtags: Vec<&str> = tag.as_str().split('-').collect();
+        for len in (1..=subtags.len()).rev() {
+            let Ok(candidate) = LocaleTag::parse(&subtags[..len].join("-")) else {
+                continue;
+            };
+            if !chain.contains(&candidate) {
+                chain.push(candidate);
+            }
+        }
+    }
+    chain
+}
+
+/// The first of `available` the chain asks for. A bare language in the chain also matches
+/// the regional forms of it, after an exact match.
+pub fn best_match<'a>(chain: &[LocaleTag], available: &[&'a LocaleTag]) -> Option<&'a LocaleTag> {
+    chain.iter().find_map(|wanted| {
+        available
+            .iter()
+            .find(|tag| **tag == wanted)
+            .or_else(|| {
+                (wanted.as_str() == wanted.language())
+                    .then(|| {
+                        available
+                            .iter()
+                            .find(|tag| tag.language() == wanted.language())
+                    })
+                    .flatten()
+            })
+            .copied()
+    })
+}
@@ -0,0 +1,88 @@
+use crate::common::config::Config;
+use crate::common::config::locale::LocaleConfig;
+use crate::test_support::TestApp;
+use poem::http::StatusCode;
+use poem::test::TestResponse;
+use serde_json::json;
+use shared::i18n::{bundle_keys, check_bundle_keys};
+use std::sync::Arc;
+
+async fn add_nameless(app: &TestApp, uri: &str, accept_language: Option<&str>) -> TestResponse {
+    let mut req = app
+        .client
+        .post(uri)
+        .body_json(&json!({"species": "", "description": "Nameless"}));
+    if let Some(accept_language) = accept_language {
+        req = req.header("Accept-Language", accept_language);
+    }
+    let res = req.send().await;
+    res.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    res
+}
+
+async fn assert_species_error(res: TestResponse, message: &str) {
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("species")
+        .assert_string_array(&[message]);
+}
+
+#[test]
+fn bundles_define_the_same_keys() {
+    check_bundle_keys().expect("bundles match the English one");
+}
+
+#[test]
+fn bundle_keys_skip_comments_and_variants() {
+    let keys = bundle_keys(
+        "# Title\nhello = Hello\nplural =\n    { $n ->\n        [one] one\n       *[other] many\n    }\n",
+    );
+    assert_eq!(keys.into_iter().collect::<Vec<_>>(), ["hello", "plural"]);
+}
+
+#[tokio::test]
+async fn regional_and_bare_languages_find_their_bundle() {
+    let app = TestApp::builder().build();
+
+    let res = add_nameless(&app, "/animal/add", Some("fr-CA")).await;
+    res.assert_header("Content-Language", "fr");
+    assert_species_error(res, "Ne peut pas être vide").await;
+
+    let res = add_nameless(&app, "/animal/add", Some("it, es-MX;q=0.5")).await;
+    res.assert_header("Content-Language", "es");
+    assert_species_error(res, "No puede estar vacío").await;
+
+    let res = add_nameless(&app, "/animal/add", Some("en")).await;
+    res.assert_header("Content-Language", "en");
+    assert_species_error(res, "Cannot be empty").await;
+}
+
+#[tokio::test]
+async fn lang_query_overrides_accept_language() {
+    let app = TestApp::builder().build();
+
+    let res = add_nameless(&app, "/animal/add?lang=de", Some("fr")).await;
+    res.assert_header("Content-Language", "de");
+    assert_species_error(res, "Darf nicht leer sein").await;
+}
+
+#[tokio::test]
+async fn unmatched_languages_fall_back_to_the_configured_default() {
+    let config = Config {
+        locale: Arc::new(LocaleConfig {
+            default: "es".to_string(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let app = TestApp::builder().config(config).build();
+
+    let res = add_nameless(&app, "/animal/add", Some("ja")).await;
+    res.assert_header("Content-Language", "es");
+    assert_species_error(res, "No puede estar vacío").await;
+
+    let res = add_nameless(&app, "/animal/add", None).await;
+    assert_species_error(res, "No puede estar vacío").await;
+}
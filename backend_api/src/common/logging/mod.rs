@@ -0,0 +1,32 @@
+use crate::common::config::{Config, ConfigError};
+use error_stack::{Report, ResultExt};
+use tracing::warn;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload};
+
+/// Log to stderr at `log.level`, which follows config reloads.
+pub async fn init_logging(config: &Config) -> Result<(), Report<ConfigError>> {
+    let (filter, handle) = reload::Layer::new(config.log.filter()?);
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer().with_writer(std::io::stderr))
+        .try_init()
+        .change_context(ConfigError::ValidationError)
+        .attach_printable("A logger is already installed")?;
+
+    let mut receiver = Config::subscribe().await?;
+    tokio::spawn(async move {
+        while receiver.changed().await.is_ok() {
+            let config = receiver.borrow_and_update().clone();
+            // A reloaded config has been validated, so its filter parses.
+            let Ok(filter) = config.log.filter() else {
+                continue;
+            };
+            if let Err(err) = handle.reload(filter) {
+                warn!("Log level not reloaded: {}", err);
+            }
+        }
+    });
+    Ok(())
+}
@@ -1,8 +1,12 @@
 pub mod cache_local;
 pub mod config;
 pub mod context;
+pub mod cors;
 pub mod db;
 pub mod error;
+pub mod limits;
 pub mod locale;
+pub mod logging;
 pub mod object;
 pub mod results;
+pub mod security_headers;
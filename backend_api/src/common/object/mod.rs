@@ -1,6 +1,43 @@
+use poem::i18n::Locale;
 use poem_openapi::Object;
+use serde_json::Value;
+use shared::i18n::{MessageId, Translator};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Object)]
 pub struct Message {
     pub message: String,
 }
+
+/// A failed validation rule, for clients that translate the message themselves or match on
+/// the rule.
+#[derive(Debug, Object, Clone, PartialEq)]
+pub struct ValidationErrorObject {
+    pub field: String,
+    /// Key of the message in the locale files, such as `validate-max-length`.
+    pub code: String,
+    /// Arguments of the message, such as `max` for `validate-max-length`.
+    pub args: BTreeMap<String, Value>,
+    pub message: String,
+}
+
+impl ValidationErrorObject {
+    /// Error of a rule checked outside the validators, whose message takes no arguments.
+    pub fn from_message(field: &str, id: MessageId, locale: &Locale) -> Self {
+        Self {
+            field: field.to_string(),
+            code: id.key().to_string(),
+            args: BTreeMap::new(),
+            message: locale.message(id),
+        }
+    }
+}
+
+/// Messages of the errors of `field`, the string form error objects kept from before codes.
+pub fn field_messages(errors: &[ValidationErrorObject], field: &str) -> Vec<String> {
+    errors
+        .iter()
+        .filter(|error| error.field == field)
+        .map(|error| error.message.clone())
+        .collect()
+}
@@ -0,0 +1,56 @@
+use crate::common::config::Config;
+use crate::version::route_path;
+use poem::http::header::{
+    CONTENT_SECURITY_POLICY, REFERRER_POLICY, STRICT_TRANSPORT_SECURITY, X_CONTENT_TYPE_OPTIONS,
+};
+use poem::http::{HeaderValue, StatusCode};
+use poem::{Endpoint, IntoResponse, Request, Response};
+use std::sync::Arc;
+
+#[cfg(test)]
+mod tests;
+
+/// Whether `path` is a page of the Swagger UI of a version.
+fn is_docs(path: &str) -> bool {
+    let path = route_path(path);
+    path == "/docs" || path.starts_with("/docs/")
+}
+
+/// Add `X-Content-Type-Options`, `Referrer-Policy` and, over TLS, `Strict-Transport-Security`
+/// to every response, error responses included, and a `Content-Security-Policy` to the
+/// Swagger UI. Headers an endpoint set itself are kept.
+pub async fn security_headers<E: Endpoint>(next: Arc<E>, req: Request) -> poem::Result<Response> {
+    let config = Config::for_request(&req)
+        .await
+        .map_err(|_| poem::Error::from_status(StatusCode::INTERNAL_SERVER_ERROR))?;
+    let security_headers = &config.security_headers;
+    let docs = is_docs(req.uri().path());
+
+    let mut resp = match next.call(req).await {
+        Ok(resp) => resp.into_response(),
+        Err(err) => err.into_response(),
+    };
+    let headers = [
+        (X_CONTENT_TYPE_OPTIONS, Some("nosniff".to_string())),
+        (
+            REFERRER_POLICY,
+            Some(security_headers.referrer_policy.clone()),
+        ),
+        (
+            STRICT_TRANSPORT_SECURITY,
+            security_headers
+                .tls
+                .then(|| format!("max-age={}", security_headers.hsts_max_age_secs)),
+        ),
+        (
+            CONTENT_SECURITY_POLICY,
+            docs.then(|| security_headers.docs_content_security_policy.clone()),
+        ),
+    ];
+    for (name, value) in headers {
+        if let Some(value) = value.and_then(|value| HeaderValue::from_str(&value).ok()) {
+            resp.headers_mut().entry(name).or_insert(value);
+        }
+    }
+    Ok(resp)
+}
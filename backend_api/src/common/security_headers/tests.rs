@@ -0,0 +1,53 @@
+use crate::common::config::Config;
+use crate::common::config::security_headers::SecurityHeadersConfig;
+use crate::test_support::TestApp;
+use poem::http::StatusCode;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn every_response_gets_the_security_headers() {
+    let app = TestApp::builder().build();
+
+    for (path, status) in [("/", StatusCode::OK), ("/missing", StatusCode::NOT_FOUND)] {
+        let res = app.client.get(path).send().await;
+        res.assert_status(status);
+        res.assert_header("X-Content-Type-Options", "nosniff");
+        res.assert_header("Referrer-Policy", "no-referrer");
+        res.assert_header_is_not_exist("Strict-Transport-Security");
+        res.assert_header_is_not_exist("Content-Security-Policy");
+    }
+}
+
+#[tokio::test]
+async fn the_swagger_ui_gets_a_content_security_policy() {
+    let app = TestApp::builder().build();
+
+    for path in ["/docs", "/v2/docs"] {
+        let res = app.client.get(path).send().await;
+        res.assert_status_is_ok();
+        res.assert_header(
+            "Content-Security-Policy",
+            SecurityHeadersConfig::default().docs_content_security_policy,
+        );
+    }
+}
+
+#[tokio::test]
+async fn hsts_is_sent_over_tls() {
+    let app = TestApp::builder()
+        .config(Config {
+            security_headers: Arc::new(SecurityHeadersConfig {
+                tls: true,
+                hsts_max_age_secs: 600,
+                ..SecurityHeadersConfig::default()
+            }),
+            ..Config::default()
+        })
+        .build();
+
+    app.client
+        .get("/")
+        .send()
+        .await
+        .assert_header("Strict-Transport-Security", "max-age=600");
+}
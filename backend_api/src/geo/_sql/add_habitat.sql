@@ -0,0 +1,2 @@
+INSERT INTO animal_habitat (animal_id, polygon)
+VALUES (:animal_id, :polygon)
\ No newline at end of file
@@ -0,0 +1,2 @@
+INSERT INTO animal_habitat_rtree (id, min_lon, max_lon, min_lat, max_lat)
+VALUES (:id, :min_lon, :max_lon, :min_lat, :max_lat)
\ No newline at end of file
@@ -0,0 +1,2 @@
+INSERT INTO animal_sighting (animal_id, longitude, latitude, observed_at)
+VALUES (:animal_id, :longitude, :latitude, :observed_at)
\ No newline at end of file
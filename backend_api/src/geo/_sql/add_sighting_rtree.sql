@@ -0,0 +1,2 @@
+INSERT INTO animal_sighting_rtree (id, min_lon, max_lon, min_lat, max_lat)
+VALUES (:id, :longitude, :longitude, :latitude, :latitude)
\ No newline at end of file
@@ -0,0 +1,3 @@
+DELETE
+FROM animal_habitat
+WHERE animal_id = :animal_id
\ No newline at end of file
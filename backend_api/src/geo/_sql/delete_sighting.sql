@@ -0,0 +1,4 @@
+DELETE
+FROM animal_sighting
+WHERE id = :id
+  AND animal_id = :animal_id
\ No newline at end of file
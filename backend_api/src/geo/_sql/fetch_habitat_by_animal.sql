@@ -0,0 +1,4 @@
+SELECT polygon
+FROM animal_habitat
+WHERE animal_id = :animal_id
+ORDER BY id
\ No newline at end of file
@@ -0,0 +1,7 @@
+SELECT animal_habitat.animal_id, animal_habitat.polygon
+FROM animal_habitat_rtree
+         JOIN animal_habitat ON animal_habitat.id = animal_habitat_rtree.id
+WHERE animal_habitat_rtree.max_lon >= :min_lon
+  AND animal_habitat_rtree.min_lon <= :max_lon
+  AND animal_habitat_rtree.max_lat >= :min_lat
+  AND animal_habitat_rtree.min_lat <= :max_lat
\ No newline at end of file
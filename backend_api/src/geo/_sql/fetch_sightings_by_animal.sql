@@ -0,0 +1,4 @@
+SELECT id, longitude, latitude, observed_at
+FROM animal_sighting
+WHERE animal_id = :animal_id
+ORDER BY id
\ No newline at end of file
@@ -0,0 +1,7 @@
+SELECT animal_sighting.animal_id, animal_sighting.longitude, animal_sighting.latitude
+FROM animal_sighting_rtree
+         JOIN animal_sighting ON animal_sighting.id = animal_sighting_rtree.id
+WHERE animal_sighting_rtree.max_lon >= :min_lon
+  AND animal_sighting_rtree.min_lon <= :max_lon
+  AND animal_sighting_rtree.max_lat >= :min_lat
+  AND animal_sighting_rtree.min_lat <= :max_lat
\ No newline at end of file
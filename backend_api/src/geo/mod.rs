@@ -0,0 +1,136 @@
+pub mod object;
+pub mod repository;
+pub mod response;
+#[cfg(test)]
+mod tests;
+
+use crate::ApiTag;
+use crate::animal::repository::DynAnimalRepository;
+use crate::common::context::Dep;
+use crate::common::results::unified;
+use crate::geo::object::{HabitatObject, SightingAddObject, SightingCollectionObject};
+use crate::geo::repository::{GeoRepository, GeoRepositoryError};
+use crate::geo::response::{
+    AddSightingResponse, DeleteSightingResponse, FetchSightingsResponse, HabitatResponse,
+};
+use poem::i18n::Locale;
+use poem_openapi::OpenApi;
+use poem_openapi::param::Path;
+use poem_openapi::payload::Json;
+
+pub struct GeoApi;
+
+#[OpenApi(tag = "ApiTag::Geo")]
+impl GeoApi {
+    /// Fetch the Habitat range of an Animal as a GeoJSON MultiPolygon
+    #[oai(path = "/animal/:id/habitat", method = "get")]
+    async fn habitat(
+        &self,
+        Path(id): Path<u64>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(geo_repository): Dep<GeoRepository>,
+    ) -> HabitatResponse {
+        unified(async {
+            animal_repository
+                .fetch_animal_by_id(id as i64)
+                .map_err(|_| HabitatResponse::NotFound)?;
+            geo_repository
+                .fetch_habitat(id as i64)
+                .map(|coordinates| HabitatResponse::Ok(Json(HabitatObject::new(coordinates))))
+                .map_err(|_| HabitatResponse::InternalServerError)
+        })
+        .await
+    }
+
+    /// Replace the Habitat range of an Animal with a GeoJSON MultiPolygon
+    #[oai(path = "/animal/:id/habitat", method = "put")]
+    async fn replace_habitat(
+        &self,
+        Path(id): Path<u64>,
+        Json(habitat): Json<HabitatObject>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(geo_repository): Dep<GeoRepository>,
+        locale: Locale,
+    ) -> HabitatResponse {
+        unified(async {
+            animal_repository
+                .fetch_animal_by_id(id as i64)
+                .map_err(|_| HabitatResponse::NotFound)?;
+            let polygons = habitat.to_validate().map_err(|geo_error| {
+                HabitatResponse::UnprocessableEntity(Json((geo_error, &locale).into()))
+            })?;
+            geo_repository
+                .replace_habitat(id as i64, &polygons)
+                .map(|coordinates| HabitatResponse::Ok(Json(HabitatObject::new(coordinates))))
+                .map_err(|_| HabitatResponse::InternalServerError)
+        })
+        .await
+    }
+
+    /// Fetch the Sightings of an Animal as a GeoJSON FeatureCollection of Points
+    #[oai(path = "/animal/:id/sightings", method = "get")]
+    async fn sightings(
+        &self,
+        Path(id): Path<u64>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(geo_repository): Dep<GeoRepository>,
+    ) -> FetchSightingsResponse {
+        unified(async {
+            animal_repository
+                .fetch_animal_by_id(id as i64)
+                .map_err(|_| FetchSightingsResponse::NotFound)?;
+            geo_repository
+                .fetch_sightings(id as i64)
+                .map(|sightings| {
+                    FetchSightingsResponse::Ok(Json(SightingCollectionObject::new(sightings)))
+                })
+                .map_err(|_| FetchSightingsResponse::InternalServerError)
+        })
+        .await
+    }
+
+    /// Add a Sighting of an Animal, given as a GeoJSON Point Feature
+    #[oai(path = "/animal/:id/sightings", method = "post")]
+    async fn add_sighting(
+        &self,
+        Path(id): Path<u64>,
+        Json(sighting): Json<SightingAddObject>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(geo_repository): Dep<GeoRepository>,
+        locale: Locale,
+    ) -> AddSightingResponse {
+        unified(async {
+            animal_repository
+                .fetch_animal_by_id(id as i64)
+                .map_err(|_| AddSightingResponse::NotFound)?;
+            let coordinate = sighting.to_validate().map_err(|geo_error| {
+                AddSightingResponse::UnprocessableEntity(Json((geo_error, &locale).into()))
+            })?;
+            geo_repository
+                .add_sighting(id as i64, coordinate, sighting.properties.observed_at)
+                .map(|sighting| AddSightingResponse::Created(Json(sighting)))
+                .map_err(|_| AddSightingResponse::InternalServerError)
+        })
+        .await
+    }
+
+    /// Delete a Sighting of an Animal
+    #[oai(path = "/animal/:id/sightings/:sighting_id", method = "delete")]
+    async fn delete_sighting(
+        &self,
+        Path(id): Path<u64>,
+        Path(sighting_id): Path<u64>,
+        Dep(geo_repository): Dep<GeoRepository>,
+    ) -> DeleteSightingResponse {
+        unified(async {
+            geo_repository
+                .delete_sighting(id as i64, sighting_id as i64)
+                .map(|_| DeleteSightingResponse::Ok)
+                .map_err(|err| match err.current_context() {
+                    GeoRepositoryError::NotFoundError => DeleteSightingResponse::NotFound,
+                    _ => DeleteSightingResponse::InternalServerError,
+                })
+        })
+        .await
+    }
+}
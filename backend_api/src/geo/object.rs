@@ -0,0 +1,183 @@
+use crate::common::locale::LocaleForErrors;
+use crate::common::object::{ValidationErrorObject, field_messages};
+use chrono::{DateTime, Utc};
+use poem::i18n::Locale;
+use poem_openapi::{Enum, Object};
+use shared::validation::types::geo::{BoundingBox, Coordinate, GeoError, Polygon};
+
+/// GeoJSON `[longitude, latitude]`, an altitude may follow and is ignored.
+pub type Position = Vec<f64>;
+
+#[derive(Debug, Enum, Clone, Copy, PartialEq)]
+pub enum MultiPolygonType {
+    #[oai(rename = "MultiPolygon")]
+    MultiPolygon,
+}
+
+#[derive(Debug, Enum, Clone, Copy, PartialEq)]
+pub enum PointType {
+    #[oai(rename = "Point")]
+    Point,
+}
+
+#[derive(Debug, Enum, Clone, Copy, PartialEq)]
+pub enum FeatureType {
+    #[oai(rename = "Feature")]
+    Feature,
+}
+
+#[derive(Debug, Enum, Clone, Copy, PartialEq)]
+pub enum FeatureCollectionType {
+    #[oai(rename = "FeatureCollection")]
+    FeatureCollection,
+}
+
+/// GeoJSON MultiPolygon geometry of the habitat range, one polygon per area.
+#[derive(Debug, Object, Clone)]
+pub struct HabitatObject {
+    #[oai(rename = "type")]
+    pub kind: MultiPolygonType,
+    pub coordinates: Vec<Vec<Vec<Position>>>,
+}
+
+impl HabitatObject {
+    pub fn new(coordinates: Vec<Vec<Vec<Position>>>) -> Self {
+        Self {
+            kind: MultiPolygonType::MultiPolygon,
+            coordinates,
+        }
+    }
+
+    pub fn to_validate(&self) -> Result<Vec<Polygon>, GeoError> {
+        self.coordinates
+            .iter()
+            .map(|polygon| Polygon::parse(polygon))
+            .collect()
+    }
+}
+
+/// GeoJSON Point geometry.
+#[derive(Debug, Object, Clone)]
+pub struct PointObject {
+    #[oai(rename = "type")]
+    pub kind: PointType,
+    pub coordinates: Position,
+}
+
+impl From<Coordinate> for PointObject {
+    fn from(coordinate: Coordinate) -> Self {
+        Self {
+            kind: PointType::Point,
+            coordinates: vec![coordinate.longitude, coordinate.latitude],
+        }
+    }
+}
+
+#[derive(Debug, Object, Clone, Default)]
+pub struct SightingPropertiesObject {
+    pub observed_at: Option<DateTime<Utc>>,
+}
+
+/// GeoJSON Feature of a sighting.
+#[derive(Debug, Object, Clone)]
+pub struct SightingObject {
+    #[oai(rename = "type")]
+    pub kind: FeatureType,
+    pub id: i64,
+    pub geometry: PointObject,
+    pub properties: SightingPropertiesObject,
+}
+
+#[derive(Debug, Object)]
+pub struct SightingAddObject {
+    #[oai(rename = "type")]
+    pub kind: FeatureType,
+    pub geometry: PointObject,
+    #[oai(default)]
+    pub properties: SightingPropertiesObject,
+}
+
+impl SightingAddObject {
+    pub fn to_validate(&self) -> Result<Coordinate, GeoError> {
+        Coordinate::parse(&self.geometry.coordinates)
+    }
+}
+
+/// GeoJSON FeatureCollection of sightings.
+#[derive(Debug, Object, Clone)]
+pub struct SightingCollectionObject {
+    #[oai(rename = "type")]
+    pub kind: FeatureCollectionType,
+    pub features: Vec<SightingObject>,
+}
+
+impl SightingCollectionObject {
+    pub fn new(features: Vec<SightingObject>) -> Self {
+        Self {
+            kind: FeatureCollectionType::FeatureCollection,
+            features,
+        }
+    }
+}
+
+#[derive(Debug, Object)]
+pub struct GeoErrorObject {
+    pub coordinates: Vec<String>,
+    /// The same errors with their code and arguments.
+    pub errors: Vec<ValidationErrorObject>,
+}
+
+impl From<(GeoError, &Locale)> for GeoErrorObject {
+    fn from((error, locale): (GeoError, &Locale)) -> Self {
+        let errors = error.0.as_validation_errors("coordinates", Some(locale));
+        Self {
+            coordinates: field_messages(&errors, "coordinates"),
+            errors,
+        }
+    }
+}
+
+/// Errors in the spatial filters of `GET /animal`.
+#[derive(Debug, Object, Default)]
+pub struct GeoQueryErrorObject {
+    pub bbox: Vec<String>,
+    pub point: Vec<String>,
+    /// The same errors with their code and arguments.
+    pub errors: Vec<ValidationErrorObject>,
+}
+
+/// Numbers of a comma separated query value, empty when any of them is not a number.
+fn parse_numbers(value: &str) -> Vec<f64> {
+    value
+        .split(',')
+        .map(|number| number.trim().parse::<f64>())
+        .collect::<Result<_, _>>()
+        .unwrap_or_default()
+}
+
+/// Parse the `bbox=west,south,east,north` and `point=longitude,latitude` filters.
+pub fn parse_geo_query(
+    bbox: Option<&str>,
+    point: Option<&str>,
+    locale: &Locale,
+) -> Result<(Option<BoundingBox>, Option<Coordinate>), GeoQueryErrorObject> {
+    let bbox = bbox.map(|bbox| BoundingBox::parse(&parse_numbers(bbox)));
+    let point = point.map(|point| Coordinate::parse(&parse_numbers(point)));
+    match (bbox.transpose(), point.transpose()) {
+        (Ok(bbox), Ok(point)) => Ok((bbox, point)),
+        (bbox, point) => {
+            let mut errors = Vec::new();
+            if let Err(error) = bbox {
+                errors.extend(error.0.as_validation_errors("bbox", Some(locale)));
+            }
+            if let Err(error) = point {
+                errors.extend(error.0.as_validation_errors("point", Some(locale)));
+            }
+            Err(GeoQueryErrorObject {
+                bbox: field_messages(&errors, "bbox"),
+                point: field_messages(&errors, "point"),
+                errors,
+            })
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use crate::common::context::{Context, ContextError, FromContext};
+use crate::common::db::{SqliteClient, TenantConnection};
+use crate::geo::object::{
+    FeatureType, PointObject, Position, SightingObject, SightingPropertiesObject,
+};
+use chrono::{DateTime, Utc};
+use error_stack::{Report, ResultExt};
+use rusqlite::{Connection, named_params};
+use shared::validation::types::geo::{BoundingBox, Coordinate, Polygon};
+use std::collections::HashSet;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum GeoRepositoryError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Row Value error")]
+    RowValueError,
+    #[error("Lock error")]
+    LockError,
+    #[error("Not found error")]
+    NotFoundError,
+}
+
+type PolygonCoordinates = Vec<Vec<Position>>;
+
+fn polygon_coordinates(polygon: &Polygon) -> PolygonCoordinates {
+    polygon
+        .rings()
+        .iter()
+        .map(|ring| {
+            ring.iter()
+                .map(|coordinate| vec![coordinate.longitude, coordinate.latitude])
+                .collect()
+        })
+        .collect()
+}
+
+/// Candidate `(animal_id, polygon)` rows whose bounds overlap `bbox`, according to the R-tree.
+fn habitats_in_bbox(
+    conn: &Connection,
+    bbox: &BoundingBox,
+) -> Result<Vec<(i64, Polygon)>, Report<GeoRepositoryError>> {
+    let mut stmt = conn
+        .prepare(include_str!("_sql/fetch_habitats_in_bbox.sql"))
+        .change_context(GeoRepositoryError::QueryError)?;
+    let item_iter = stmt
+        .query_map(
+            named_params! {
+                ":min_lon": bbox.min.longitude,
+                ":max_lon": bbox.max.longitude,
+                ":min_lat": bbox.min.latitude,
+                ":max_lat": bbox.max.latitude,
+            },
+            |row| {
+                Ok((
+                    row.get::<_, i64>("animal_id")?,
+                    row.get::<_, String>("polygon")?,
+                ))
+            },
+        )
+        .change_context(GeoRepositoryError::QueryError)?;
+
+    let mut items = Vec::new();
+    for item in item_iter {
+        let (animal_id, polygon) = item.change_context(GeoRepositoryError::RowValueError)?;
+        let coordinates: PolygonCoordinates =
+            serde_json::from_str(&polygon).change_context(GeoRepositoryError::RowValueError)?;
+        let polygon =
+            Polygon::parse(&coordinates).map_err(|_| GeoRepositoryError::RowValueError)?;
+        items.push((animal_id, polygon));
+    }
+
+    Ok(items)
+}
+
+/// Habitat ranges and sightings, kept next to the animals with an R-tree over their bounds.
+pub struct GeoRepository {
+    sqlite_client: SqliteClient<TenantConnection>,
+}
+
+impl GeoRepository {
+    pub fn new(sqlite_client: SqliteClient<TenantConnection>) -> Self {
+        Self { sqlite_client }
+    }
+
+    pub fn fetch_habitat(
+        &self,
+        animal_id: i64,
+    ) -> Result<Vec<PolygonCoordinates>, Report<GeoRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| GeoRepositoryError::LockError)?;
+
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_habitat_by_animal.sql"))
+            .change_context(GeoRepositoryError::QueryError)?;
+        let item_iter = stmt
+            .query_map(named_params! {":animal_id": animal_id}, |row| {
+                row.get::<_, String>("polygon")
+            })
+            .change_context(GeoRepositoryError::QueryError)?;
+
+        let mut items = Vec::new();
+        for item in item_iter {
+            let polygon = item.change_context(GeoRepositoryError::RowValueError)?;
+            items.push(
+                serde_json::from_str(&polygon).change_context(GeoRepositoryError::RowValueError)?,
+            );
+        }
+
+        Ok(items)
+    }
+
+    /// Replace the whole habitat range of the animal.
+    pub fn replace_habitat(
+        &self,
+        animal_id: i64,
+        polygons: &[Polygon],
+    ) -> Result<Vec<PolygonCoordinates>, Report<GeoRepositoryError>> {
+        let mut conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| GeoRepositoryError::LockError)?;
+        let tx = conn
+            .transaction()
+            .change_context(GeoRepositoryError::QueryError)?;
+
+        tx.execute(
+            include_str!("_sql/delete_habitat.sql"),
+            named_params! {":animal_id": animal_id},
+        )
+        .change_context(GeoRepositoryError::QueryError)?;
+        let mut coordinates = Vec::with_capacity(polygons.len());
+        for polygon in polygons {
+            let polygon_coordinates = polygon_coordinates(polygon);
+            let polygon_json = serde_json::to_string(&polygon_coordinates)
+                .change_context(GeoRepositoryError::QueryError)?;
+            tx.execute(
+                include_str!("_sql/add_habitat.sql"),
+                named_params! {":animal_id": animal_id, ":polygon": polygon_json},
+            )
+            .change_context(GeoRepositoryError::QueryError)?;
+            let bbox = polygon.bounding_box();
+            tx.execute(
+                include_str!("_sql/add_habitat_rtree.sql"),
+                named_params! {
+                    ":id": tx.last_insert_rowid(),
+                    ":min_lon": bbox.min.longitude,
+                    ":max_lon": bbox.max.longitude,
+                    ":min_lat": bbox.min.latitude,
+                    ":max_lat": bbox.max.latitude,
+                },
+            )
+            .change_context(GeoRepositoryError::QueryError)?;
+            coordinates.push(polygon_coordinates);
+        }
+        tx.commit().change_context(GeoRepositoryError::QueryError)?;
+
+        Ok(coordinates)
+    }
+
+    pub fn fetch_sightings(
+        &self,
+        animal_id: i64,
+    ) -> Result<Vec<SightingObject>, Report<GeoRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| GeoRepositoryError::LockError)?;
+
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_sightings_by_animal.sql"))
+            .change_context(GeoRepositoryError::QueryError)?;
+        let item_iter = stmt
+            .query_map(named_params! {":animal_id": animal_id}, |row| {
+                Ok(SightingObject {
+                    kind: FeatureType::Feature,
+                    id: row.get("id")?,
+                    geometry: PointObject::from(Coordinate {
+                        longitude: row.get("longitude")?,
+                        latitude: row.get("latitude")?,
+                    }),
+                    properties: SightingPropertiesObject {
+                        observed_at: row.get("observed_at")?,
+                    },
+                })
+            })
+            .change_context(GeoRepositoryError::QueryError)?;
+
+        let mut items = Vec::new();
+        for item in item_iter {
+            items.push(item.change_context(GeoRepositoryError::RowValueError)?);
+        }
+
+        Ok(items)
+    }
+
+    pub fn add_sighting(
+        &self,
+        animal_id: i64,
+        coordinate: Coordinate,
+        observed_at: Option<DateTime<Utc>>,
+    ) -> Result<SightingObject, Report<GeoRepositoryError>> {
+        let mut conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| GeoRepositoryError::LockError)?;
+        let tx = conn
+            .transaction()
+            .change_context(GeoRepositoryError::QueryError)?;
+
+        tx.execute(
+            include_str!("_sql/add_sighting.sql"),
+            named_params! {
+                ":animal_id": animal_id,
+                ":longitude": coordinate.longitude,
+                ":latitude": coordinate.latitude,
+                ":observed_at": observed_at,
+            },
+        )
+        .change_context(GeoRepositoryError::QueryError)?;
+        let id = tx.last_insert_rowid();
+        tx.execute(
+            include_str!("_sql/add_sighting_rtree.sql"),
+            named_params! {
+                ":id": id,
+                ":longitude": coordinate.longitude,
+                ":latitude": coordinate.latitude,
+            },
+        )
+        .change_context(GeoRepositoryError::QueryError)?;
+        tx.commit().change_context(GeoRepositoryError::QueryError)?;
+
+        Ok(SightingObject {
+            kind: FeatureType::Feature,
+            id,
+            geometry: coordinate.into(),
+            properties: SightingPropertiesObject { observed_at },
+        })
+    }
+
+    pub fn delete_sighting(
+        &self,
+        animal_id: i64,
+        id: i64,
+    ) -> Result<(), Report<GeoRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| GeoRepositoryError::LockError)?;
+
+        let deleted = conn
+            .execute(
+                include_str!("_sql/delete_sighting.sql"),
+                named_params! {":animal_id": animal_id, ":id": id},
+            )
+            .change_context(GeoRepositoryError::QueryError)?;
+        if deleted == 0 {
+            return Err(GeoRepositoryError::NotFoundError.into());
+        }
+
+        Ok(())
+    }
+
+    /// Ids of the animals with a habitat overlapping `bbox` or a sighting inside it.
+    pub fn fetch_animal_ids_in_bbox(
+        &self,
+        bbox: &BoundingBox,
+    ) -> Result<HashSet<i64>, Report<GeoRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| GeoRepositoryError::LockError)?;
+
+        // The R-tree stores rounded bounds, so every candidate is checked exactly.
+        let mut ids: HashSet<i64> = habitats_in_bbox(&conn, bbox)?
+            .into_iter()
+            .filter(|(_, polygon)| polygon.intersects(bbox))
+            .map(|(animal_id, _)| animal_id)
+            .collect();
+
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_sightings_in_bbox.sql"))
+            .change_context(GeoRepositoryError::QueryError)?;
+        let item_iter = stmt
+            .query_map(
+                named_params! {
+                    ":min_lon": bbox.min.longitude,
+                    ":max_lon": bbox.max.longitude,
+                    ":min_lat": bbox.min.latitude,
+                    ":max_lat": bbox.max.latitude,
+                },
+                |row| {
+                    Ok((
+                        row.get::<_, i64>("animal_id")?,
+                        Coordinate {
+                            longitude: row.get("longitude")?,
+                            latitude: row.get("latitude")?,
+                        },
+                    ))
+                },
+            )
+            .change_context(GeoRepositoryError::QueryError)?;
+        for item in item_iter {
+            let (animal_id, coordinate) = item.change_context(GeoRepositoryError::RowValueError)?;
+            if bbox.contains(&coordinate) {
+                ids.insert(animal_id);
+            }
+        }
+
+        Ok(ids)
+    }
+
+    /// Ids of the animals whose habitat range covers `coordinate`.
+    pub fn fetch_animal_ids_at(
+        &self,
+        coordinate: &Coordinate,
+    ) -> Result<HashSet<i64>, Report<GeoRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| GeoRepositoryError::LockError)?;
+
+        let point = BoundingBox {
+            min: *coordinate,
+            max: *coordinate,
+        };
+        Ok(habitats_in_bbox(&conn, &point)?
+            .into_iter()
+            .filter(|(_, polygon)| polygon.contains(coordinate))
+            .map(|(animal_id, _)| animal_id)
+            .collect())
+    }
+}
+
+impl FromContext for GeoRepository {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(
+            SqliteClient::<TenantConnection>::from_context(ctx).await?,
+        ))
+    }
+}
@@ -0,0 +1,47 @@
+use crate::geo::object::{GeoErrorObject, HabitatObject, SightingCollectionObject, SightingObject};
+use poem_openapi::ApiResponse;
+use poem_openapi::payload::Json;
+
+#[derive(ApiResponse)]
+pub enum HabitatResponse {
+    #[oai(status = 200)]
+    Ok(Json<HabitatObject>),
+    #[oai(status = 404)]
+    NotFound,
+    #[oai(status = 422)]
+    UnprocessableEntity(Json<GeoErrorObject>),
+    #[oai(status = 500)]
+    InternalServerError,
+}
+
+#[derive(ApiResponse)]
+pub enum FetchSightingsResponse {
+    #[oai(status = 200)]
+    Ok(Json<SightingCollectionObject>),
+    #[oai(status = 404)]
+    NotFound,
+    #[oai(status = 500)]
+    InternalServerError,
+}
+
+#[derive(ApiResponse)]
+pub enum AddSightingResponse {
+    #[oai(status = 201)]
+    Created(Json<SightingObject>),
+    #[oai(status = 404)]
+    NotFound,
+    #[oai(status = 422)]
+    UnprocessableEntity(Json<GeoErrorObject>),
+    #[oai(status = 500)]
+    InternalServerError,
+}
+
+#[derive(ApiResponse)]
+pub enum DeleteSightingResponse {
+    #[oai(status = 200)]
+    Ok,
+    #[oai(status = 404)]
+    NotFound,
+    #[oai(status = 500)]
+    InternalServerError,
+}
@@ -0,0 +1,242 @@
+use crate::test_support::TestApp;
+use poem::http::StatusCode;
+use serde_json::{Value, json};
+
+const DOG: i64 = 1;
+const CAT: i64 = 2;
+const LION: i64 = 3;
+
+/// A square ring from `(west, south)` to `(east, north)`.
+fn square(west: f64, south: f64, east: f64, north: f64) -> Value {
+    json!([
+        [west, south],
+        [east, south],
+        [east, north],
+        [west, north],
+        [west, south]
+    ])
+}
+
+async fn put_habitat(app: &TestApp, animal_id: i64, polygons: Value) {
+    app.client
+        .put(format!("/animal/{}/habitat", animal_id))
+        .body_json(&json!({"type": "MultiPolygon", "coordinates": polygons}))
+        .send()
+        .await
+        .assert_status_is_ok();
+}
+
+async fn add_sighting(app: &TestApp, animal_id: i64, longitude: f64, latitude: f64) -> i64 {
+    let res = app
+        .client
+        .post(format!("/animal/{}/sightings", animal_id))
+        .body_json(&json!({
+            "type": "Feature",
+            "geometry": {"type": "Point", "coordinates": [longitude, latitude]},
+            "properties": {"observed_at": "2025-06-01T08:30:00Z"},
+        }))
+        .send()
+        .await;
+    res.assert_status(StatusCode::CREATED);
+    res.json().await.value().object().get("id").i64()
+}
+
+async fn species_found(app: &TestApp, query: &str) -> Vec<String> {
+    let res = app.client.get(format!("/animal?{}", query)).send().await;
+    res.assert_status_is_ok();
+    let json = res.json().await;
+    json.value()
+        .array()
+        .iter()
+        .map(|animal| animal.object().get("species").string().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn habitat_round_trips_as_geojson() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .get(format!("/animal/{}/habitat", LION))
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    res.assert_json(json!({"type": "MultiPolygon", "coordinates": []}))
+        .await;
+
+    put_habitat(&app, LION, json!([[square(30.0, -5.0, 40.0, 5.0)]])).await;
+    let res = app
+        .client
+        .get(format!("/animal/{}/habitat", LION))
+        .send()
+        .await;
+    res.assert_json(json!({
+        "type": "MultiPolygon",
+        "coordinates": [[square(30.0, -5.0, 40.0, 5.0)]],
+    }))
+    .await;
+
+    app.client
+        .get("/animal/999/habitat")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn habitat_coordinates_are_validated() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .put(format!("/animal/{}/habitat", LION))
+        .body_json(&json!({
+            "type": "MultiPolygon",
+            "coordinates": [[[[0.0, 0.0], [200.0, 0.0], [0.0, 1.0], [0.0, 0.0]]]],
+        }))
+        .send()
+        .await;
+    res.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("coordinates")
+        .assert_string_array(&["Longitude must be between -180 and 180"]);
+
+    let res = app
+        .client
+        .put(format!("/animal/{}/habitat", LION))
+        .header("Accept-Language", "fr-FR")
+        .body_json(&json!({
+            "type": "MultiPolygon",
+            "coordinates": [[[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]]],
+        }))
+        .send()
+        .await;
+    res.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("coordinates")
+        .assert_string_array(&["Un anneau doit se terminer là où il commence"]);
+}
+
+#[tokio::test]
+async fn sightings_are_a_feature_collection() {
+    let app = TestApp::builder().build();
+    let id = add_sighting(&app, CAT, 2.35, 48.85).await;
+
+    let res = app
+        .client
+        .get(format!("/animal/{}/sightings", CAT))
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    let json = res.json().await;
+    let collection = json.value().object();
+    collection.get("type").assert_string("FeatureCollection");
+    let features = collection.get("features").array();
+    features.assert_len(1);
+    let feature = features.get(0).object();
+    feature.get("type").assert_string("Feature");
+    feature.get("id").assert_i64(id);
+    let geometry = feature.get("geometry").object();
+    geometry.get("type").assert_string("Point");
+    geometry.get("coordinates").assert_f64_array(&[2.35, 48.85]);
+    feature
+        .get("properties")
+        .object()
+        .get("observed_at")
+        .assert_not_null();
+
+    app.client
+        .post(format!("/animal/{}/sightings", CAT))
+        .body_json(&json!({
+            "type": "Feature",
+            "geometry": {"type": "Point", "coordinates": [2.35, 95.0]},
+        }))
+        .send()
+        .await
+        .assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+
+    app.client
+        .delete(format!("/animal/{}/sightings/{}", CAT, id))
+        .send()
+        .await
+        .assert_status_is_ok();
+    app.client
+        .delete(format!("/animal/{}/sightings/{}", CAT, id))
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn index_filters_by_point_in_habitat() {
+    let app = TestApp::builder().build();
+    // A habitat with a hole, and a second area.
+    put_habitat(
+        &app,
+        LION,
+        json!([
+            [square(30.0, -5.0, 40.0, 5.0), square(34.0, -1.0, 36.0, 1.0)],
+            [square(-10.0, 10.0, -5.0, 15.0)]
+        ]),
+    )
+    .await;
+    put_habitat(&app, DOG, json!([[square(0.0, 40.0, 20.0, 60.0)]])).await;
+
+    assert_eq!(species_found(&app, "point=31,0").await, vec!["lion"]);
+    assert_eq!(species_found(&app, "point=-7,12").await, vec!["lion"]);
+    assert!(species_found(&app, "point=35,0").await.is_empty());
+    assert_eq!(species_found(&app, "point=10,50").await, vec!["dog"]);
+    assert!(species_found(&app, "point=25,25").await.is_empty());
+}
+
+#[tokio::test]
+async fn index_filters_by_bbox_over_habitats_and_sightings() {
+    let app = TestApp::builder().build();
+    put_habitat(&app, LION, json!([[square(30.0, -5.0, 40.0, 5.0)]])).await;
+    add_sighting(&app, CAT, 2.35, 48.85).await;
+
+    // Overlaps the habitat without containing any of its corners.
+    assert_eq!(species_found(&app, "bbox=35,-10,36,10").await, vec!["lion"]);
+    assert_eq!(species_found(&app, "bbox=0,45,5,50").await, vec!["cat"]);
+    assert_eq!(
+        species_found(&app, "bbox=-180,-90,180,90").await,
+        vec!["cat", "lion"]
+    );
+    assert!(species_found(&app, "bbox=41,-5,50,5").await.is_empty());
+}
+
+#[tokio::test]
+async fn index_rejects_malformed_spatial_filters() {
+    let app = TestApp::builder().build();
+
+    let res = app.client.get("/animal?bbox=1,2,3").send().await;
+    res.assert_status(StatusCode::BAD_REQUEST);
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("bbox")
+        .assert_string_array(&["Must be four numbers: west, south, east and north"]);
+
+    let res = app
+        .client
+        .get("/animal?bbox=10,0,5,5&point=a,b")
+        .send()
+        .await;
+    res.assert_status(StatusCode::BAD_REQUEST);
+    let json = res.json().await;
+    let errors = json.value().object();
+    errors
+        .get("bbox")
+        .assert_string_array(&["West and south must not exceed east and north"]);
+    errors
+        .get("point")
+        .assert_string_array(&["Must be a longitude and latitude pair"]);
+}
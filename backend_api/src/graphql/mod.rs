@@ -0,0 +1,109 @@
+pub mod mutation;
+pub mod object;
+pub mod query;
+pub mod subscription;
+#[cfg(test)]
+mod tests;
+
+use crate::animal::events::{AnimalEvents, EventScope};
+use crate::animal::repository::DynAnimalRepository;
+use crate::common::context::{Context, ContextError, Dep, FromContext};
+use crate::graphql::mutation::MutationRoot;
+use crate::graphql::query::QueryRoot;
+use crate::graphql::subscription::SubscriptionRoot;
+use crate::tag::repository::TagRepository;
+use crate::taxonomy::repository::TaxonRepository;
+use async_graphql::http::{ALL_WEBSOCKET_PROTOCOLS, GraphiQLSource};
+use async_graphql::{Data, Schema};
+use async_graphql_poem::{GraphQLProtocol, GraphQLRequest, GraphQLResponse, GraphQLWebSocket};
+use error_stack::Report;
+use poem::i18n::Locale;
+use poem::web::websocket::WebSocket;
+use poem::web::{self, Html};
+use poem::{IntoResponse, handler};
+use shared::validation::rules::AnimalRules;
+
+pub type AnimalSchema = Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
+
+pub fn build_schema() -> AnimalSchema {
+    Schema::new(QueryRoot, MutationRoot, SubscriptionRoot)
+}
+
+/// What the resolvers take from the request, built the same way as for the REST endpoints.
+pub struct GraphQLContext {
+    animal_repository: DynAnimalRepository,
+    taxon_repository: TaxonRepository,
+    tag_repository: TagRepository,
+    rules: AnimalRules,
+    events: AnimalEvents,
+    scope: EventScope,
+}
+
+impl GraphQLContext {
+    pub fn insert_into(self, data: &mut Data, locale: Locale) {
+        data.insert(self.animal_repository);
+        data.insert(self.taxon_repository);
+        data.insert(self.tag_repository);
+        data.insert(self.rules);
+        data.insert(self.events);
+        data.insert(self.scope);
+        data.insert(locale);
+    }
+}
+
+impl FromContext for GraphQLContext {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self {
+            // Named, as inferring it loses the `'static` bound of the trait object.
+            animal_repository: DynAnimalRepository::from_context(ctx).await?,
+            taxon_repository: ctx.inject().await?,
+            tag_repository: ctx.inject().await?,
+            rules: ctx.inject().await?,
+            events: ctx.inject().await?,
+            scope: ctx.inject().await?,
+        })
+    }
+}
+
+#[handler]
+pub async fn graphiql() -> Html<String> {
+    Html(
+        GraphiQLSource::build()
+            .endpoint("/graphql")
+            .subscription_endpoint("/graphql/ws")
+            .finish(),
+    )
+}
+
+#[handler]
+pub async fn graphql_request(
+    web::Data(schema): web::Data<&AnimalSchema>,
+    Dep(context): Dep<GraphQLContext>,
+    locale: Locale,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    let mut request = request.0;
+    context.insert_into(&mut request.data, locale);
+    schema.execute(request).await.into()
+}
+
+/// Subscriptions over a WebSocket. Tenant and locale are those of the upgrade request.
+#[handler]
+pub async fn graphql_ws(
+    web::Data(schema): web::Data<&AnimalSchema>,
+    Dep(context): Dep<GraphQLContext>,
+    locale: Locale,
+    protocol: GraphQLProtocol,
+    websocket: WebSocket,
+) -> impl IntoResponse {
+    let schema = schema.clone();
+    let mut data = Data::default();
+    context.insert_into(&mut data, locale);
+    websocket
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |stream| {
+            GraphQLWebSocket::new(stream, schema, protocol)
+                .with_data(data)
+                .serve()
+        })
+}
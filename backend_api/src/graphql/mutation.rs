@@ -0,0 +1,73 @@
+use crate::animal::object::{AnimalAddUpdateObject, AnimalErrorObject};
+use crate::animal::repository::{AnimalRepositoryError, DynAnimalRepository};
+use crate::graphql::object::{AnimalInput, AnimalPayload};
+use crate::taxonomy::repository::TaxonRepository;
+use async_graphql::{Context, Object, Result};
+use poem::i18n::Locale;
+use shared::validation::rules::AnimalRules;
+
+pub struct MutationRoot;
+
+/// Validate the animal as the REST endpoints do, then add it when `id` is `None` or update it.
+async fn save_animal(
+    ctx: &Context<'_>,
+    animal: AnimalAddUpdateObject,
+    id: Option<i64>,
+) -> Result<AnimalPayload> {
+    let animal_repository = ctx.data::<DynAnimalRepository>()?;
+    let locale = ctx.data::<Locale>()?;
+
+    if let Err(animal_err) = animal
+        .to_validate_unique(ctx.data::<AnimalRules>()?, animal_repository.as_ref(), id)
+        .await
+    {
+        return Ok(AnimalPayload::rejected((animal_err, locale).into()));
+    }
+    if let Some(taxon_id) = animal.taxon_id {
+        let taxon_repository = ctx.data::<TaxonRepository>()?;
+        if taxon_repository.fetch_taxon_by_id(taxon_id).is_err() {
+            return Ok(AnimalPayload::rejected(AnimalErrorObject::unknown_taxon(
+                locale,
+            )));
+        }
+    }
+
+    let written = match id {
+        Some(id) => animal_repository.update_animal(&animal, id).map(|_| id),
+        None => animal_repository.add_animal(&animal),
+    };
+    let id = match written {
+        Ok(id) => id,
+        Err(err) => {
+            return match err.current_context() {
+                AnimalRepositoryError::SpeciesTakenError => Ok(AnimalPayload::rejected(
+                    AnimalErrorObject::species_taken(locale),
+                )),
+                AnimalRepositoryError::NotFoundError => Err("Animal not found".into()),
+                _ => Err("Unable to save the animal".into()),
+            };
+        }
+    };
+    animal_repository
+        .fetch_animal_by_id(id)
+        .map(AnimalPayload::written)
+        .map_err(|_| "Unable to fetch the animal".into())
+}
+
+#[Object]
+impl MutationRoot {
+    /// Add an animal. Invalid input is returned in `errors` instead of failing the mutation.
+    async fn add_animal(&self, ctx: &Context<'_>, input: AnimalInput) -> Result<AnimalPayload> {
+        save_animal(ctx, input.into(), None).await
+    }
+
+    /// Update an animal, which fails the mutation when there is no animal with the id.
+    async fn update_animal(
+        &self,
+        ctx: &Context<'_>,
+        id: i64,
+        input: AnimalInput,
+    ) -> Result<AnimalPayload> {
+        save_animal(ctx, input.into(), Some(id)).await
+    }
+}
@@ -0,0 +1,231 @@
+use crate::animal::events::AnimalEventKind;
+use crate::animal::object::{
+    AnimalAddUpdateObject, AnimalErrorObject, AnimalObject, ConservationStatus,
+};
+use crate::animal::repository::{AnimalRepositoryError, DynAnimalRepository};
+use crate::common::object::ValidationErrorObject;
+use crate::tag::object::TagObject;
+use crate::tag::repository::TagRepository;
+use crate::taxonomy::object::TaxonObject;
+use crate::taxonomy::repository::TaxonRepository;
+use async_graphql::{Context, Enum, InputObject, Json, Object, Result, SimpleObject};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+#[derive(Debug, SimpleObject)]
+pub struct Taxon {
+    pub id: i64,
+    /// Absent for kingdoms, the roots of the tree.
+    pub parent_id: Option<i64>,
+    pub rank: String,
+    pub name: String,
+}
+
+impl From<TaxonObject> for Taxon {
+    fn from(taxon: TaxonObject) -> Self {
+        Self {
+            id: taxon.id,
+            parent_id: taxon.parent_id,
+            rank: taxon.rank.as_str().to_string(),
+            name: taxon.name,
+        }
+    }
+}
+
+#[derive(Debug, SimpleObject)]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,
+}
+
+impl From<TagObject> for Tag {
+    fn from(tag: TagObject) -> Self {
+        Self {
+            id: tag.id,
+            name: tag.name,
+        }
+    }
+}
+
+/// An animal, along with its taxon and tags for a single round trip.
+#[derive(Debug)]
+pub struct Animal(pub AnimalObject);
+
+#[Object]
+impl Animal {
+    async fn id(&self) -> i64 {
+        self.0.id
+    }
+
+    async fn species(&self) -> &str {
+        &self.0.species
+    }
+
+    async fn description(&self) -> &str {
+        &self.0.description
+    }
+
+    async fn conservation_status(&self) -> ConservationStatus {
+        self.0.conservation_status
+    }
+
+    async fn taxon_id(&self) -> Option<i64> {
+        self.0.taxon_id
+    }
+
+    async fn taxon(&self, ctx: &Context<'_>) -> Result<Option<Taxon>> {
+        let Some(taxon_id) = self.0.taxon_id else {
+            return Ok(None);
+        };
+        let taxon = ctx
+            .data::<TaxonRepository>()?
+            .fetch_taxon_by_id(taxon_id)
+            .map_err(|_| "Unable to fetch the taxon")?;
+        Ok(Some(taxon.into()))
+    }
+
+    async fn tags(&self, ctx: &Context<'_>) -> Result<Vec<Tag>> {
+        let tags = ctx
+            .data::<TagRepository>()?
+            .fetch_tags_by_animal(self.0.id)
+            .map_err(|_| "Unable to fetch the tags")?;
+        Ok(tags.into_vec().into_iter().map(Tag::from).collect())
+    }
+}
+
+#[derive(Debug, SimpleObject)]
+pub struct AnimalPage {
+    /// Animals matching the filter, on every page.
+    pub total_count: usize,
+    pub has_next_page: bool,
+    pub items: Vec<Animal>,
+}
+
+#[derive(Debug, InputObject, Default)]
+pub struct AnimalFilter {
+    /// Part of the species, ignoring case.
+    pub species: Option<String>,
+    /// Animals in any of the statuses.
+    #[graphql(default)]
+    pub conservation_statuses: Vec<ConservationStatus>,
+    /// Animals classified under the taxon or any taxon below it.
+    pub taxon_id: Option<i64>,
+    /// Animals carrying every tag, ignoring case.
+    #[graphql(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, InputObject)]
+pub struct AnimalInput {
+    pub species: String,
+    pub description: String,
+    /// Taxon the animal is classified under. Left out on update, the current one is kept.
+    pub taxon_id: Option<i64>,
+    /// Left out on add, the animal is not evaluated. Left out on update, the current one is kept.
+    pub conservation_status: Option<ConservationStatus>,
+}
+
+impl From<AnimalInput> for AnimalAddUpdateObject {
+    fn from(input: AnimalInput) -> Self {
+        Self {
+            species: input.species,
+            description: input.description,
+            taxon_id: input.taxon_id,
+            conservation_status: input.conservation_status,
+        }
+    }
+}
+
+/// A failed validation rule of an input field, as in the REST error objects.
+#[derive(Debug, SimpleObject)]
+pub struct FieldError {
+    pub field: String,
+    /// Key of the message in the locale files, such as `validate-max-length`.
+    pub code: String,
+    pub args: Json<BTreeMap<String, Value>>,
+    pub message: String,
+}
+
+impl From<ValidationErrorObject> for FieldError {
+    fn from(error: ValidationErrorObject) -> Self {
+        Self {
+            field: error.field,
+            code: error.code,
+            args: Json(error.args),
+            message: error.message,
+        }
+    }
+}
+
+/// The animal once written, or the errors of the input.
+#[derive(Debug, SimpleObject)]
+pub struct AnimalPayload {
+    pub animal: Option<Animal>,
+    pub errors: Vec<FieldError>,
+}
+
+impl AnimalPayload {
+    pub fn written(animal: AnimalObject) -> Self {
+        Self {
+            animal: Some(Animal(animal)),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn rejected(error: AnimalErrorObject) -> Self {
+        Self {
+            animal: None,
+            errors: error.errors.into_iter().map(FieldError::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Enum, Clone, Copy, PartialEq, Eq)]
+pub enum AnimalChangeKind {
+    Added,
+    Updated,
+    Imported,
+    Deleted,
+}
+
+impl From<AnimalEventKind> for AnimalChangeKind {
+    fn from(kind: AnimalEventKind) -> Self {
+        match kind {
+            AnimalEventKind::Added => Self::Added,
+            AnimalEventKind::Updated => Self::Updated,
+            AnimalEventKind::Imported => Self::Imported,
+            AnimalEventKind::Deleted => Self::Deleted,
+        }
+    }
+}
+
+/// A write to an animal.
+pub struct AnimalChange {
+    pub kind: AnimalChangeKind,
+    pub animal_id: i64,
+}
+
+#[Object]
+impl AnimalChange {
+    async fn kind(&self) -> AnimalChangeKind {
+        self.kind
+    }
+
+    async fn animal_id(&self) -> i64 {
+        self.animal_id
+    }
+
+    /// The animal as it is now.
+    async fn animal(&self, ctx: &Context<'_>) -> Result<Option<Animal>> {
+        match ctx
+            .data::<DynAnimalRepository>()?
+            .fetch_animal_by_id(self.animal_id)
+        {
+            Ok(animal) => Ok(Some(Animal(animal))),
+            Err(err) => match err.current_context() {
+                AnimalRepositoryError::NotFoundError => Ok(None),
+                _ => Err("Unable to fetch the animal".into()),
+            },
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::animal::repository::{AnimalRepositoryError, DynAnimalRepository};
+use crate::graphql::object::{Animal, AnimalFilter, AnimalPage};
+use crate::tag::object::TagMatch;
+use crate::tag::repository::TagRepository;
+use crate::taxonomy::repository::TaxonRepository;
+use async_graphql::{Context, Object, Result};
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn animal(&self, ctx: &Context<'_>, id: i64) -> Result<Option<Animal>> {
+        match ctx.data::<DynAnimalRepository>()?.fetch_animal_by_id(id) {
+            Ok(animal) => Ok(Some(Animal(animal))),
+            Err(err) => match err.current_context() {
+                AnimalRepositoryError::NotFoundError => Ok(None),
+                _ => Err("Unable to fetch the animal".into()),
+            },
+        }
+    }
+
+    /// Animals matching every part of the filter, in id order. Pages hold at most 100 animals.
+    async fn animals(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] filter: AnimalFilter,
+        #[graphql(default)] offset: usize,
+        #[graphql(default = 20, validator(maximum = 100))] limit: usize,
+    ) -> Result<AnimalPage> {
+        let animals = ctx
+            .data::<DynAnimalRepository>()?
+            .fetch_all_animals()
+            .map_err(|_| "Unable to fetch the animals")?;
+
+        let taxon_ids = match filter.taxon_id {
+            Some(taxon_id) => Some(
+                ctx.data::<TaxonRepository>()?
+                    .fetch_subtree_ids(taxon_id)
+                    .map_err(|_| "Unable to fetch the taxa")?,
+            ),
+            None => None,
+        };
+        let tagged_ids = match filter.tags.is_empty() {
+            true => None,
+            false => Some(
+                ctx.data::<TagRepository>()?
+                    .fetch_animal_ids_by_tags(&filter.tags, TagMatch::All)
+                    .map_err(|_| "Unable to fetch the tags")?,
+            ),
+        };
+        let species = filter.species.as_deref().map(str::to_lowercase);
+
+        let matching: Vec<_> = animals
+            .iter()
+            .filter(|animal| {
+                species
+                    .as_deref()
+                    .is_none_or(|species| animal.species.to_lowercase().contains(species))
+            })
+            .filter(|animal| {
+                filter.conservation_statuses.is_empty()
+                    || filter
+                        .conservation_statuses
+                        .contains(&animal.conservation_status)
+            })
+            .filter(|animal| {
+                taxon_ids.as_ref().is_none_or(|ids| {
+                    animal
+                        .taxon_id
+                        .is_some_and(|taxon_id| ids.contains(&taxon_id))
+                })
+            })
+            .filter(|animal| {
+                tagged_ids
+                    .as_ref()
+                    .is_none_or(|ids| ids.contains(&animal.id))
+            })
+            .collect();
+
+        let total_count = matching.len();
+        let items: Vec<Animal> = matching
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .map(|animal| Animal(animal.clone()))
+            .collect();
+        Ok(AnimalPage {
+            total_count,
+            has_next_page: offset + items.len() < total_count,
+            items,
+        })
+    }
+}
@@ -0,0 +1,26 @@
+use crate::animal::events::{AnimalEvents, EventScope};
+use crate::graphql::object::AnimalChange;
+use async_graphql::{Context, Result, Subscription};
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
+
+pub struct SubscriptionRoot;
+
+#[Subscription]
+impl SubscriptionRoot {
+    /// Writes to the animals of the tenant the subscription was made for, as they happen.
+    /// Changes missed by a listener that falls too far behind are skipped.
+    async fn animal_changes(&self, ctx: &Context<'_>) -> Result<impl Stream<Item = AnimalChange>> {
+        let scope = ctx.data::<EventScope>()?.clone();
+        let receiver = ctx.data::<AnimalEvents>()?.subscribe();
+        Ok(BroadcastStream::new(receiver).filter_map(move |event| {
+            event
+                .ok()
+                .filter(|event| event.scope == scope)
+                .map(|event| AnimalChange {
+                    kind: event.kind.into(),
+                    animal_id: event.animal_id,
+                })
+        }))
+    }
+}
@@ -0,0 +1,148 @@
+use crate::animal::events::{AnimalEvents, EventScope, ObservedAnimalRepository};
+use crate::animal::object::AnimalAddUpdateObject;
+use crate::animal::repository::memory::InMemoryAnimalRepository;
+use crate::animal::repository::{AnimalRepository, DynAnimalRepository};
+use crate::graphql::build_schema;
+use crate::test_support::TestApp;
+use async_graphql::Request;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::timeout;
+use tokio_stream::StreamExt;
+
+#[tokio::test]
+async fn animals_are_filtered_and_paginated() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .post("/graphql")
+        .body_json(&json!({
+            "query": "{ animals(filter: {taxonId: 5}, limit: 2) { totalCount hasNextPage items { species taxon { name } } } }"
+        }))
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    res.assert_json(json!({
+        "data": {
+            "animals": {
+                "totalCount": 3,
+                "hasNextPage": true,
+                "items": [
+                    {"species": "cat", "taxon": {"name": "Felis catus"}},
+                    {"species": "lion", "taxon": {"name": "Panthera leo"}},
+                ],
+            },
+        },
+    }))
+    .await;
+}
+
+#[tokio::test]
+async fn add_animal_returns_field_errors() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .post("/graphql")
+        .body_json(&json!({
+            "query": r#"mutation { addAnimal(input: {species: "", description: "Nameless"}) { animal { id } errors { field code } } }"#
+        }))
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    let json = res.json().await;
+    let payload = json
+        .value()
+        .object()
+        .get("data")
+        .object()
+        .get("addAnimal")
+        .object();
+    payload.get("animal").assert_null();
+    let error = payload.get("errors").array().get(0).object();
+    error.get("field").assert_string("species");
+    error.get("code").assert_string("validate-cannot-be-empty");
+}
+
+#[tokio::test]
+async fn add_animal_creates_the_animal() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .post("/graphql")
+        .body_json(&json!({
+            "query": r#"mutation { addAnimal(input: {species: "otter", description: "Floats on its back", conservationStatus: NT}) { animal { id species conservationStatus } errors { code } } }"#
+        }))
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    res.assert_json(json!({
+        "data": {
+            "addAnimal": {
+                "animal": {"id": 13, "species": "otter", "conservationStatus": "NT"},
+                "errors": [],
+            },
+        },
+    }))
+    .await;
+}
+
+#[tokio::test]
+async fn animal_changes_follow_repository_writes() {
+    let events = AnimalEvents::default();
+    let repository: DynAnimalRepository = Arc::new(ObservedAnimalRepository::new(
+        Arc::new(InMemoryAnimalRepository::with_sample_data()),
+        events.clone(),
+        EventScope(None),
+    ));
+    let schema = build_schema();
+    let mut stream = schema.execute_stream(
+        Request::new("subscription { animalChanges { kind animalId animal { species } } }")
+            .data(repository.clone())
+            .data(events.clone())
+            .data(EventScope(None)),
+    );
+    // The subscription only listens once polled.
+    assert!(
+        timeout(Duration::from_millis(50), stream.next())
+            .await
+            .is_err()
+    );
+
+    // Writes to another tenant are left out.
+    ObservedAnimalRepository::new(
+        Arc::new(InMemoryAnimalRepository::with_sample_data()),
+        events,
+        EventScope(Some("other".to_string())),
+    )
+    .update_animal(
+        &AnimalAddUpdateObject {
+            species: "hound".to_string(),
+            description: "Domestic Dog".to_string(),
+            taxon_id: None,
+            conservation_status: None,
+        },
+        1,
+    )
+    .expect("update");
+    repository
+        .update_animal(
+            &AnimalAddUpdateObject {
+                species: "wolf".to_string(),
+                description: "Wild Dog".to_string(),
+                taxon_id: None,
+                conservation_status: None,
+            },
+            1,
+        )
+        .expect("update");
+
+    let response = stream.next().await.expect("change");
+    assert_eq!(
+        response.data.into_json().expect("json"),
+        json!({"animalChanges": {"kind": "UPDATED", "animalId": 1, "animal": {"species": "wolf"}}})
+    );
+}
@@ -0,0 +1,2 @@
+INSERT INTO idempotent_request (scope, idempotency_key, fingerprint, created_at, expires_at)
+VALUES (:scope, :idempotency_key, :fingerprint, :created_at, :expires_at)
\ No newline at end of file
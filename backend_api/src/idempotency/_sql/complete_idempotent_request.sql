@@ -0,0 +1,7 @@
+UPDATE idempotent_request
+SET status     = :status,
+    headers    = :headers,
+    body       = :body,
+    expires_at = :expires_at
+WHERE scope = :scope
+  AND idempotency_key = :idempotency_key
\ No newline at end of file
@@ -0,0 +1,3 @@
+DELETE
+FROM idempotent_request
+WHERE expires_at <= :now
\ No newline at end of file
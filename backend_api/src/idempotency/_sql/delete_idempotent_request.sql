@@ -0,0 +1,4 @@
+DELETE
+FROM idempotent_request
+WHERE scope = :scope
+  AND idempotency_key = :idempotency_key
\ No newline at end of file
@@ -0,0 +1,4 @@
+SELECT fingerprint, status, headers, body
+FROM idempotent_request
+WHERE scope = :scope
+  AND idempotency_key = :idempotency_key
\ No newline at end of file
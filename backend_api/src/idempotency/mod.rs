@@ -0,0 +1,167 @@
+pub mod repository;
+#[cfg(test)]
+mod tests;
+
+use crate::api_key::API_KEY_HEADER;
+use crate::common::config::Config;
+use crate::common::context::{Context, FromContext};
+use crate::common::limits::client_ip;
+use crate::idempotency::repository::{Claim, IdempotencyRepository, StoredResponse};
+use crate::tenant::pool::resolve_slug;
+use chrono::TimeDelta;
+use poem::http::header::RETRY_AFTER;
+use poem::http::{Method, StatusCode};
+use poem::{Body, Endpoint, IntoResponse, Request, Response};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+/// Set on responses replayed from an earlier request with the same key.
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "Idempotent-Replayed";
+
+/// Keys belong to the client that sent them: its tenant and API key, or its address when it
+/// has no key. The API key is hashed so it is not stored.
+fn scope(req: &Request, config: &Config) -> String {
+    let tenant = match config.tenant.enabled {
+        true => resolve_slug(req, &config.tenant),
+        false => None,
+    };
+    let client = match req.header(API_KEY_HEADER) {
+        Some(api_key) => format!("key:{}", api_key),
+        None => format!("addr:{}", client_ip(req)),
+    };
+    let scope = format!("{}\n{}", tenant.unwrap_or_default(), client);
+    format!("{:x}", Sha256::digest(scope.as_bytes()))
+}
+
+/// Tells a retry from a different request reusing the key.
+fn fingerprint(req: &Request, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(req.method().as_str().as_bytes());
+    hasher.update(b"\n");
+    hasher.update(req.uri().to_string().as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    format!("{:x}", hasher.finalize())
+}
+
+fn internal_error<T>(_: T) -> poem::Error {
+    poem::Error::from_status(StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+fn replay(stored: StoredResponse) -> Response {
+    let mut builder = Response::builder()
+        .status(StatusCode::from_u16(stored.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR));
+    for (name, value) in stored.headers {
+        builder = builder.header(name, value);
+    }
+    builder
+        .header(IDEMPOTENT_REPLAYED_HEADER, "true")
+        .body(stored.body)
+}
+
+/// Claim on a key, given up when dropped before completing, so a request that fails or is
+/// cancelled half-way does not block its retries until the claim expires.
+struct ClaimGuard<'a> {
+    repository: &'a IdempotencyRepository,
+    scope: &'a str,
+    key: &'a str,
+    completed: bool,
+}
+
+impl ClaimGuard<'_> {
+    fn complete(mut self, response: &StoredResponse, ttl: TimeDelta) {
+        self.completed = self
+            .repository
+            .complete(self.scope, self.key, response, ttl)
+            .is_ok();
+    }
+}
+
+impl Drop for ClaimGuard<'_> {
+    fn drop(&mut self) {
+        if !self.completed {
+            let _ = self.repository.release(self.scope, self.key);
+        }
+    }
+}
+
+/// `POST` and `PATCH` requests with an `Idempotency-Key` are processed once per key. Retries
+/// get the stored response back, unless the first request is still in flight (409) or the
+/// key was used for a different request (422). Server errors are not stored, so they can be
+/// retried.
+pub async fn idempotent<E: Endpoint>(next: Arc<E>, mut req: Request) -> poem::Result<Response> {
+    let key = match req.header(IDEMPOTENCY_KEY_HEADER) {
+        Some(key) if matches!(*req.method(), Method::POST | Method::PATCH) => key.to_string(),
+        _ => return next.call(req).await.map(IntoResponse::into_response),
+    };
+    if key.is_empty() || key.len() > 255 {
+        return Err(poem::Error::from_string(
+            "Idempotency-Key must be 1 to 255 characters",
+            StatusCode::BAD_REQUEST,
+        ));
+    }
+
+    let config = Config::for_request(&req).await.map_err(internal_error)?;
+    let body = req.take_body().into_bytes().await?;
+    let scope = scope(&req, &config);
+    let fingerprint = fingerprint(&req, &body);
+    req.set_body(body);
+
+    let repository = IdempotencyRepository::from_context(&Context {
+        config: Arc::downgrade(&config),
+        req: &req,
+    })
+    .await
+    .map_err(internal_error)?;
+    let lease = TimeDelta::seconds(config.idempotency.lease_secs as i64);
+    let ttl = TimeDelta::seconds(config.idempotency.ttl_secs as i64);
+
+    match repository
+        .claim(&scope, &key, &fingerprint, lease)
+        .map_err(internal_error)?
+    {
+        Claim::Mismatch => Err(poem::Error::from_string(
+            "Idempotency-Key was used for a different request",
+            StatusCode::UNPROCESSABLE_ENTITY,
+        )),
+        Claim::InFlight => Ok(Response::builder()
+            .status(StatusCode::CONFLICT)
+            .header(RETRY_AFTER, "1")
+            .body("A request with this Idempotency-Key is in progress")),
+        Claim::Completed(stored) => Ok(replay(stored)),
+        Claim::Claimed => {
+            let guard = ClaimGuard {
+                repository: &repository,
+                scope: &scope,
+                key: &key,
+                completed: false,
+            };
+            let resp = match next.call(req).await {
+                Ok(resp) => resp.into_response(),
+                Err(err) => err.into_response(),
+            };
+            if resp.status().is_server_error() {
+                return Ok(resp);
+            }
+
+            let (parts, body) = resp.into_parts();
+            let body = body.into_vec().await?;
+            guard.complete(
+                &StoredResponse {
+                    status: parts.status.as_u16(),
+                    headers: parts
+                        .headers
+                        .iter()
+                        .filter_map(|(name, value)| {
+                            Some((name.to_string(), value.to_str().ok()?.to_string()))
+                        })
+                        .collect(),
+                    body: body.clone(),
+                },
+                ttl,
+            );
+            Ok(Response::from_parts(parts, Body::from(body)))
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use crate::common::context::{Context, ContextError, FromContext};
+use crate::common::db::{DefaultConnection, SqliteClient};
+use chrono::{TimeDelta, Utc};
+use error_stack::{Report, ResultExt};
+use rusqlite::{OptionalExtension, named_params};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IdempotencyRepositoryError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Row Value error")]
+    RowValueError,
+    #[error("Lock error")]
+    LockError,
+}
+
+/// Response to a request made with an idempotency key, replayed to its retries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// Outcome of claiming an idempotency key for a request.
+#[derive(Debug, PartialEq)]
+pub enum Claim {
+    /// The key is new, the request is to be processed.
+    Claimed,
+    /// The key was used for a different request.
+    Mismatch,
+    /// The first request with the key is still being processed.
+    InFlight,
+    /// The first request with the key has been answered.
+    Completed(StoredResponse),
+}
+
+pub struct IdempotencyRepository {
+    sqlite_client: SqliteClient,
+}
+
+impl IdempotencyRepository {
+    pub fn new(sqlite_client: SqliteClient) -> Self {
+        Self { sqlite_client }
+    }
+
+    /// Claim `key` within `scope` for the request of `fingerprint` for `lease`, unless a live
+    /// claim on the key exists. Expired claims, such as those of a server that died before
+    /// answering, are dropped on the way.
+    pub fn claim(
+        &self,
+        scope: &str,
+        key: &str,
+        fingerprint: &str,
+        lease: TimeDelta,
+    ) -> Result<Claim, Report<IdempotencyRepositoryError>> {
+        let mut conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| IdempotencyRepositoryError::LockError)?;
+        let tx = conn
+            .transaction()
+            .change_context(IdempotencyRepositoryError::QueryError)?;
+
+        let now = Utc::now();
+        tx.execute(
+            include_str!("_sql/delete_expired_requests.sql"),
+            named_params! {":now": now},
+        )
+        .change_context(IdempotencyRepositoryError::QueryError)?;
+
+        let existing = tx
+            .query_row(
+                include_str!("_sql/fetch_idempotent_request.sql"),
+                named_params! {":scope": scope, ":idempotency_key": key},
+                |row| {
+                    Ok((
+                        row.get::<_, String>("fingerprint")?,
+                        row.get::<_, Option<u16>>("status")?,
+                        row.get::<_, Option<String>>("headers")?,
+                        row.get::<_, Option<Vec<u8>>>("body")?,
+                    ))
+                },
+            )
+            .optional()
+            .change_context(IdempotencyRepositoryError::QueryError)?;
+
+        let claim = match existing {
+            None => {
+                tx.execute(
+                    include_str!("_sql/add_idempotent_request.sql"),
+                    named_params! {
+                        ":scope": scope,
+                        ":idempotency_key": key,
+                        ":fingerprint": fingerprint,
+                        ":created_at": now,
+                        ":expires_at": now + lease,
+                    },
+                )
+                .change_context(IdempotencyRepositoryError::QueryError)?;
+                Claim::Claimed
+            }
+            Some((stored, _, _, _)) if stored != fingerprint => Claim::Mismatch,
+            Some((_, Some(status), headers, body)) => Claim::Completed(StoredResponse {
+                status,
+                headers: serde_json::from_str(headers.as_deref().unwrap_or("[]"))
+                    .change_context(IdempotencyRepositoryError::RowValueError)?,
+                body: body.unwrap_or_default(),
+            }),
+            Some(_) => Claim::InFlight,
+        };
+        tx.commit()
+            .change_context(IdempotencyRepositoryError::QueryError)?;
+        Ok(claim)
+    }
+
+    /// Keep the response to the request that claimed `key`, for its retries during `ttl`.
+    pub fn complete(
+        &self,
+        scope: &str,
+        key: &str,
+        response: &StoredResponse,
+        ttl: TimeDelta,
+    ) -> Result<(), Report<IdempotencyRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| IdempotencyRepositoryError::LockError)?;
+        let headers = serde_json::to_string(&response.headers)
+            .change_context(IdempotencyRepositoryError::RowValueError)?;
+
+        conn.execute(
+            include_str!("_sql/complete_idempotent_request.sql"),
+            named_params! {
+                ":scope": scope,
+                ":idempotency_key": key,
+                ":status": response.status,
+                ":headers": headers,
+                ":body": response.body,
+                ":expires_at": Utc::now() + ttl,
+            },
+        )
+        .change_context(IdempotencyRepositoryError::QueryError)?;
+        Ok(())
+    }
+
+    /// Give up the claim on `key`, so a retry is processed afresh.
+    pub fn release(
+        &self,
+        scope: &str,
+        key: &str,
+    ) -> Result<(), Report<IdempotencyRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| IdempotencyRepositoryError::LockError)?;
+
+        conn.execute(
+            include_str!("_sql/delete_idempotent_request.sql"),
+            named_params! {":scope": scope, ":idempotency_key": key},
+        )
+        .change_context(IdempotencyRepositoryError::QueryError)?;
+        Ok(())
+    }
+}
+
+impl FromContext for IdempotencyRepository {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(
+            SqliteClient::<DefaultConnection>::from_context(ctx).await?,
+        ))
+    }
+}
@@ -0,0 +1,175 @@
+use crate::common::config::Config;
+use crate::idempotency::repository::{Claim, IdempotencyRepository, StoredResponse};
+use crate::idempotency::scope;
+use crate::test_support::TestApp;
+use chrono::TimeDelta;
+use poem::http::StatusCode;
+use poem::http::uri::Scheme;
+use poem::web::{LocalAddr, RemoteAddr};
+use poem::{Addr, Body, Request, RequestParts};
+use serde_json::json;
+use std::net::SocketAddr;
+
+#[tokio::test]
+async fn retries_get_the_first_response() {
+    let app = TestApp::builder().build();
+
+    for replayed in [false, true] {
+        let res = app
+            .client
+            .post("/animal/add")
+            .header("Idempotency-Key", "add-otter")
+            .body_json(&json!({"species": "otter", "description": "Floats on its back"}))
+            .send()
+            .await;
+        res.assert_status(StatusCode::CREATED);
+        match replayed {
+            true => res.assert_header("Idempotent-Replayed", "true"),
+            false => res.assert_header_is_not_exist("Idempotent-Replayed"),
+        }
+    }
+
+    app.client
+        .get("/animal")
+        .send()
+        .await
+        .json()
+        .await
+        .value()
+        .array()
+        .assert_len(13);
+}
+
+#[tokio::test]
+async fn reusing_a_key_for_another_body_is_unprocessable() {
+    let app = TestApp::builder().build();
+
+    app.client
+        .post("/animal/add")
+        .header("Idempotency-Key", "add")
+        .body_json(&json!({"species": "otter", "description": "Floats on its back"}))
+        .send()
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let res = app
+        .client
+        .post("/animal/add")
+        .header("Idempotency-Key", "add")
+        .body_json(&json!({"species": "beaver", "description": "Builds dams"}))
+        .send()
+        .await;
+    res.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn requests_without_a_key_are_processed_every_time() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .post("/animal/add")
+        .body_json(&json!({"species": "otter", "description": "Floats on its back"}))
+        .send()
+        .await;
+    res.assert_status(StatusCode::CREATED);
+    let res = app
+        .client
+        .post("/animal/add")
+        .body_json(&json!({"species": "otter", "description": "Floats on its back"}))
+        .send()
+        .await;
+    res.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn claims_hold_until_released_or_expired() {
+    let app = TestApp::builder().build();
+    let repository = IdempotencyRepository::new(app.sqlite_client.clone());
+    let lease = TimeDelta::minutes(1);
+    let ttl = TimeDelta::hours(1);
+
+    let claim = |fingerprint| {
+        repository
+            .claim("client", "key", fingerprint, lease)
+            .unwrap()
+    };
+    assert_eq!(claim("a"), Claim::Claimed);
+    assert_eq!(claim("a"), Claim::InFlight);
+    assert_eq!(claim("b"), Claim::Mismatch);
+
+    let response = StoredResponse {
+        status: 201,
+        headers: vec![("location".to_string(), "/v2/animal/13".to_string())],
+        body: b"{}".to_vec(),
+    };
+    repository
+        .complete("client", "key", &response, ttl)
+        .unwrap();
+    assert_eq!(claim("a"), Claim::Completed(response));
+
+    repository.release("client", "key").unwrap();
+    assert_eq!(claim("a"), Claim::Claimed);
+
+    repository
+        .claim("client", "expired", "a", TimeDelta::seconds(-1))
+        .unwrap();
+    assert_eq!(
+        repository.claim("client", "expired", "b", lease).unwrap(),
+        Claim::Claimed
+    );
+}
+
+#[tokio::test]
+async fn completed_responses_outlive_the_lease() {
+    let app = TestApp::builder().build();
+    let repository = IdempotencyRepository::new(app.sqlite_client.clone());
+    let response = StoredResponse {
+        status: 201,
+        headers: vec![],
+        body: b"{}".to_vec(),
+    };
+
+    repository
+        .claim("client", "key", "a", TimeDelta::seconds(-1))
+        .unwrap();
+    repository
+        .complete("client", "key", &response, TimeDelta::hours(1))
+        .unwrap();
+    assert_eq!(
+        repository
+            .claim("client", "key", "a", TimeDelta::minutes(1))
+            .unwrap(),
+        Claim::Completed(response)
+    );
+}
+
+fn request_from(addr: &str, api_key: Option<&str>) -> Request {
+    let mut builder = poem::http::Request::builder();
+    if let Some(api_key) = api_key {
+        builder = builder.header("X-Api-Key", api_key);
+    }
+    let (parts, ()) = builder.body(()).unwrap().into_parts();
+    let remote_addr = RemoteAddr(Addr::from(addr.parse::<SocketAddr>().unwrap()));
+    Request::from_parts(
+        RequestParts::from((parts, LocalAddr::default(), remote_addr, Scheme::HTTP)),
+        Body::empty(),
+    )
+}
+
+#[test]
+fn keys_are_scoped_to_the_api_key_or_else_the_address() {
+    let config = Config::default();
+    let scope = |addr, api_key| scope(&request_from(addr, api_key), &config);
+
+    assert_eq!(scope("192.0.2.1:4000", None), scope("192.0.2.1:4001", None));
+    assert_ne!(scope("192.0.2.1:4000", None), scope("192.0.2.2:4000", None));
+    assert_eq!(
+        scope("192.0.2.1:4000", Some("key")),
+        scope("192.0.2.2:4000", Some("key"))
+    );
+    assert_ne!(
+        scope("192.0.2.1:4000", Some("key")),
+        scope("192.0.2.1:4000", None)
+    );
+}
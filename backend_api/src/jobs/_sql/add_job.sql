@@ -0,0 +1,2 @@
+INSERT INTO job (kind, status, run_at, created_at)
+VALUES (:kind, 'queued', :run_at, :created_at)
\ No newline at end of file
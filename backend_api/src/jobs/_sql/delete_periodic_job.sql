@@ -0,0 +1,5 @@
+DELETE
+FROM job
+WHERE kind = :kind
+  AND schedule IS NOT NULL
+  AND status != 'running'
\ No newline at end of file
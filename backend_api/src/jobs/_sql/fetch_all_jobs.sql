@@ -0,0 +1,3 @@
+SELECT id, kind, schedule, status, attempts, run_at, started_at, finished_at, last_error, created_at
+FROM job
+ORDER BY id
\ No newline at end of file
@@ -0,0 +1,6 @@
+SELECT id, kind, schedule, attempts
+FROM job
+WHERE (status = 'queued' AND run_at <= :now)
+   OR (status = 'running' AND lease_expires_at <= :now)
+ORDER BY run_at, id
+LIMIT 1
\ No newline at end of file
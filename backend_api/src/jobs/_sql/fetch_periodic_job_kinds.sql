@@ -0,0 +1,3 @@
+SELECT kind
+FROM job
+WHERE schedule IS NOT NULL
\ No newline at end of file
@@ -0,0 +1,10 @@
+UPDATE job
+SET status           = :status,
+    attempts         = :attempts,
+    run_at           = :run_at,
+    last_error       = :last_error,
+    finished_at      = :now,
+    lease_owner      = NULL,
+    lease_expires_at = NULL
+WHERE id = :id
+  AND lease_owner = :lease_owner
\ No newline at end of file
@@ -0,0 +1,7 @@
+UPDATE job
+SET status           = 'running',
+    attempts         = attempts + 1,
+    lease_owner      = :lease_owner,
+    lease_expires_at = :lease_expires_at,
+    started_at       = :now
+WHERE id = :id
\ No newline at end of file
@@ -0,0 +1,6 @@
+INSERT INTO job (kind, schedule, status, run_at, created_at)
+VALUES (:kind, :schedule, 'queued', :run_at, :created_at)
+ON CONFLICT (kind) WHERE schedule IS NOT NULL DO UPDATE
+    SET schedule = excluded.schedule,
+        run_at   = excluded.run_at
+WHERE job.schedule != excluded.schedule
\ No newline at end of file
@@ -0,0 +1,37 @@
+pub mod object;
+pub mod repository;
+pub mod response;
+pub mod scheduler;
+pub mod task;
+#[cfg(test)]
+mod tests;
+
+use crate::ApiTag;
+use crate::api_key::AdminApiKey;
+use crate::common::context::Dep;
+use crate::common::results::unified;
+use crate::jobs::repository::JobRepository;
+use crate::jobs::response::FetchAllJobsResponse;
+use poem_openapi::OpenApi;
+use poem_openapi::payload::Json;
+
+pub struct JobApi;
+
+#[OpenApi(tag = "ApiTag::Job")]
+impl JobApi {
+    /// Fetch All Jobs, periodic and one-shot, with the outcome of their latest run
+    #[oai(path = "/jobs", method = "get")]
+    async fn index(
+        &self,
+        Dep(_admin): Dep<AdminApiKey>,
+        Dep(job_repository): Dep<JobRepository>,
+    ) -> FetchAllJobsResponse {
+        unified(async {
+            job_repository
+                .fetch_all_jobs()
+                .map(|jobs| FetchAllJobsResponse::Ok(Json(jobs.to_vec())))
+                .map_err(|_| FetchAllJobsResponse::InternalServerError)
+        })
+        .await
+    }
+}
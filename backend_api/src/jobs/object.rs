@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use poem_openapi::{Enum, Object};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use serde::{Deserialize, Serialize};
+
+/// Maintenance a job carries out on the main database.
+#[derive(Debug, Enum, ValueEnum, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[oai(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    /// Rebuild the database file, giving free pages back to the file system.
+    Vacuum,
+    /// Refresh the statistics the query planner picks indexes by.
+    Analyze,
+    /// Move the write-ahead log into the database file and truncate it.
+    WalCheckpoint,
+    /// Back up the database into `backup.directory`.
+    Backup,
+}
+
+impl JobKind {
+    pub const ALL: [JobKind; 4] = [
+        JobKind::Vacuum,
+        JobKind::Analyze,
+        JobKind::WalCheckpoint,
+        JobKind::Backup,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            JobKind::Vacuum => "vacuum",
+            JobKind::Analyze => "analyze",
+            JobKind::WalCheckpoint => "wal_checkpoint",
+            JobKind::Backup => "backup",
+        }
+    }
+}
+
+impl ToSql for JobKind {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(self.as_str().into())
+    }
+}
+
+impl FromSql for JobKind {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let value = value.as_str()?;
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == value)
+            .ok_or(FromSqlError::InvalidType)
+    }
+}
+
+#[derive(Debug, Enum, Clone, Copy, PartialEq, Eq)]
+#[oai(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// Waiting for `run_at`, periodic jobs go back to it after each run.
+    Queued,
+    /// Leased by a scheduler and running.
+    Running,
+    /// A one-shot job that is done.
+    Succeeded,
+    /// A one-shot job that failed on its last attempt.
+    Failed,
+}
+
+impl JobStatus {
+    pub const ALL: [JobStatus; 4] = [
+        JobStatus::Queued,
+        JobStatus::Running,
+        JobStatus::Succeeded,
+        JobStatus::Failed,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Succeeded => "succeeded",
+            JobStatus::Failed => "failed",
+        }
+    }
+}
+
+impl ToSql for JobStatus {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(self.as_str().into())
+    }
+}
+
+impl FromSql for JobStatus {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let value = value.as_str()?;
+        Self::ALL
+            .into_iter()
+            .find(|status| status.as_str() == value)
+            .ok_or(FromSqlError::InvalidType)
+    }
+}
+
+#[derive(Debug, Object, Clone)]
+pub struct JobObject {
+    pub id: i64,
+    pub kind: JobKind,
+    /// Cron expression of a periodic job, left out for a one-shot job.
+    pub schedule: Option<String>,
+    pub status: JobStatus,
+    /// Runs since the job was queued, or since its last scheduled time for a periodic job.
+    pub attempts: u32,
+    /// When the job is due, or was due once a one-shot job is over.
+    pub run_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Error of the latest run, left out when it succeeded.
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
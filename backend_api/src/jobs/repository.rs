@@ -0,0 +1,268 @@
+use crate::common::context::{Context, ContextError, FromContext};
+use crate::common::db::{DefaultConnection, SqliteClient};
+use crate::jobs::object::{JobKind, JobObject, JobStatus};
+use chrono::{DateTime, TimeDelta, Utc};
+use error_stack::{Report, ResultExt};
+use rusqlite::{OptionalExtension, Row, TransactionBehavior, named_params};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum JobRepositoryError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Row Value error")]
+    RowValueError,
+    #[error("Lock error")]
+    LockError,
+    #[error("Lease lost error")]
+    LeaseLostError,
+}
+
+fn job_from_row(row: &Row) -> rusqlite::Result<JobObject> {
+    Ok(JobObject {
+        id: row.get("id")?,
+        kind: row.get("kind")?,
+        schedule: row.get("schedule")?,
+        status: row.get("status")?,
+        attempts: row.get("attempts")?,
+        run_at: row.get("run_at")?,
+        started_at: row.get("started_at")?,
+        finished_at: row.get("finished_at")?,
+        last_error: row.get("last_error")?,
+        created_at: row.get("created_at")?,
+    })
+}
+
+/// A periodic job as configured, with the next time its schedule fires.
+pub struct PeriodicJob<'a> {
+    pub kind: JobKind,
+    pub schedule: &'a str,
+    pub next_run_at: DateTime<Utc>,
+}
+
+/// A job leased to a scheduler, `attempts` counts the run it was leased for.
+#[derive(Debug, Clone)]
+pub struct LeasedJob {
+    pub id: i64,
+    pub kind: JobKind,
+    pub schedule: Option<String>,
+    pub attempts: u32,
+}
+
+/// Where a job stands once a run is over.
+#[derive(Debug, PartialEq)]
+pub struct JobFinish {
+    pub status: JobStatus,
+    pub attempts: u32,
+    pub run_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+}
+
+/// The job queue, kept in the main database.
+pub struct JobRepository {
+    sqlite_client: SqliteClient,
+}
+
+impl JobRepository {
+    pub fn new(sqlite_client: SqliteClient) -> Self {
+        Self { sqlite_client }
+    }
+
+    pub fn fetch_all_jobs(&self) -> Result<Box<[JobObject]>, Report<JobRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| JobRepositoryError::LockError)?;
+
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_all_jobs.sql"))
+            .change_context(JobRepositoryError::QueryError)?;
+
+        let item_iter = stmt
+            .query_map([], job_from_row)
+            .change_context(JobRepositoryError::QueryError)?;
+
+        let mut items = Vec::new();
+        for item in item_iter {
+            items.push(item.change_context(JobRepositoryError::RowValueError)?);
+        }
+
+        Ok(items.into())
+    }
+
+    /// Queue a one-shot job, run once `run_at` has passed.
+    pub fn queue_job(
+        &self,
+        kind: JobKind,
+        run_at: DateTime<Utc>,
+    ) -> Result<i64, Report<JobRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| JobRepositoryError::LockError)?;
+
+        conn.execute(
+            include_str!("_sql/add_job.sql"),
+            named_params! {
+                ":kind": kind,
+                ":run_at": run_at,
+                ":created_at": Utc::now(),
+            },
+        )
+        .change_context(JobRepositoryError::QueryError)?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Keep one periodic job per entry of `periodic_jobs`, rescheduled when its expression
+    /// changed, and drop the periodic jobs left out of it unless they are running.
+    pub fn sync_periodic_jobs(
+        &self,
+        periodic_jobs: &[PeriodicJob],
+    ) -> Result<(), Report<JobRepositoryError>> {
+        let mut conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| JobRepositoryError::LockError)?;
+        let tx = conn
+            .transaction()
+            .change_context(JobRepositoryError::QueryError)?;
+
+        let now = Utc::now();
+        for periodic_job in periodic_jobs {
+            tx.execute(
+                include_str!("_sql/upsert_periodic_job.sql"),
+                named_params! {
+                    ":kind": periodic_job.kind,
+                    ":schedule": periodic_job.schedule,
+                    ":run_at": periodic_job.next_run_at,
+                    ":created_at": now,
+                },
+            )
+            .change_context(JobRepositoryError::QueryError)?;
+        }
+
+        let kinds = {
+            let mut stmt = tx
+                .prepare(include_str!("_sql/fetch_periodic_job_kinds.sql"))
+                .change_context(JobRepositoryError::QueryError)?;
+            let kind_iter = stmt
+                .query_map([], |row| row.get::<_, JobKind>("kind"))
+                .change_context(JobRepositoryError::QueryError)?;
+            let mut kinds = Vec::new();
+            for kind in kind_iter {
+                kinds.push(kind.change_context(JobRepositoryError::RowValueError)?);
+            }
+            kinds
+        };
+        for kind in kinds {
+            if periodic_jobs
+                .iter()
+                .all(|periodic_job| periodic_job.kind != kind)
+            {
+                tx.execute(
+                    include_str!("_sql/delete_periodic_job.sql"),
+                    named_params! {":kind": kind},
+                )
+                .change_context(JobRepositoryError::QueryError)?;
+            }
+        }
+
+        tx.commit().change_context(JobRepositoryError::QueryError)?;
+        Ok(())
+    }
+
+    /// Lease the job that has been due the longest to `lease_owner` until `lease` has passed.
+    /// Jobs whose lease ran out, as when a server stopped while running them, are due again.
+    pub fn lease_due_job(
+        &self,
+        lease_owner: &str,
+        lease: TimeDelta,
+    ) -> Result<Option<LeasedJob>, Report<JobRepositoryError>> {
+        let mut conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| JobRepositoryError::LockError)?;
+        let tx = conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .change_context(JobRepositoryError::QueryError)?;
+
+        let now = Utc::now();
+        let job = tx
+            .query_row(
+                include_str!("_sql/fetch_due_job.sql"),
+                named_params! {":now": now},
+                |row| {
+                    Ok(LeasedJob {
+                        id: row.get("id")?,
+                        kind: row.get("kind")?,
+                        schedule: row.get("schedule")?,
+                        attempts: row.get::<_, u32>("attempts")? + 1,
+                    })
+                },
+            )
+            .optional()
+            .change_context(JobRepositoryError::QueryError)?;
+
+        if let Some(job) = &job {
+            tx.execute(
+                include_str!("_sql/lease_job.sql"),
+                named_params! {
+                    ":id": job.id,
+                    ":lease_owner": lease_owner,
+                    ":lease_expires_at": now + lease,
+                    ":now": now,
+                },
+            )
+            .change_context(JobRepositoryError::QueryError)?;
+        }
+        tx.commit().change_context(JobRepositoryError::QueryError)?;
+        Ok(job)
+    }
+
+    /// Record the end of a run of a job leased to `lease_owner`.
+    pub fn finish_job(
+        &self,
+        id: i64,
+        lease_owner: &str,
+        finish: &JobFinish,
+    ) -> Result<(), Report<JobRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| JobRepositoryError::LockError)?;
+
+        let finished = conn
+            .execute(
+                include_str!("_sql/finish_job.sql"),
+                named_params! {
+                    ":id": id,
+                    ":lease_owner": lease_owner,
+                    ":status": finish.status,
+                    ":attempts": finish.attempts,
+                    ":run_at": finish.run_at,
+                    ":last_error": finish.last_error,
+                    ":now": Utc::now(),
+                },
+            )
+            .change_context(JobRepositoryError::QueryError)?;
+        if finished == 0 {
+            return Err(JobRepositoryError::LeaseLostError.into());
+        }
+
+        Ok(())
+    }
+}
+
+impl FromContext for JobRepository {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(
+            SqliteClient::<DefaultConnection>::from_context(ctx).await?,
+        ))
+    }
+}
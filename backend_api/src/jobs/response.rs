@@ -0,0 +1,11 @@
+use crate::jobs::object::JobObject;
+use poem_openapi::ApiResponse;
+use poem_openapi::payload::Json;
+
+#[derive(ApiResponse)]
+pub enum FetchAllJobsResponse {
+    #[oai(status = 200)]
+    Ok(Json<Vec<JobObject>>),
+    #[oai(status = 500)]
+    InternalServerError,
+}
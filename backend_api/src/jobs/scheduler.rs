@@ -0,0 +1,224 @@
+use crate::backup::store::BackupStores;
+use crate::common::config::Config;
+use crate::common::config::jobs::JobsConfig;
+use crate::common::config::tenant::TenantConfig;
+use crate::common::db::SqliteClient;
+use crate::jobs::object::JobStatus;
+use crate::jobs::repository::{
+    JobFinish, JobRepository, JobRepositoryError, LeasedJob, PeriodicJob,
+};
+use crate::jobs::task::{self, JobTaskError, describe};
+use crate::tenant::repository::TenantRepository;
+use chrono::{DateTime, TimeDelta, Utc};
+use cron::Schedule;
+use error_stack::{Report, ResultExt};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::{JoinHandle, spawn_blocking};
+use tokio::time::sleep;
+use tracing::error;
+
+fn next_run_at(schedule: &str, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    Schedule::from_str(schedule).ok()?.after(&after).next()
+}
+
+/// Where `job` stands after a run that failed with `error`, or succeeded without one. A
+/// failed run is retried with a doubling delay until `max_attempts` runs failed.
+pub fn finish(
+    job: &LeasedJob,
+    error: Option<String>,
+    config: &JobsConfig,
+    now: DateTime<Utc>,
+) -> JobFinish {
+    if error.is_some() && job.attempts < config.max_attempts {
+        let backoff = 1_i64 << (job.attempts - 1).min(16);
+        return JobFinish {
+            status: JobStatus::Queued,
+            attempts: job.attempts,
+            run_at: now + TimeDelta::seconds(config.retry_delay_secs as i64 * backoff),
+            last_error: error,
+        };
+    }
+    match job
+        .schedule
+        .as_deref()
+        .map(|schedule| next_run_at(schedule, now))
+    {
+        Some(Some(run_at)) => JobFinish {
+            status: JobStatus::Queued,
+            attempts: 0,
+            run_at,
+            last_error: error,
+        },
+        Some(None) => JobFinish {
+            status: JobStatus::Failed,
+            attempts: job.attempts,
+            run_at: now,
+            last_error: Some(error.unwrap_or_else(|| "Schedule has no next time".to_string())),
+        },
+        None => JobFinish {
+            status: match error {
+                Some(_) => JobStatus::Failed,
+                None => JobStatus::Succeeded,
+            },
+            attempts: job.attempts,
+            run_at: now,
+            last_error: error,
+        },
+    }
+}
+
+/// Bring the periodic jobs in line with `jobs.schedules`.
+pub fn sync_schedules(
+    repository: &JobRepository,
+    config: &JobsConfig,
+) -> Result<(), Report<JobRepositoryError>> {
+    let now = Utc::now();
+    let periodic_jobs: Vec<PeriodicJob> = config
+        .periodic_jobs()
+        .into_iter()
+        .filter_map(|(kind, schedule, parsed)| {
+            Some(PeriodicJob {
+                kind,
+                schedule,
+                next_run_at: parsed.after(&now).next()?,
+            })
+        })
+        .collect();
+    repository.sync_periodic_jobs(&periodic_jobs)
+}
+
+/// Tenants in multi-tenant mode, leaving out those never used, which have no database file
+/// yet.
+fn tenant_slugs(
+    tenant_repository: &TenantRepository,
+    config: &TenantConfig,
+) -> Result<Vec<String>, Report<JobTaskError>> {
+    if !config.enabled {
+        return Ok(Vec::new());
+    }
+    Ok(tenant_repository
+        .fetch_all_tenants()
+        .change_context(JobTaskError::QueryError)?
+        .iter()
+        .filter(|tenant| config.database_path(&tenant.slug).exists())
+        .map(|tenant| tenant.slug.clone())
+        .collect())
+}
+
+/// Run the jobs that are due one after the other, until none is left or `stopped` turns
+/// true. Returns how many ran.
+pub async fn run_due_jobs(
+    repository: &JobRepository,
+    tenant_repository: &TenantRepository,
+    config: &Config,
+    lease_owner: &str,
+    stopped: &watch::Receiver<bool>,
+) -> Result<usize, Report<JobRepositoryError>> {
+    let lease = TimeDelta::seconds(config.jobs.lease_secs as i64);
+    let mut ran = 0;
+    while !*stopped.borrow() {
+        let Some(job) = repository.lease_due_job(lease_owner, lease)? else {
+            break;
+        };
+
+        let sqlite_path = config.sqlite.path.clone();
+        let tenant_config = Arc::clone(&config.tenant);
+        let backup_stores = BackupStores::new(config);
+        let kind = job.kind;
+        let error = match tenant_slugs(tenant_repository, &config.tenant) {
+            Ok(tenant_slugs) => match spawn_blocking(move || {
+                task::run(
+                    kind,
+                    &sqlite_path,
+                    &tenant_slugs,
+                    &tenant_config,
+                    &backup_stores,
+                )
+            })
+            .await
+            {
+                Ok(Ok(())) => None,
+                Ok(Err(report)) => Some(describe(&report)),
+                Err(err) => Some(format!("Job panicked: {}", err)),
+            },
+            Err(report) => Some(describe(&report)),
+        };
+
+        repository.finish_job(
+            job.id,
+            lease_owner,
+            &finish(&job, error, &config.jobs, Utc::now()),
+        )?;
+        ran += 1;
+    }
+    Ok(ran)
+}
+
+async fn poll(
+    sqlite_client: SqliteClient,
+    lease_owner: String,
+    mut stopped: watch::Receiver<bool>,
+) {
+    let repository = JobRepository::new(sqlite_client.clone());
+    let tenant_repository = TenantRepository::new(sqlite_client.clone());
+    while !*stopped.borrow() {
+        // Read on every round, so schedule changes apply on config reload.
+        let config = match Config::current().await {
+            Ok(config) => config,
+            Err(report) => {
+                error!("Job scheduler has no config: {:?}", report);
+                return;
+            }
+        };
+        if let Err(report) = sync_schedules(&repository, &config.jobs) {
+            error!("Job schedules not synced: {:?}", report);
+        }
+        if let Err(report) = run_due_jobs(
+            &repository,
+            &tenant_repository,
+            &config,
+            &lease_owner,
+            &stopped,
+        )
+        .await
+        {
+            error!("Jobs not run: {:?}", report);
+        }
+
+        tokio::select! {
+            changed = stopped.changed() => {
+                if changed.is_err() {
+                    return;
+                }
+            }
+            _ = sleep(Duration::from_secs(config.jobs.poll_interval_secs)) => {}
+        }
+    }
+}
+
+/// Runs due jobs in the background until stopped.
+pub struct Scheduler {
+    stop: watch::Sender<bool>,
+    task: JoinHandle<()>,
+}
+
+impl Scheduler {
+    /// Start polling the job queue of the main database `sqlite_client` is open on.
+    pub fn start(sqlite_client: SqliteClient) -> Self {
+        let lease_owner = format!("{}-{:016x}", std::process::id(), rand::random::<u64>());
+        let (stop, stopped) = watch::channel(false);
+        let task = tokio::spawn(poll(sqlite_client, lease_owner, stopped));
+        Self { stop, task }
+    }
+
+    /// Stop polling, once the job running right now, if any, is over.
+    pub async fn stop(self) {
+        self.stop.send_replace(true);
+        if let Err(err) = self.task.await {
+            error!("Job scheduler stopped abnormally: {}", err);
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use crate::backup::store::{BackupStore, BackupStores};
+use crate::common::config::tenant::TenantConfig;
+use crate::jobs::object::JobKind;
+use chrono::Utc;
+use error_stack::{AttachmentKind, FrameKind, Report, ResultExt};
+use rusqlite::Connection;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum JobTaskError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Backup error")]
+    BackupError,
+    #[error("Checkpoint blocked by a reader or writer")]
+    CheckpointBusy,
+}
+
+/// One line out of every context and printable attachment of `report`, kept as the error of
+/// the run.
+pub fn describe(report: &Report<JobTaskError>) -> String {
+    report
+        .frames()
+        .filter_map(|frame| match frame.kind() {
+            FrameKind::Context(context) => Some(context.to_string()),
+            FrameKind::Attachment(AttachmentKind::Printable(attachment)) => {
+                Some(attachment.to_string())
+            }
+            FrameKind::Attachment(_) => None,
+        })
+        .collect::<Vec<_>>()
+        .join(": ")
+}
+
+fn checkpoint(conn: &Connection) -> Result<(), Report<JobTaskError>> {
+    let busy: i64 = conn
+        .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |row| row.get(0))
+        .change_context(JobTaskError::QueryError)?;
+    if busy != 0 {
+        return Err(JobTaskError::CheckpointBusy.into());
+    }
+    Ok(())
+}
+
+fn open(path: &Path) -> Result<Connection, Report<JobTaskError>> {
+    Connection::open(path)
+        .change_context(JobTaskError::QueryError)
+        .attach_printable_lazy(|| format!("Opening {}", path.display()))
+}
+
+/// Carry out `kind` on the database at `path`, backed up into `backup_store`.
+fn run_on(
+    kind: JobKind,
+    path: &Path,
+    backup_store: BackupStore,
+) -> Result<(), Report<JobTaskError>> {
+    match kind {
+        JobKind::Vacuum => open(path)?
+            .execute_batch("VACUUM")
+            .change_context(JobTaskError::QueryError),
+        JobKind::Analyze => open(path)?
+            .execute_batch("ANALYZE")
+            .change_context(JobTaskError::QueryError),
+        JobKind::WalCheckpoint => checkpoint(&open(path)?),
+        JobKind::Backup => backup_store
+            .create(Utc::now())
+            .map(|_| ())
+            .change_context(JobTaskError::BackupError),
+    }
+    .attach_printable_lazy(|| format!("On {}", path.display()))
+}
+
+/// Carry out `kind` on the main database at `sqlite_path`, then on the databases of the
+/// tenants `tenant_slugs`, each on a connection of its own so requests are not held up behind
+/// the shared ones meanwhile. A database failing does not keep the others from their turn.
+pub fn run(
+    kind: JobKind,
+    sqlite_path: &str,
+    tenant_slugs: &[String],
+    tenant_config: &TenantConfig,
+    backup_stores: &BackupStores,
+) -> Result<(), Report<JobTaskError>> {
+    let databases = std::iter::once((None, PathBuf::from(sqlite_path))).chain(
+        tenant_slugs
+            .iter()
+            .map(|slug| (Some(slug.as_str()), tenant_config.database_path(slug))),
+    );
+
+    let mut result: Result<(), Report<JobTaskError>> = Ok(());
+    for (slug, path) in databases {
+        if let Err(report) = run_on(kind, &path, backup_stores.store(slug)) {
+            match &mut result {
+                Ok(()) => result = Err(report),
+                Err(first) => first.extend_one(report),
+            }
+        }
+    }
+    result
+}
@@ -0,0 +1,290 @@
+use crate::api_key::repository::ApiKeyRepository;
+use crate::backup::store::{BackupStore, BackupStores};
+use crate::common::config::Config;
+use crate::common::config::backup::BackupConfig;
+use crate::common::config::jobs::JobsConfig;
+use crate::common::config::sqlite::SqliteConfig;
+use crate::common::config::tenant::TenantConfig;
+use crate::common::db::SqliteClient;
+use crate::jobs::object::{JobKind, JobStatus};
+use crate::jobs::repository::{JobRepository, JobRepositoryError, LeasedJob};
+use crate::jobs::scheduler::{finish, run_due_jobs, sync_schedules};
+use crate::tenant::object::TenantAddObject;
+use crate::tenant::pool::TenantPool;
+use crate::tenant::repository::TenantRepository;
+use crate::test_support::TestApp;
+use chrono::{TimeDelta, TimeZone, Utc};
+use poem::http::StatusCode;
+use rusqlite::Connection;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+use tokio::sync::watch;
+
+fn repository() -> (JobRepository, SqliteClient) {
+    let sqlite_client = SqliteClient::new_in_memory().expect("in-memory database");
+    (JobRepository::new(sqlite_client.clone()), sqlite_client)
+}
+
+/// Jobs run on a connection of their own, so on a database file, kept in `directory` along
+/// with its backups and the databases of the tenants, if enabled.
+fn file_database(
+    directory: &Path,
+    backups_kept: usize,
+    tenants: bool,
+) -> (Config, JobRepository, TenantRepository) {
+    let config = Config {
+        sqlite: Arc::new(SqliteConfig {
+            path: directory.join("sqlite.db").to_string_lossy().to_string(),
+        }),
+        backup: Arc::new(BackupConfig {
+            directory: directory.join("backups").to_string_lossy().to_string(),
+            kept: backups_kept,
+        }),
+        tenant: Arc::new(TenantConfig {
+            enabled: tenants,
+            directory: directory.join("tenants").to_string_lossy().to_string(),
+            ..TenantConfig::default()
+        }),
+        ..Config::default()
+    };
+    let sqlite_client = SqliteClient::new(config.sqlite.path.clone()).expect("database");
+    (
+        config,
+        JobRepository::new(sqlite_client.clone()),
+        TenantRepository::new(sqlite_client),
+    )
+}
+
+fn jobs_directory() -> TempDir {
+    TempDir::new().expect("jobs directory")
+}
+
+#[tokio::test]
+async fn jobs_endpoint_lists_jobs_for_platform_keys() {
+    let app = TestApp::builder().build();
+    let repository = JobRepository::new(app.sqlite_client.clone());
+    sync_schedules(&repository, &JobsConfig::default()).expect("sync");
+    repository
+        .queue_job(JobKind::Backup, Utc::now())
+        .expect("queue");
+
+    app.client
+        .get("/jobs")
+        .send()
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    let admin_key = ApiKeyRepository::new(app.sqlite_client.clone())
+        .create_api_key("admin", None)
+        .expect("admin key");
+    let res = app
+        .client
+        .get("/jobs")
+        .header("X-Api-Key", admin_key)
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    let json = res.json().await;
+    let jobs = json.value().array();
+    jobs.assert_len(5);
+    let one_shot = jobs.get(4).object();
+    one_shot.get("kind").assert_string("backup");
+    one_shot.get("status").assert_string("queued");
+}
+
+#[tokio::test]
+async fn due_one_shot_jobs_run_once() {
+    let directory = jobs_directory();
+    let (config, repository, tenant_repository) = file_database(directory.path(), 7, false);
+    repository
+        .queue_job(JobKind::Analyze, Utc::now())
+        .expect("queue");
+    repository
+        .queue_job(JobKind::Vacuum, Utc::now() + TimeDelta::hours(1))
+        .expect("queue");
+    let (_stop, stopped) = watch::channel(false);
+
+    let ran = run_due_jobs(&repository, &tenant_repository, &config, "test", &stopped)
+        .await
+        .expect("run");
+    assert_eq!(ran, 1);
+
+    let jobs = repository.fetch_all_jobs().expect("fetch");
+    assert_eq!(jobs[0].status, JobStatus::Succeeded);
+    assert_eq!(jobs[0].attempts, 1);
+    assert_eq!(jobs[0].last_error, None);
+    assert_eq!(jobs[1].status, JobStatus::Queued);
+}
+
+#[test]
+fn failed_runs_are_retried_with_a_doubling_delay() {
+    let config = JobsConfig::default();
+    let now = Utc.with_ymd_and_hms(2026, 10, 19, 12, 0, 0).unwrap();
+    let mut job = LeasedJob {
+        id: 1,
+        kind: JobKind::Vacuum,
+        schedule: None,
+        attempts: 2,
+    };
+
+    let retry = finish(&job, Some("Query error".to_string()), &config, now);
+    assert_eq!(retry.status, JobStatus::Queued);
+    assert_eq!(retry.run_at, now + TimeDelta::seconds(120));
+
+    job.attempts = 3;
+    let failed = finish(&job, Some("Query error".to_string()), &config, now);
+    assert_eq!(failed.status, JobStatus::Failed);
+    assert_eq!(failed.last_error.as_deref(), Some("Query error"));
+
+    job.schedule = Some("0 0 3 * * *".to_string());
+    let next_time = finish(&job, Some("Query error".to_string()), &config, now);
+    assert_eq!(next_time.status, JobStatus::Queued);
+    assert_eq!(next_time.attempts, 0);
+    assert_eq!(
+        next_time.run_at,
+        Utc.with_ymd_and_hms(2026, 10, 20, 3, 0, 0).unwrap()
+    );
+}
+
+#[test]
+fn expired_leases_are_taken_over() {
+    let (repository, _) = repository();
+    let id = repository
+        .queue_job(JobKind::Analyze, Utc::now())
+        .expect("queue");
+
+    let crashed = repository
+        .lease_due_job("crashed", TimeDelta::seconds(-1))
+        .expect("lease")
+        .expect("due job");
+    assert_eq!(crashed.id, id);
+    let taken_over = repository
+        .lease_due_job("live", TimeDelta::minutes(10))
+        .expect("lease")
+        .expect("expired lease");
+    assert_eq!(taken_over.id, id);
+    assert_eq!(taken_over.attempts, 2);
+    assert!(
+        repository
+            .lease_due_job("other", TimeDelta::minutes(10))
+            .expect("lease")
+            .is_none()
+    );
+
+    let finished = finish(&taken_over, None, &JobsConfig::default(), Utc::now());
+    let err = repository
+        .finish_job(id, "crashed", &finished)
+        .expect_err("lease lost");
+    assert!(matches!(
+        err.current_context(),
+        JobRepositoryError::LeaseLostError
+    ));
+    repository
+        .finish_job(id, "live", &finished)
+        .expect("finish");
+}
+
+#[test]
+fn syncing_schedules_drops_the_jobs_left_out() {
+    let (repository, _) = repository();
+    let mut config = JobsConfig::default();
+    sync_schedules(&repository, &config).expect("sync");
+    assert_eq!(repository.fetch_all_jobs().expect("fetch").len(), 4);
+
+    config.schedules.vacuum = Some(String::new());
+    config.schedules.analyze = Some("0 0 4 * * *".to_string());
+    sync_schedules(&repository, &config).expect("sync");
+    let jobs = repository.fetch_all_jobs().expect("fetch");
+    let kinds: Vec<JobKind> = jobs.iter().map(|job| job.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![JobKind::Analyze, JobKind::WalCheckpoint, JobKind::Backup]
+    );
+    assert_eq!(jobs[0].schedule.as_deref(), Some("0 0 4 * * *"));
+}
+
+#[tokio::test]
+async fn backup_jobs_keep_the_newest_backups() {
+    let directory = jobs_directory();
+    let (config, repository, tenant_repository) = file_database(directory.path(), 1, false);
+    let backup_store = BackupStore::new(Arc::clone(&config.backup), &config.sqlite.path);
+    let old = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+    backup_store.create(old).expect("old backup");
+    repository
+        .queue_job(JobKind::Backup, Utc::now())
+        .expect("queue");
+    let (_stop, stopped) = watch::channel(false);
+
+    run_due_jobs(&repository, &tenant_repository, &config, "test", &stopped)
+        .await
+        .expect("run");
+
+    assert_eq!(
+        repository.fetch_all_jobs().expect("fetch")[0].status,
+        JobStatus::Succeeded
+    );
+    let backups = backup_store.list().expect("backups");
+    assert_eq!(backups.len(), 1);
+    assert!(backups[0].created_at > old);
+    assert_eq!(
+        std::fs::read_dir(directory.path().join("backups"))
+            .expect("backups")
+            .count(),
+        2
+    );
+}
+
+fn has_statistics(path: &Path) -> bool {
+    Connection::open(path)
+        .expect("open")
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE name = 'sqlite_stat1'",
+            [],
+            |row| row.get::<_, i64>(0),
+        )
+        .expect("query")
+        == 1
+}
+
+#[tokio::test]
+async fn jobs_run_on_the_tenant_databases_too() {
+    let directory = jobs_directory();
+    let (config, repository, tenant_repository) = file_database(directory.path(), 7, true);
+    tenant_repository
+        .add_tenant(&TenantAddObject {
+            slug: "zoo-a".to_string(),
+            name: "Zoo A".to_string(),
+        })
+        .expect("tenant");
+    TenantPool::new(Arc::clone(&config.tenant))
+        .client("zoo-a")
+        .expect("tenant database");
+    let tenant_path = config.tenant.database_path("zoo-a");
+    assert!(!has_statistics(&tenant_path));
+
+    for kind in [JobKind::Analyze, JobKind::WalCheckpoint, JobKind::Backup] {
+        repository.queue_job(kind, Utc::now()).expect("queue");
+    }
+    let (_stop, stopped) = watch::channel(false);
+    run_due_jobs(&repository, &tenant_repository, &config, "test", &stopped)
+        .await
+        .expect("run");
+
+    for job in repository.fetch_all_jobs().expect("fetch") {
+        assert_eq!(job.status, JobStatus::Succeeded, "{:?}", job.last_error);
+    }
+    assert!(has_statistics(Path::new(&config.sqlite.path)));
+    assert!(has_statistics(&tenant_path));
+    let journal_mode: String = Connection::open(&tenant_path)
+        .expect("open")
+        .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+        .expect("journal mode");
+    assert_eq!(journal_mode, "wal");
+    let tenant_backups = BackupStores::new(&config)
+        .store(Some("zoo-a"))
+        .list()
+        .expect("tenant backups");
+    assert_eq!(tenant_backups.len(), 1);
+    assert!(tenant_backups[0].file_name.starts_with("zoo-a-"));
+}
@@ -1,17 +1,65 @@
 use crate::animal::AnimalApi;
+use crate::animal::v2::AnimalApiV2;
+use crate::backup::BackupApi;
+use crate::cli::animal::{export, import, seed};
+use crate::cli::api_key::create_api_key;
+use crate::cli::backup::restore;
+use crate::cli::job::queue_job;
+use crate::cli::migrate::migrate;
+use crate::cli::{Cli, Command, check_config, openapi};
 use crate::common::config::Config;
+use crate::common::config::reload::watch_for_reload;
+use crate::common::cors::reloadable_cors;
+use crate::common::db::SqliteClient;
+use crate::common::limits::{compressed, limited};
 use crate::common::locale::build_resources;
+use crate::common::locale::middleware::negotiated_language;
+use crate::common::logging::init_logging;
 use crate::common::object::Message;
+use crate::common::security_headers::security_headers;
+use crate::geo::GeoApi;
+use crate::graphql::{build_schema, graphiql, graphql_request, graphql_ws};
+use crate::idempotency::idempotent;
+use crate::jobs::JobApi;
+use crate::jobs::scheduler::Scheduler;
+use crate::photo::PhotoApi;
+use crate::tag::TagApi;
+use crate::taxonomy::TaxonomyApi;
+use crate::tenant::TenantApi;
+use crate::translation::TranslationApi;
+use crate::validation::ValidationApi;
+use crate::validation::spec::openapi_json;
+use crate::version::{ApiVersion, VersionUsage, versioned, versions};
+use clap::Parser;
 use error_stack::{Report, ResultExt};
 use poem::listener::TcpListener;
-use poem::middleware::Cors;
-use poem::{EndpointExt, Route, Server};
+use poem::{Endpoint, EndpointExt, Response, Route, Server, get};
 use poem_openapi::payload::Json;
 use poem_openapi::{OpenApi, OpenApiService, Tags};
+use shared::i18n::check_bundle_keys;
+use std::future::pending;
+use std::time::Duration;
 use thiserror::Error;
+use tracing::{info, warn};
 
 pub mod animal;
+pub mod api_key;
+pub mod backup;
+pub mod cli;
 pub mod common;
+pub mod geo;
+pub mod graphql;
+pub mod idempotency;
+pub mod jobs;
+pub mod photo;
+pub mod tag;
+pub mod taxonomy;
+pub mod tenant;
+#[cfg(test)]
+pub mod test_support;
+pub mod translation;
+pub mod validation;
+pub mod version;
 
 #[derive(Tags)]
 pub enum ApiTag {
@@ -19,9 +67,27 @@ pub enum ApiTag {
     Home,
     /// All about animals
     Animal,
+    /// Animal photos and their thumbnails
+    Photo,
+    /// Classification tree of the animals
+    Taxonomy,
+    /// Tags animals are filed under, such as exhibit or diet
+    Tag,
+    /// Habitat ranges and sightings of the animals, as GeoJSON
+    Geo,
+    /// Species and description of the animals in other languages
+    Translation,
+    /// Tenant administration, requires a platform API key
+    Tenant,
+    /// Rules clients should validate input against
+    Validation,
+    /// Background maintenance jobs, requires a platform API key
+    Job,
+    /// Backups of the main database, requires a platform API key
+    Backup,
 }
 
-struct HomeApi;
+pub struct HomeApi;
 
 #[OpenApi(tag = "ApiTag::Home")]
 impl HomeApi {
@@ -42,32 +108,209 @@ pub enum MainError {
     IoError,
     #[error("Locale error")]
     LocaleError,
+    #[error("Command error")]
+    CommandError,
+    #[error("Database error")]
+    DatabaseError,
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Report<MainError>> {
-    let config = Config::fetch()
-        .await
-        .change_context_lazy(|| MainError::ConfigError)?;
+pub type Apis = (
+    HomeApi,
+    AnimalApi,
+    PhotoApi,
+    TaxonomyApi,
+    TagApi,
+    GeoApi,
+    TranslationApi,
+    TenantApi,
+    ValidationApi,
+    JobApi,
+    BackupApi,
+);
+
+pub fn api_service() -> OpenApiService<Apis, ()> {
+    OpenApiService::new(
+        (
+            HomeApi,
+            AnimalApi,
+            PhotoApi,
+            TaxonomyApi,
+            TagApi,
+            GeoApi,
+            TranslationApi,
+            TenantApi,
+            ValidationApi,
+            JobApi,
+            BackupApi,
+        ),
+        "Animal API",
+        "1.0.0",
+    )
+}
+
+pub type ApisV2 = (
+    HomeApi,
+    AnimalApiV2,
+    PhotoApi,
+    TaxonomyApi,
+    TagApi,
+    GeoApi,
+    TranslationApi,
+    TenantApi,
+    ValidationApi,
+    JobApi,
+    BackupApi,
+);
+
+pub fn api_service_v2() -> OpenApiService<ApisV2, ()> {
+    OpenApiService::new(
+        (
+            HomeApi,
+            AnimalApiV2,
+            PhotoApi,
+            TaxonomyApi,
+            TagApi,
+            GeoApi,
+            TranslationApi,
+            TenantApi,
+            ValidationApi,
+            JobApi,
+            BackupApi,
+        ),
+        "Animal API",
+        "2.0.0",
+    )
+}
 
-    let api_service = OpenApiService::new((HomeApi, AnimalApi), "Animal API", "1.0.0");
+/// A version of the API with its Swagger UI at `/docs` and its spec at `/openapi.json`.
+fn version_routes<T: OpenApi + 'static>(
+    version: ApiVersion,
+    api_service: OpenApiService<T, ()>,
+) -> impl Endpoint<Output = Response> {
     let ui = api_service.swagger_ui();
-    let app = Route::new()
+    Route::new()
         .nest("/", api_service)
         .nest("/docs", ui)
+        .at("/openapi.json", get(openapi_json))
+        .around(move |next, req| versioned(version, next, req))
+}
+
+/// The full route tree with its middleware, shared by the server and the tests.
+pub fn build_app() -> Result<impl Endpoint<Output = Response>, Report<MainError>> {
+    check_bundle_keys()
+        .map_err(Report::new)
+        .change_context(MainError::LocaleError)?;
+    let app = Route::new()
+        .nest(
+            ApiVersion::V1.base_path(),
+            version_routes(
+                ApiVersion::V1,
+                api_service().server(ApiVersion::V1.base_path()),
+            ),
+        )
+        .nest(
+            ApiVersion::V2.base_path(),
+            version_routes(
+                ApiVersion::V2,
+                api_service_v2().server(ApiVersion::V2.base_path()),
+            ),
+        )
+        .nest("/", version_routes(ApiVersion::V1, api_service()))
+        .at("/versions", get(versions))
+        .at("/graphql", get(graphiql).post(graphql_request))
+        .at("/graphql/ws", get(graphql_ws))
+        .data(build_schema())
+        .data(VersionUsage::default())
         .data(build_resources().change_context(MainError::LocaleError)?);
 
-    let cors = Cors::new();
-    let app = app.with(cors);
+    Ok(app
+        .around(idempotent)
+        .around(negotiated_language)
+        .around(limited)
+        .around(compressed)
+        .around(reloadable_cors)
+        .around(security_headers))
+}
+
+/// How long requests in flight get to finish once shutdown is asked for.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Resolves on Ctrl-C, or on SIGTERM on unix.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(err) => {
+                warn!("Unable to listen for SIGTERM, only Ctrl-C stops the server: {err}");
+                pending::<()>().await
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = pending::<()>();
+
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = terminate => {}
+    }
+}
+
+async fn serve() -> Result<(), Report<MainError>> {
+    let config = Config::fetch()
+        .await
+        .change_context_lazy(|| MainError::ConfigError)?;
+
+    let app = build_app()?;
 
     match config.upgrade() {
         Some(config) => {
-            println!("Listening on http://{}", config.poem.parse_address());
-            Server::new(TcpListener::bind(config.poem.parse_address().as_str()))
-                .run(app)
+            init_logging(&config)
                 .await
-                .change_context_lazy(|| MainError::IoError)
+                .change_context(MainError::ConfigError)?;
+            tokio::spawn(watch_for_reload());
+
+            let scheduler = if config.jobs.enabled {
+                let sqlite_client = SqliteClient::shared(&config.sqlite.path)
+                    .await
+                    .change_context(MainError::DatabaseError)?;
+                Some(Scheduler::start(sqlite_client))
+            } else {
+                None
+            };
+
+            info!("Listening on http://{}", config.poem.parse_address());
+            let served = Server::new(TcpListener::bind(config.poem.parse_address().as_str()))
+                .run_with_graceful_shutdown(app, shutdown_signal(), Some(SHUTDOWN_TIMEOUT))
+                .await
+                .change_context_lazy(|| MainError::IoError);
+
+            if let Some(scheduler) = scheduler {
+                scheduler.stop().await;
+            }
+            served
         }
         None => Err(Report::new(MainError::ConfigError)),
     }
 }
+
+#[tokio::main]
+async fn main() -> Result<(), Report<MainError>> {
+    let cli = Cli::parse();
+
+    let result = match cli.command.unwrap_or(Command::Serve) {
+        Command::Serve => return serve().await,
+        Command::Migrate { action } => migrate(action, cli.tenant).await,
+        Command::Seed { file } => seed(file, cli.tenant).await,
+        Command::Export { output } => export(output, cli.tenant).await,
+        Command::Import { file } => import(file, cli.tenant).await,
+        Command::CheckConfig => check_config().await,
+        Command::Openapi { format, api } => openapi(format, api).await,
+        Command::CreateApiKey { name } => create_api_key(name, cli.tenant).await,
+        Command::QueueJob { kind } => queue_job(kind).await,
+        Command::Restore { file } => restore(file, cli.tenant).await,
+    };
+    result.change_context(MainError::CommandError)
+}
@@ -0,0 +1,2 @@
+INSERT INTO animal_photo (animal_id, file_key, content_type, width, height, byte_size, created_at)
+VALUES (:animal_id, :file_key, :content_type, :width, :height, :byte_size, :created_at)
\ No newline at end of file
@@ -0,0 +1,2 @@
+INSERT INTO animal_photo_thumbnail (photo_id, size, file_key, width, height)
+VALUES (:photo_id, :size, :file_key, :width, :height)
\ No newline at end of file
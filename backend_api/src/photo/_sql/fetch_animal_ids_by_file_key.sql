@@ -0,0 +1,8 @@
+SELECT animal_id
+FROM animal_photo
+WHERE file_key = :file_key
+UNION
+SELECT p.animal_id
+FROM animal_photo_thumbnail t
+         JOIN animal_photo p ON p.id = t.photo_id
+WHERE t.file_key = :file_key
\ No newline at end of file
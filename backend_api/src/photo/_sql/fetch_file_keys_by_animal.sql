@@ -0,0 +1,8 @@
+SELECT file_key
+FROM animal_photo
+WHERE animal_id = :animal_id
+UNION
+SELECT t.file_key
+FROM animal_photo_thumbnail t
+         JOIN animal_photo p ON p.id = t.photo_id
+WHERE p.animal_id = :animal_id
\ No newline at end of file
@@ -0,0 +1,4 @@
+SELECT id, animal_id, file_key, content_type, width, height, byte_size, created_at
+FROM animal_photo
+WHERE animal_id = :animal_id
+ORDER BY id
\ No newline at end of file
@@ -0,0 +1,5 @@
+SELECT t.photo_id, t.size, t.file_key, t.width, t.height
+FROM animal_photo_thumbnail t
+         JOIN animal_photo p ON p.id = t.photo_id
+WHERE p.animal_id = :animal_id
+ORDER BY t.photo_id, t.size
\ No newline at end of file
@@ -0,0 +1,157 @@
+pub mod object;
+pub mod repository;
+pub mod response;
+pub mod storage;
+pub mod sweeper;
+#[cfg(test)]
+mod tests;
+
+use crate::ApiTag;
+use crate::animal::repository::DynAnimalRepository;
+use crate::common::context::Dep;
+use crate::common::object::Message;
+use crate::common::results::unified;
+use crate::photo::object::UploadPhotoPayload;
+use crate::photo::repository::PhotoRepository;
+use crate::photo::response::{FetchAnimalPhotosResponse, ServePhotoResponse, UploadPhotoResponse};
+use crate::photo::storage::{PhotoStorage, PhotoStorageError};
+use poem_openapi::OpenApi;
+use poem_openapi::param::{Header, Path};
+use poem_openapi::payload::{Binary, Json};
+
+/// Stored files never change, so browsers may keep them for good. Shared caches may not, as
+/// whether a tenant may see a file depends on the request.
+const CACHE_CONTROL: &str = "private, max-age=31536000, immutable";
+
+pub struct PhotoApi;
+
+#[OpenApi(tag = "ApiTag::Photo")]
+impl PhotoApi {
+    /// Upload Animal Photo, stored without metadata along with its thumbnails
+    #[oai(path = "/animal/:id/photos", method = "post")]
+    async fn upload(
+        &self,
+        Path(id): Path<u64>,
+        payload: UploadPhotoPayload,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(photo_repository): Dep<PhotoRepository>,
+        Dep(photo_storage): Dep<PhotoStorage>,
+    ) -> UploadPhotoResponse {
+        unified(async {
+            animal_repository
+                .fetch_animal_by_id(id as i64)
+                .map_err(|_| UploadPhotoResponse::NotFound)?;
+            if payload.file.size() > photo_storage.max_upload_bytes() {
+                return Err(UploadPhotoResponse::PayloadTooLarge(Json(Message {
+                    message: format!(
+                        "Photo must be at most {} bytes",
+                        photo_storage.max_upload_bytes()
+                    ),
+                })));
+            }
+            let bytes = payload
+                .file
+                .into_vec()
+                .await
+                .map_err(|_| UploadPhotoResponse::InternalServerError)?;
+
+            let stored = tokio::task::spawn_blocking(move || photo_storage.store_photo(&bytes))
+                .await
+                .map_err(|_| UploadPhotoResponse::InternalServerError)?
+                .map_err(|err| match err.current_context() {
+                    PhotoStorageError::UnsupportedTypeError => {
+                        UploadPhotoResponse::UnsupportedMediaType(Json(Message {
+                            message: "Photo must be a JPEG, PNG or WebP image".to_string(),
+                        }))
+                    }
+                    PhotoStorageError::DecodeError => {
+                        UploadPhotoResponse::UnprocessableEntity(Json(Message {
+                            message: "Photo could not be decoded".to_string(),
+                        }))
+                    }
+                    _ => UploadPhotoResponse::InternalServerError,
+                })?;
+
+            photo_repository
+                .add_photo(id as i64, &stored)
+                .map(|photo| UploadPhotoResponse::Created(Json(photo)))
+                .map_err(|_| UploadPhotoResponse::InternalServerError)
+        })
+        .await
+    }
+
+    /// Fetch Animal Photos
+    #[oai(path = "/animal/:id/photos", method = "get")]
+    async fn fetch_by_animal(
+        &self,
+        Path(id): Path<u64>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(photo_repository): Dep<PhotoRepository>,
+    ) -> FetchAnimalPhotosResponse {
+        unified(async {
+            animal_repository
+                .fetch_animal_by_id(id as i64)
+                .map_err(|_| FetchAnimalPhotosResponse::NotFound)?;
+            photo_repository
+                .fetch_photos_by_animal(id as i64)
+                .map(|photos| FetchAnimalPhotosResponse::Ok(Json(photos.to_vec())))
+                .map_err(|_| FetchAnimalPhotosResponse::InternalServerError)
+        })
+        .await
+    }
+
+    /// Serve a stored Photo or Thumbnail of an animal of the tenant
+    #[oai(path = "/photo/:key", method = "get")]
+    async fn serve(
+        &self,
+        Path(key): Path<String>,
+        #[oai(name = "If-None-Match")] Header(if_none_match): Header<Option<String>>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(photo_repository): Dep<PhotoRepository>,
+        Dep(photo_storage): Dep<PhotoStorage>,
+    ) -> ServePhotoResponse {
+        unified(async {
+            if !PhotoStorage::is_valid_key(&key) {
+                return Err(ServePhotoResponse::NotFound);
+            }
+            // Files are shared by every tenant storing the same bytes, so the key alone does
+            // not tell whether this tenant may see it.
+            let animal_ids = photo_repository
+                .fetch_animal_ids_by_file_key(&key)
+                .map_err(|_| ServePhotoResponse::InternalServerError)?;
+            if !animal_ids
+                .iter()
+                .any(|id| animal_repository.fetch_animal_by_id(*id).is_ok())
+            {
+                return Err(ServePhotoResponse::NotFound);
+            }
+            let content_type =
+                PhotoStorage::content_type(&key).ok_or(ServePhotoResponse::NotFound)?;
+            let etag = format!("\"{}\"", key);
+            if if_none_match.is_some_and(|value| {
+                value.split(',').any(|tag| {
+                    let tag = tag.trim();
+                    tag == "*" || tag == etag
+                })
+            }) {
+                return Ok(ServePhotoResponse::NotModified(
+                    CACHE_CONTROL.to_string(),
+                    etag,
+                ));
+            }
+
+            let bytes = photo_storage
+                .get(&key)
+                .await
+                .map_err(|_| ServePhotoResponse::InternalServerError)?
+                .ok_or(ServePhotoResponse::NotFound)?;
+            Ok(ServePhotoResponse::Ok(
+                Binary(bytes),
+                content_type.to_string(),
+                CACHE_CONTROL.to_string(),
+                etag,
+            ))
+        })
+        .await
+    }
+}
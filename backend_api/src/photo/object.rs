@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+use poem_openapi::types::multipart::Upload;
+use poem_openapi::{Multipart, Object};
+
+#[derive(Debug, Object, Clone)]
+pub struct PhotoThumbnailObject {
+    /// Longest edge the thumbnail was generated for.
+    pub size: u32,
+    pub url: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Object, Clone)]
+pub struct AnimalPhotoObject {
+    pub id: i64,
+    pub animal_id: i64,
+    /// Served with long-lived cache headers, as the content behind a url never changes.
+    pub url: String,
+    pub content_type: String,
+    pub width: u32,
+    pub height: u32,
+    pub byte_size: i64,
+    pub created_at: DateTime<Utc>,
+    pub thumbnails: Vec<PhotoThumbnailObject>,
+}
+
+#[derive(Debug, Multipart)]
+pub struct UploadPhotoPayload {
+    /// JPEG, PNG or WebP image.
+    pub file: Upload,
+}
+
+pub fn photo_url(key: &str) -> String {
+    format!("/photo/{}", key)
+}
@@ -0,0 +1,214 @@
+use crate::common::context::{Context, ContextError, FromContext};
+use crate::common::db::{SqliteClient, TenantConnection};
+use crate::photo::object::{AnimalPhotoObject, PhotoThumbnailObject, photo_url};
+use crate::photo::storage::StoredPhoto;
+use chrono::Utc;
+use error_stack::{Report, ResultExt};
+use rusqlite::{Row, named_params};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PhotoRepositoryError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Row Value error")]
+    RowValueError,
+    #[error("Lock error")]
+    LockError,
+}
+
+fn photo_from_row(row: &Row) -> rusqlite::Result<AnimalPhotoObject> {
+    Ok(AnimalPhotoObject {
+        id: row.get("id")?,
+        animal_id: row.get("animal_id")?,
+        url: photo_url(&row.get::<_, String>("file_key")?),
+        content_type: row.get("content_type")?,
+        width: row.get("width")?,
+        height: row.get("height")?,
+        byte_size: row.get("byte_size")?,
+        created_at: row.get("created_at")?,
+        thumbnails: Vec::new(),
+    })
+}
+
+fn thumbnail_from_row(row: &Row) -> rusqlite::Result<(i64, PhotoThumbnailObject)> {
+    Ok((
+        row.get("photo_id")?,
+        PhotoThumbnailObject {
+            size: row.get("size")?,
+            url: photo_url(&row.get::<_, String>("file_key")?),
+            width: row.get("width")?,
+            height: row.get("height")?,
+        },
+    ))
+}
+
+/// Photo metadata, kept next to the animals; the files themselves live in `PhotoStorage`.
+pub struct PhotoRepository {
+    sqlite_client: SqliteClient<TenantConnection>,
+}
+
+impl PhotoRepository {
+    pub fn new(sqlite_client: SqliteClient<TenantConnection>) -> Self {
+        Self { sqlite_client }
+    }
+
+    pub fn add_photo(
+        &self,
+        animal_id: i64,
+        photo: &StoredPhoto,
+    ) -> Result<AnimalPhotoObject, Report<PhotoRepositoryError>> {
+        let mut conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| PhotoRepositoryError::LockError)?;
+        let tx = conn
+            .transaction()
+            .change_context(PhotoRepositoryError::QueryError)?;
+
+        let created_at = Utc::now();
+        tx.execute(
+            include_str!("_sql/add_photo.sql"),
+            named_params! {
+                ":animal_id": animal_id,
+                ":file_key": photo.original.key,
+                ":content_type": photo.original.format.content_type(),
+                ":width": photo.original.width,
+                ":height": photo.original.height,
+                ":byte_size": photo.original.byte_size,
+                ":created_at": created_at,
+            },
+        )
+        .change_context(PhotoRepositoryError::QueryError)?;
+        let id = tx.last_insert_rowid();
+
+        let mut thumbnails = Vec::new();
+        for (size, thumbnail) in photo.thumbnails.iter() {
+            tx.execute(
+                include_str!("_sql/add_photo_thumbnail.sql"),
+                named_params! {
+                    ":photo_id": id,
+                    ":size": size,
+                    ":file_key": thumbnail.key,
+                    ":width": thumbnail.width,
+                    ":height": thumbnail.height,
+                },
+            )
+            .change_context(PhotoRepositoryError::QueryError)?;
+            thumbnails.push(PhotoThumbnailObject {
+                size: *size,
+                url: photo_url(&thumbnail.key),
+                width: thumbnail.width,
+                height: thumbnail.height,
+            });
+        }
+        tx.commit()
+            .change_context(PhotoRepositoryError::QueryError)?;
+
+        Ok(AnimalPhotoObject {
+            id,
+            animal_id,
+            url: photo_url(&photo.original.key),
+            content_type: photo.original.format.content_type().to_string(),
+            width: photo.original.width,
+            height: photo.original.height,
+            byte_size: photo.original.byte_size,
+            created_at,
+            thumbnails,
+        })
+    }
+
+    pub fn fetch_photos_by_animal(
+        &self,
+        animal_id: i64,
+    ) -> Result<Box<[AnimalPhotoObject]>, Report<PhotoRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| PhotoRepositoryError::LockError)?;
+
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_photos_by_animal.sql"))
+            .change_context(PhotoRepositoryError::QueryError)?;
+        let item_iter = stmt
+            .query_map(named_params! {":animal_id": animal_id}, photo_from_row)
+            .change_context(PhotoRepositoryError::QueryError)?;
+        let mut photos = Vec::new();
+        for item in item_iter {
+            photos.push(item.change_context(PhotoRepositoryError::RowValueError)?);
+        }
+
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_thumbnails_by_animal.sql"))
+            .change_context(PhotoRepositoryError::QueryError)?;
+        let item_iter = stmt
+            .query_map(named_params! {":animal_id": animal_id}, thumbnail_from_row)
+            .change_context(PhotoRepositoryError::QueryError)?;
+        for item in item_iter {
+            let (photo_id, thumbnail) = item.change_context(PhotoRepositoryError::RowValueError)?;
+            if let Some(photo) = photos.iter_mut().find(|photo| photo.id == photo_id) {
+                photo.thumbnails.push(thumbnail);
+            }
+        }
+
+        Ok(photos.into())
+    }
+
+    /// Animals with a photo or thumbnail stored under `file_key`.
+    pub fn fetch_animal_ids_by_file_key(
+        &self,
+        file_key: &str,
+    ) -> Result<Box<[i64]>, Report<PhotoRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| PhotoRepositoryError::LockError)?;
+
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_animal_ids_by_file_key.sql"))
+            .change_context(PhotoRepositoryError::QueryError)?;
+        let item_iter = stmt
+            .query_map(named_params! {":file_key": file_key}, |row| row.get(0))
+            .change_context(PhotoRepositoryError::QueryError)?;
+        let mut animal_ids = Vec::new();
+        for item in item_iter {
+            animal_ids.push(item.change_context(PhotoRepositoryError::RowValueError)?);
+        }
+        Ok(animal_ids.into())
+    }
+
+    /// Keys of the files of the animal's photos and their thumbnails.
+    pub fn fetch_file_keys_by_animal(
+        &self,
+        animal_id: i64,
+    ) -> Result<Box<[String]>, Report<PhotoRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| PhotoRepositoryError::LockError)?;
+
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_file_keys_by_animal.sql"))
+            .change_context(PhotoRepositoryError::QueryError)?;
+        let item_iter = stmt
+            .query_map(named_params! {":animal_id": animal_id}, |row| row.get(0))
+            .change_context(PhotoRepositoryError::QueryError)?;
+        let mut file_keys = Vec::new();
+        for item in item_iter {
+            file_keys.push(item.change_context(PhotoRepositoryError::RowValueError)?);
+        }
+        Ok(file_keys.into())
+    }
+}
+
+impl FromContext for PhotoRepository {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(
+            SqliteClient::<TenantConnection>::from_context(ctx).await?,
+        ))
+    }
+}
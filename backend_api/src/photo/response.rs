@@ -0,0 +1,50 @@
+use crate::common::object::Message;
+use crate::photo::object::AnimalPhotoObject;
+use poem_openapi::ApiResponse;
+use poem_openapi::payload::{Binary, Json};
+
+#[derive(ApiResponse)]
+pub enum UploadPhotoResponse {
+    #[oai(status = 201)]
+    Created(Json<AnimalPhotoObject>),
+    #[oai(status = 404)]
+    NotFound,
+    #[oai(status = 413)]
+    PayloadTooLarge(Json<Message>),
+    #[oai(status = 415)]
+    UnsupportedMediaType(Json<Message>),
+    #[oai(status = 422)]
+    UnprocessableEntity(Json<Message>),
+    #[oai(status = 500)]
+    InternalServerError,
+}
+
+#[derive(ApiResponse)]
+pub enum FetchAnimalPhotosResponse {
+    #[oai(status = 200)]
+    Ok(Json<Vec<AnimalPhotoObject>>),
+    #[oai(status = 404)]
+    NotFound,
+    #[oai(status = 500)]
+    InternalServerError,
+}
+
+#[derive(ApiResponse)]
+pub enum ServePhotoResponse {
+    #[oai(status = 200)]
+    Ok(
+        Binary<Vec<u8>>,
+        #[oai(header = "Content-Type")] String,
+        #[oai(header = "Cache-Control")] String,
+        #[oai(header = "ETag")] String,
+    ),
+    #[oai(status = 304)]
+    NotModified(
+        #[oai(header = "Cache-Control")] String,
+        #[oai(header = "ETag")] String,
+    ),
+    #[oai(status = 404)]
+    NotFound,
+    #[oai(status = 500)]
+    InternalServerError,
+}
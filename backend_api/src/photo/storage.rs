@@ -0,0 +1,258 @@
+use crate::common::config::storage::StorageConfig;
+use crate::common::context::{Context, ContextError, FromContext};
+use error_stack::{Report, ResultExt};
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader};
+use sha2::{Digest, Sha256};
+use std::io::{Cursor, ErrorKind};
+use std::path::PathBuf;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PhotoStorageError {
+    #[error("IO error")]
+    IoError,
+    #[error("Unsupported type error")]
+    UnsupportedTypeError,
+    #[error("Decode error")]
+    DecodeError,
+    #[error("Encode error")]
+    EncodeError,
+    #[error("Invalid key error")]
+    InvalidKeyError,
+}
+
+/// Formats accepted for upload, sniffed from the bytes rather than the client's content type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PhotoFormat {
+    Jpeg,
+    Png,
+    WebP,
+}
+
+impl PhotoFormat {
+    fn sniff(bytes: &[u8]) -> Option<Self> {
+        match image::guess_format(bytes).ok()? {
+            ImageFormat::Jpeg => Some(Self::Jpeg),
+            ImageFormat::Png => Some(Self::Png),
+            ImageFormat::WebP => Some(Self::WebP),
+            _ => None,
+        }
+    }
+
+    fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "jpg" => Some(Self::Jpeg),
+            "png" => Some(Self::Png),
+            "webp" => Some(Self::WebP),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpg",
+            Self::Png => "png",
+            Self::WebP => "webp",
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Jpeg => "image/jpeg",
+            Self::Png => "image/png",
+            Self::WebP => "image/webp",
+        }
+    }
+
+    fn image_format(self) -> ImageFormat {
+        match self {
+            Self::Jpeg => ImageFormat::Jpeg,
+            Self::Png => ImageFormat::Png,
+            Self::WebP => ImageFormat::WebP,
+        }
+    }
+}
+
+struct EncodedImage {
+    bytes: Vec<u8>,
+    format: PhotoFormat,
+    width: u32,
+    height: u32,
+}
+
+/// An image written to storage, addressed by `key`.
+pub struct StoredImage {
+    pub key: String,
+    pub format: PhotoFormat,
+    pub width: u32,
+    pub height: u32,
+    pub byte_size: i64,
+}
+
+pub struct StoredPhoto {
+    pub original: StoredImage,
+    /// One per configured size, keyed by that size.
+    pub thumbnails: Vec<(u32, StoredImage)>,
+}
+
+/// Re-encode in `format`, which drops EXIF and any other metadata of the upload.
+fn encode(
+    image: &DynamicImage,
+    format: PhotoFormat,
+) -> Result<EncodedImage, Report<PhotoStorageError>> {
+    // JPEG has no alpha channel and the WebP encoder only takes 8-bit RGBA.
+    let image = match format {
+        PhotoFormat::Jpeg => DynamicImage::ImageRgb8(image.to_rgb8()),
+        PhotoFormat::Png | PhotoFormat::WebP => DynamicImage::ImageRgba8(image.to_rgba8()),
+    };
+    let mut bytes = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut bytes), format.image_format())
+        .change_context(PhotoStorageError::EncodeError)?;
+    Ok(EncodedImage {
+        bytes,
+        format,
+        width: image.width(),
+        height: image.height(),
+    })
+}
+
+/// An upload re-encoded, with its thumbnails by size.
+type Processed = (EncodedImage, Vec<(u32, EncodedImage)>);
+
+/// Decode an upload, apply its EXIF orientation, then re-encode it and its thumbnails.
+fn process(bytes: &[u8], thumbnail_sizes: &[u32]) -> Result<Processed, Report<PhotoStorageError>> {
+    let format = PhotoFormat::sniff(bytes).ok_or(PhotoStorageError::UnsupportedTypeError)?;
+    let mut decoder = ImageReader::with_format(Cursor::new(bytes), format.image_format())
+        .into_decoder()
+        .change_context(PhotoStorageError::DecodeError)?;
+    let orientation = decoder
+        .orientation()
+        .change_context(PhotoStorageError::DecodeError)?;
+    let mut image =
+        DynamicImage::from_decoder(decoder).change_context(PhotoStorageError::DecodeError)?;
+    image.apply_orientation(orientation);
+
+    let mut thumbnails = Vec::new();
+    for &size in thumbnail_sizes {
+        // Never upscale, an image smaller than the size is its own thumbnail.
+        let thumbnail = if size >= image.width().max(image.height()) {
+            encode(&image, format)?
+        } else {
+            encode(&image.thumbnail(size, size), format)?
+        };
+        thumbnails.push((size, thumbnail));
+    }
+
+    Ok((encode(&image, format)?, thumbnails))
+}
+
+/// Files named after the SHA-256 of their content, so an identical image is stored once and a
+/// stored file never changes.
+#[derive(Clone)]
+pub struct PhotoStorage {
+    config: Arc<StorageConfig>,
+}
+
+impl PhotoStorage {
+    pub fn new(config: Arc<StorageConfig>) -> Self {
+        Self { config }
+    }
+
+    pub fn max_upload_bytes(&self) -> usize {
+        self.config.max_upload_bytes
+    }
+
+    /// `<sha256>.<ext>`, the only keys that map to a path, which rules out traversal.
+    pub fn is_valid_key(key: &str) -> bool {
+        match key.split_once('.') {
+            Some((hash, extension)) => {
+                hash.len() == 64
+                    && hash.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f'))
+                    && PhotoFormat::from_extension(extension).is_some()
+            }
+            None => false,
+        }
+    }
+
+    pub fn content_type(key: &str) -> Option<&'static str> {
+        let (_, extension) = key.split_once('.')?;
+        PhotoFormat::from_extension(extension).map(PhotoFormat::content_type)
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, Report<PhotoStorageError>> {
+        if !Self::is_valid_key(key) {
+            return Err(Report::new(PhotoStorageError::InvalidKeyError)
+                .attach_printable(format!("Invalid storage key '{}'", key)));
+        }
+        Ok(PathBuf::from(&self.config.directory)
+            .join(&key[0..2])
+            .join(&key[2..4])
+            .join(key))
+    }
+
+    fn put(&self, image: EncodedImage) -> Result<StoredImage, Report<PhotoStorageError>> {
+        let key = format!(
+            "{:x}.{}",
+            Sha256::digest(&image.bytes),
+            image.format.extension()
+        );
+        let path = self.path(&key)?;
+        if !path.exists() {
+            let directory = path.parent().ok_or(PhotoStorageError::IoError)?;
+            std::fs::create_dir_all(directory).change_context(PhotoStorageError::IoError)?;
+            // Write then rename, so a reader never sees a partial file.
+            let temp_path = directory.join(format!(".{}.{}", key, rand::random::<u64>()));
+            std::fs::write(&temp_path, &image.bytes).change_context(PhotoStorageError::IoError)?;
+            std::fs::rename(&temp_path, &path).change_context(PhotoStorageError::IoError)?;
+        }
+        Ok(StoredImage {
+            key,
+            format: image.format,
+            width: image.width,
+            height: image.height,
+            byte_size: image.bytes.len() as i64,
+        })
+    }
+
+    /// Process an upload and store it along with its thumbnails. Blocks, so call it from
+    /// `spawn_blocking`.
+    pub fn store_photo(&self, bytes: &[u8]) -> Result<StoredPhoto, Report<PhotoStorageError>> {
+        let (original, thumbnails) = process(bytes, &self.config.thumbnail_sizes)?;
+        let original = self.put(original)?;
+        let mut stored_thumbnails = Vec::new();
+        for (size, thumbnail) in thumbnails {
+            stored_thumbnails.push((size, self.put(thumbnail)?));
+        }
+        Ok(StoredPhoto {
+            original,
+            thumbnails: stored_thumbnails,
+        })
+    }
+
+    /// Remove the file under `key`, if there is one. Blocks, so call it from `spawn_blocking`.
+    pub fn remove(&self, key: &str) -> Result<(), Report<PhotoStorageError>> {
+        match std::fs::remove_file(self.path(key)?) {
+            Err(err) if err.kind() != ErrorKind::NotFound => {
+                Err(Report::new(err).change_context(PhotoStorageError::IoError))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Report<PhotoStorageError>> {
+        match tokio::fs::read(self.path(key)?).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(Report::new(err).change_context(PhotoStorageError::IoError)),
+        }
+    }
+}
+
+impl FromContext for PhotoStorage {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let config = ctx.config.upgrade().ok_or(ContextError::ConfigError)?;
+        Ok(Self::new(Arc::clone(&config.storage)))
+    }
+}
@@ -0,0 +1,90 @@
+use crate::common::context::{Context, ContextError, FromContext};
+use crate::photo::repository::PhotoRepository;
+use crate::photo::storage::PhotoStorage;
+use crate::tenant::pool::TenantPool;
+use crate::tenant::repository::TenantRepository;
+use error_stack::{Report, ResultExt};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PhotoSweeperError {
+    #[error("Repository error")]
+    RepositoryError,
+    #[error("Storage error")]
+    StorageError,
+}
+
+/// Removes the files of deleted photos. A file is shared by every photo of every tenant with
+/// the same bytes, so it stays as long as any of them refers to it.
+pub struct PhotoSweeper {
+    photo_storage: PhotoStorage,
+    photo_repository: PhotoRepository,
+    /// Every tenant's database shares the storage in multi-tenant mode.
+    tenants: Option<(TenantRepository, TenantPool)>,
+}
+
+impl PhotoSweeper {
+    fn photo_repositories(&self) -> Result<Vec<PhotoRepository>, Report<PhotoSweeperError>> {
+        let Some((tenant_repository, tenant_pool)) = &self.tenants else {
+            return Ok(Vec::new());
+        };
+        let mut photo_repositories = Vec::new();
+        for tenant in tenant_repository
+            .fetch_all_tenants()
+            .change_context(PhotoSweeperError::RepositoryError)?
+        {
+            let sqlite_client = tenant_pool
+                .client(&tenant.slug)
+                .change_context(PhotoSweeperError::RepositoryError)?;
+            photo_repositories.push(PhotoRepository::new(sqlite_client));
+        }
+        Ok(photo_repositories)
+    }
+
+    /// Remove the files under `file_keys` no photo refers to anymore. Blocks, so call it from
+    /// `spawn_blocking`.
+    pub fn sweep(&self, file_keys: &[String]) -> Result<(), Report<PhotoSweeperError>> {
+        if file_keys.is_empty() {
+            return Ok(());
+        }
+        let tenant_photo_repositories = self.photo_repositories()?;
+        for file_key in file_keys {
+            let mut referenced = false;
+            for photo_repository in
+                std::iter::once(&self.photo_repository).chain(&tenant_photo_repositories)
+            {
+                referenced = !photo_repository
+                    .fetch_animal_ids_by_file_key(file_key)
+                    .change_context(PhotoSweeperError::RepositoryError)?
+                    .is_empty();
+                if referenced {
+                    break;
+                }
+            }
+            if !referenced {
+                self.photo_storage
+                    .remove(file_key)
+                    .change_context(PhotoSweeperError::StorageError)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromContext for PhotoSweeper {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let config = ctx.config.upgrade().ok_or(ContextError::ConfigError)?;
+        let tenants = match config.tenant.enabled {
+            true => Some((
+                TenantRepository::from_context(ctx).await?,
+                TenantPool::from_context(ctx).await?,
+            )),
+            false => None,
+        };
+        Ok(Self {
+            photo_storage: PhotoStorage::from_context(ctx).await?,
+            photo_repository: PhotoRepository::from_context(ctx).await?,
+            tenants,
+        })
+    }
+}
@@ -0,0 +1,251 @@
+use crate::api_key::repository::ApiKeyRepository;
+use crate::common::config::Config;
+use crate::common::config::storage::StorageConfig;
+use crate::common::config::tenant::TenantConfig;
+use crate::test_support::TestApp;
+use image::{ImageFormat, RgbaImage};
+use poem::http::StatusCode;
+use poem::test::{TestForm, TestFormField};
+use serde_json::json;
+use std::io::Cursor;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn storage_config(directory: &TempDir, max_upload_bytes: usize) -> Arc<StorageConfig> {
+    Arc::new(StorageConfig {
+        directory: directory.path().to_string_lossy().to_string(),
+        max_upload_bytes,
+        thumbnail_sizes: vec![16, 64],
+    })
+}
+
+/// The photos are stored in the returned directory, which is removed once dropped.
+fn photo_app(max_upload_bytes: usize) -> (TestApp, TempDir) {
+    let directory = TempDir::new().expect("storage directory");
+    let config = Config {
+        storage: storage_config(&directory, max_upload_bytes),
+        ..Config::default()
+    };
+    (TestApp::builder().config(config).build(), directory)
+}
+
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    RgbaImage::from_pixel(width, height, image::Rgba([200, 120, 40, 255]))
+        .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+        .expect("png");
+    bytes
+}
+
+fn upload_form(bytes: Vec<u8>) -> TestForm {
+    TestForm::new().field(
+        TestFormField::bytes(bytes)
+            .name("file")
+            .filename("otter.png")
+            .content_type("image/png"),
+    )
+}
+
+#[tokio::test]
+async fn upload_stores_the_photo_and_its_thumbnails() {
+    let (app, _directory) = photo_app(1024 * 1024);
+
+    let res = app
+        .client
+        .post("/animal/3/photos")
+        .multipart(upload_form(png(32, 24)))
+        .send()
+        .await;
+    res.assert_status(StatusCode::CREATED);
+    let json = res.json().await;
+    let photo = json.value().object();
+    photo.get("content_type").assert_string("image/png");
+    photo.get("width").assert_i64(32);
+    let thumbnails = photo.get("thumbnails").array();
+    thumbnails.assert_len(2);
+    thumbnails.get(0).object().get("width").assert_i64(16);
+    thumbnails.get(0).object().get("height").assert_i64(12);
+    // Larger than the photo, so the thumbnail is the photo itself.
+    thumbnails.get(1).object().get("width").assert_i64(32);
+    let thumbnail_url = thumbnails.get(0).object().get("url").string().to_string();
+
+    let res = app.client.get("/animal/3/photos").send().await;
+    res.assert_status_is_ok();
+    res.json().await.value().array().assert_len(1);
+
+    let res = app.client.get(&thumbnail_url).send().await;
+    res.assert_status_is_ok();
+    res.assert_content_type("image/png");
+    res.assert_header("Cache-Control", "private, max-age=31536000, immutable");
+    let etag = res.0.headers().get("ETag").expect("etag").clone();
+
+    app.client
+        .get(&thumbnail_url)
+        .header("If-None-Match", etag)
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_MODIFIED);
+}
+
+#[tokio::test]
+async fn upload_sniffs_the_type_and_enforces_the_size_limit() {
+    let (app, _directory) = photo_app(1024);
+
+    app.client
+        .post("/animal/3/photos")
+        .multipart(upload_form(
+            b"not an image, whatever the filename says".to_vec(),
+        ))
+        .send()
+        .await
+        .assert_status(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    app.client
+        .post("/animal/3/photos")
+        .multipart(upload_form(vec![0; 2048]))
+        .send()
+        .await
+        .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+
+    app.client
+        .post("/animal/999/photos")
+        .multipart(upload_form(png(8, 8)))
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn serve_refuses_keys_outside_storage() {
+    let (app, _directory) = photo_app(1024);
+
+    app.client
+        .get("/photo/..%2Fsqlite.db")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    app.client
+        .get(format!("/photo/{}.png", "0".repeat(64)))
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn photos_of_unknown_animals_are_not_found() {
+    let (app, _directory) = photo_app(1024);
+
+    app.client
+        .get("/animal/999/photos")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn photos_are_served_to_their_tenant_only() {
+    let tenant_directory = TempDir::new().expect("tenant directory");
+    let storage_directory = TempDir::new().expect("storage directory");
+    let config = Config {
+        tenant: Arc::new(TenantConfig {
+            enabled: true,
+            directory: tenant_directory.path().to_string_lossy().to_string(),
+            ..TenantConfig::default()
+        }),
+        storage: storage_config(&storage_directory, 1024 * 1024),
+        ..Config::default()
+    };
+    let app = TestApp::builder().config(config).build();
+    let admin_key = ApiKeyRepository::new(app.sqlite_client.clone())
+        .create_api_key("admin", None)
+        .expect("admin key");
+    for slug in ["zoo-a", "zoo-b"] {
+        app.client
+            .post("/admin/tenant/add")
+            .header("X-Api-Key", &admin_key)
+            .body_json(&json!({"slug": slug, "name": slug}))
+            .send()
+            .await
+            .assert_status(StatusCode::CREATED);
+        app.client
+            .post("/animal/add")
+            .header("X-Tenant", slug)
+            .body_json(&json!({"species": "otter", "description": "Floats on its back"}))
+            .send()
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let res = app
+        .client
+        .post("/animal/1/photos")
+        .header("X-Tenant", "zoo-a")
+        .multipart(upload_form(png(8, 8)))
+        .send()
+        .await;
+    res.assert_status(StatusCode::CREATED);
+    let url = res
+        .json()
+        .await
+        .value()
+        .object()
+        .get("url")
+        .string()
+        .to_string();
+
+    app.client
+        .get(&url)
+        .header("X-Tenant", "zoo-a")
+        .send()
+        .await
+        .assert_status_is_ok();
+    app.client
+        .get(&url)
+        .header("X-Tenant", "zoo-b")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+fn stored_files(directory: &TempDir) -> usize {
+    fn count(path: &std::path::Path) -> usize {
+        std::fs::read_dir(path)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| match entry.path().is_dir() {
+                        true => count(&entry.path()),
+                        false => 1,
+                    })
+                    .sum()
+            })
+            .unwrap_or(0)
+    }
+    count(directory.path())
+}
+
+#[tokio::test]
+async fn deleting_animals_removes_the_files_no_photo_uses() {
+    let (app, directory) = photo_app(1024 * 1024);
+    for id in [1, 2] {
+        app.client
+            .post(format!("/animal/{}/photos", id))
+            .multipart(upload_form(png(32, 24)))
+            .send()
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+    // The photo, its 16px thumbnail, and the photo again as its own 64px thumbnail.
+    assert_eq!(stored_files(&directory), 2);
+
+    let delete = |id| {
+        app.client
+            .post("/animal/batch")
+            .body_json(&json!({"mode": "atomic", "operations": [{"op": "delete", "id": id}]}))
+            .send()
+    };
+    delete(1).await.assert_status_is_ok();
+    assert_eq!(stored_files(&directory), 2);
+    delete(2).await.assert_status_is_ok();
+    assert_eq!(stored_files(&directory), 0);
+}
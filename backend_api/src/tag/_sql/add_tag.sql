@@ -0,0 +1,2 @@
+INSERT INTO tag (name)
+VALUES (:name)
\ No newline at end of file
@@ -0,0 +1,2 @@
+INSERT OR IGNORE INTO animal_tag (animal_id, tag_id)
+VALUES (:animal_id, :tag_id)
\ No newline at end of file
@@ -0,0 +1,3 @@
+DELETE
+FROM tag
+WHERE id = :id
\ No newline at end of file
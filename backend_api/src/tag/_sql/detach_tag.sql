@@ -0,0 +1,4 @@
+DELETE
+FROM animal_tag
+WHERE animal_id = :animal_id
+  AND tag_id = :tag_id
\ No newline at end of file
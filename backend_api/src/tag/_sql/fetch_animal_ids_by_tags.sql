@@ -0,0 +1,6 @@
+SELECT animal_tag.animal_id
+FROM animal_tag
+         JOIN tag ON tag.id = animal_tag.tag_id
+WHERE tag.name IN (SELECT value FROM json_each(:names))
+GROUP BY animal_tag.animal_id
+HAVING COUNT(DISTINCT tag.id) >= :required
\ No newline at end of file
@@ -0,0 +1,3 @@
+SELECT id, name
+FROM tag
+WHERE id = :id
\ No newline at end of file
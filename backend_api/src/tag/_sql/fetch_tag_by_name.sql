@@ -0,0 +1,3 @@
+SELECT id, name
+FROM tag
+WHERE name = :name
\ No newline at end of file
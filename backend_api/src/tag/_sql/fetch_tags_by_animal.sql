@@ -0,0 +1,5 @@
+SELECT tag.id, tag.name
+FROM tag
+         JOIN animal_tag ON animal_tag.tag_id = tag.id
+WHERE animal_tag.animal_id = :animal_id
+ORDER BY tag.name
\ No newline at end of file
@@ -0,0 +1,4 @@
+INSERT OR IGNORE INTO animal_tag (animal_id, tag_id)
+SELECT animal_id, :into_id
+FROM animal_tag
+WHERE tag_id = :id
\ No newline at end of file
@@ -0,0 +1,5 @@
+SELECT id, name
+FROM tag
+WHERE name LIKE :prefix || '%' ESCAPE '\'
+ORDER BY name
+LIMIT :limit
\ No newline at end of file
@@ -0,0 +1,181 @@
+pub mod object;
+pub mod repository;
+pub mod response;
+#[cfg(test)]
+mod tests;
+
+use crate::ApiTag;
+use crate::animal::repository::DynAnimalRepository;
+use crate::common::context::Dep;
+use crate::common::results::unified;
+use crate::tag::object::{TagMergeObject, TagNameObject};
+use crate::tag::repository::{TagRepository, TagRepositoryError};
+use crate::tag::response::{
+    AddTagResponse, AnimalTagsResponse, MergeTagResponse, RenameTagResponse, SearchTagsResponse,
+};
+use poem::i18n::Locale;
+use poem_openapi::OpenApi;
+use poem_openapi::param::{Path, Query};
+use poem_openapi::payload::Json;
+
+/// Enough suggestions for an autocomplete list.
+const SEARCH_LIMIT: u32 = 20;
+
+pub struct TagApi;
+
+#[OpenApi(tag = "ApiTag::Tag")]
+impl TagApi {
+    /// Search Tags by the start of their name, for autocomplete
+    #[oai(path = "/tag", method = "get")]
+    async fn search(
+        &self,
+        #[oai(name = "q")] Query(prefix): Query<Option<String>>,
+        Dep(tag_repository): Dep<TagRepository>,
+    ) -> SearchTagsResponse {
+        unified(async {
+            tag_repository
+                .search_tags(prefix.as_deref().unwrap_or_default().trim(), SEARCH_LIMIT)
+                .map(|tags| SearchTagsResponse::Ok(Json(tags.to_vec())))
+                .map_err(|_| SearchTagsResponse::InternalServerError)
+        })
+        .await
+    }
+
+    /// Add Tag
+    #[oai(path = "/tag/add", method = "post")]
+    async fn add(
+        &self,
+        Json(tag): Json<TagNameObject>,
+        Dep(tag_repository): Dep<TagRepository>,
+        locale: Locale,
+    ) -> AddTagResponse {
+        unified(async {
+            let name = tag.to_validate().map_err(|tag_error| {
+                AddTagResponse::UnprocessableEntity(Json((tag_error, &locale).into()))
+            })?;
+            tag_repository
+                .add_tag(name.as_str())
+                .map(|tag| AddTagResponse::Created(Json(tag)))
+                .map_err(|err| match err.current_context() {
+                    TagRepositoryError::NameTakenError => AddTagResponse::Conflict,
+                    _ => AddTagResponse::InternalServerError,
+                })
+        })
+        .await
+    }
+
+    /// Rename Tag
+    #[oai(path = "/tag/rename/:id", method = "patch")]
+    async fn rename(
+        &self,
+        Path(id): Path<u64>,
+        Json(tag): Json<TagNameObject>,
+        Dep(tag_repository): Dep<TagRepository>,
+        locale: Locale,
+    ) -> RenameTagResponse {
+        unified(async {
+            let name = tag.to_validate().map_err(|tag_error| {
+                RenameTagResponse::UnprocessableEntity(Json((tag_error, &locale).into()))
+            })?;
+            tag_repository
+                .rename_tag(id as i64, name.as_str())
+                .map(|tag| RenameTagResponse::Ok(Json(tag)))
+                .map_err(|err| match err.current_context() {
+                    TagRepositoryError::NotFoundError => RenameTagResponse::NotFound,
+                    TagRepositoryError::NameTakenError => RenameTagResponse::Conflict,
+                    _ => RenameTagResponse::InternalServerError,
+                })
+        })
+        .await
+    }
+
+    /// Merge Tag into another, which takes over its animals
+    #[oai(path = "/tag/merge/:id", method = "post")]
+    async fn merge(
+        &self,
+        Path(id): Path<u64>,
+        Json(target): Json<TagMergeObject>,
+        Dep(tag_repository): Dep<TagRepository>,
+    ) -> MergeTagResponse {
+        unified(async {
+            tag_repository
+                .merge_tag(id as i64, target.into_id)
+                .map(|tag| MergeTagResponse::Ok(Json(tag)))
+                .map_err(|err| match err.current_context() {
+                    TagRepositoryError::NotFoundError => MergeTagResponse::NotFound,
+                    TagRepositoryError::MergeIntoItselfError => {
+                        MergeTagResponse::UnprocessableEntity
+                    }
+                    _ => MergeTagResponse::InternalServerError,
+                })
+        })
+        .await
+    }
+
+    /// Fetch the Tags of an Animal
+    #[oai(path = "/animal/:id/tags", method = "get")]
+    async fn animal_tags(
+        &self,
+        Path(id): Path<u64>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(tag_repository): Dep<TagRepository>,
+    ) -> AnimalTagsResponse {
+        unified(async {
+            animal_repository
+                .fetch_animal_by_id(id as i64)
+                .map_err(|_| AnimalTagsResponse::NotFound)?;
+            tag_repository
+                .fetch_tags_by_animal(id as i64)
+                .map(|tags| AnimalTagsResponse::Ok(Json(tags.to_vec())))
+                .map_err(|_| AnimalTagsResponse::InternalServerError)
+        })
+        .await
+    }
+
+    /// Attach a Tag to an Animal by name, adding the tag if it is new
+    #[oai(path = "/animal/:id/tags", method = "post")]
+    async fn attach(
+        &self,
+        Path(id): Path<u64>,
+        Json(tag): Json<TagNameObject>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(tag_repository): Dep<TagRepository>,
+        locale: Locale,
+    ) -> AnimalTagsResponse {
+        unified(async {
+            animal_repository
+                .fetch_animal_by_id(id as i64)
+                .map_err(|_| AnimalTagsResponse::NotFound)?;
+            let name = tag.to_validate().map_err(|tag_error| {
+                AnimalTagsResponse::UnprocessableEntity(Json((tag_error, &locale).into()))
+            })?;
+            tag_repository
+                .attach_tag_by_name(id as i64, name.as_str())
+                .and_then(|_| tag_repository.fetch_tags_by_animal(id as i64))
+                .map(|tags| AnimalTagsResponse::Ok(Json(tags.to_vec())))
+                .map_err(|_| AnimalTagsResponse::InternalServerError)
+        })
+        .await
+    }
+
+    /// Detach a Tag from an Animal, the tag itself is kept
+    #[oai(path = "/animal/:id/tags/:tag_id", method = "delete")]
+    async fn detach(
+        &self,
+        Path(id): Path<u64>,
+        Path(tag_id): Path<u64>,
+        Dep(tag_repository): Dep<TagRepository>,
+    ) -> AnimalTagsResponse {
+        unified(async {
+            tag_repository
+                .detach_tag(id as i64, tag_id as i64)
+                .and_then(|_| tag_repository.fetch_tags_by_animal(id as i64))
+                .map(|tags| AnimalTagsResponse::Ok(Json(tags.to_vec())))
+                .map_err(|err| match err.current_context() {
+                    TagRepositoryError::NotFoundError => AnimalTagsResponse::NotFound,
+                    _ => AnimalTagsResponse::InternalServerError,
+                })
+        })
+        .await
+    }
+}
@@ -0,0 +1,56 @@
+use crate::common::locale::LocaleForErrors;
+use crate::common::object::{ValidationErrorObject, field_messages};
+use poem::i18n::Locale;
+use poem_openapi::{Enum, Object};
+use shared::validation::types::tag_name::{TagName, TagNameError};
+
+#[derive(Debug, Object, Clone)]
+pub struct TagObject {
+    pub id: i64,
+    pub name: String,
+}
+
+#[derive(Debug, Object)]
+pub struct TagNameObject {
+    /// Unique regardless of case, surrounding whitespace is dropped.
+    pub name: String,
+}
+
+impl TagNameObject {
+    pub fn to_validate(&self) -> Result<TagName, TagNameError> {
+        TagName::parse(self.name.trim().to_string())
+    }
+}
+
+#[derive(Debug, Object)]
+pub struct TagMergeObject {
+    /// Tag that takes over the animals of the merged tag.
+    pub into_id: i64,
+}
+
+#[derive(Debug, Object)]
+pub struct TagErrorObject {
+    pub name: Vec<String>,
+    /// The same errors with their code and arguments.
+    pub errors: Vec<ValidationErrorObject>,
+}
+
+impl From<(TagNameError, &Locale)> for TagErrorObject {
+    fn from((error, locale): (TagNameError, &Locale)) -> Self {
+        let errors = error.0.as_validation_errors("name", Some(locale));
+        Self {
+            name: field_messages(&errors, "name"),
+            errors,
+        }
+    }
+}
+
+/// How the `tag` filter of `GET /animal` combines several tags.
+#[derive(Debug, Enum, Clone, Copy, PartialEq)]
+#[oai(rename_all = "snake_case")]
+pub enum TagMatch {
+    /// Animals carrying every tag.
+    All,
+    /// Animals carrying at least one of the tags.
+    Any,
+}
@@ -0,0 +1,307 @@
+use crate::common::context::{Context, ContextError, FromContext};
+use crate::common::db::{SqliteClient, TenantConnection};
+use crate::tag::object::{TagMatch, TagObject};
+use error_stack::{Report, ResultExt};
+use rusqlite::{Connection, ErrorCode, OptionalExtension, Row, named_params};
+use std::collections::HashSet;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TagRepositoryError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Row Value error")]
+    RowValueError,
+    #[error("Lock error")]
+    LockError,
+    #[error("Not found error")]
+    NotFoundError,
+    #[error("Name taken error")]
+    NameTakenError,
+    #[error("Merge into itself error")]
+    MergeIntoItselfError,
+}
+
+fn tag_from_row(row: &Row) -> rusqlite::Result<TagObject> {
+    Ok(TagObject {
+        id: row.get("id")?,
+        name: row.get("name")?,
+    })
+}
+
+fn fetch_tag(conn: &Connection, id: i64) -> Result<Option<TagObject>, Report<TagRepositoryError>> {
+    conn.query_row(
+        include_str!("_sql/fetch_tag_by_id.sql"),
+        named_params! {":id": id},
+        tag_from_row,
+    )
+    .optional()
+    .change_context(TagRepositoryError::QueryError)
+}
+
+fn map_write_error(err: rusqlite::Error) -> Report<TagRepositoryError> {
+    match err.sqlite_error_code() {
+        Some(ErrorCode::ConstraintViolation) => {
+            Report::new(err).change_context(TagRepositoryError::NameTakenError)
+        }
+        _ => Report::new(err).change_context(TagRepositoryError::QueryError),
+    }
+}
+
+/// Tags and the animals carrying them, kept next to the animals.
+pub struct TagRepository {
+    sqlite_client: SqliteClient<TenantConnection>,
+}
+
+impl TagRepository {
+    pub fn new(sqlite_client: SqliteClient<TenantConnection>) -> Self {
+        Self { sqlite_client }
+    }
+
+    fn query_tags(
+        conn: &Connection,
+        sql: &str,
+        params: &[(&str, &dyn rusqlite::ToSql)],
+    ) -> Result<Box<[TagObject]>, Report<TagRepositoryError>> {
+        let mut stmt = conn
+            .prepare(sql)
+            .change_context(TagRepositoryError::QueryError)?;
+        let item_iter = stmt
+            .query_map(params, tag_from_row)
+            .change_context(TagRepositoryError::QueryError)?;
+
+        let mut items = Vec::new();
+        for item in item_iter {
+            items.push(item.change_context(TagRepositoryError::RowValueError)?);
+        }
+
+        Ok(items.into())
+    }
+
+    /// Tags whose name starts with `prefix`, ignoring case, in name order.
+    pub fn search_tags(
+        &self,
+        prefix: &str,
+        limit: u32,
+    ) -> Result<Box<[TagObject]>, Report<TagRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TagRepositoryError::LockError)?;
+
+        let prefix = prefix
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        Self::query_tags(
+            &conn,
+            include_str!("_sql/search_tags.sql"),
+            named_params! {":prefix": prefix, ":limit": limit},
+        )
+    }
+
+    pub fn add_tag(&self, name: &str) -> Result<TagObject, Report<TagRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TagRepositoryError::LockError)?;
+
+        conn.execute(
+            include_str!("_sql/add_tag.sql"),
+            named_params! {":name": name},
+        )
+        .map_err(map_write_error)?;
+
+        Ok(TagObject {
+            id: conn.last_insert_rowid(),
+            name: name.to_string(),
+        })
+    }
+
+    pub fn rename_tag(&self, id: i64, name: &str) -> Result<TagObject, Report<TagRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TagRepositoryError::LockError)?;
+
+        let renamed = conn
+            .execute(
+                include_str!("_sql/rename_tag.sql"),
+                named_params! {":id": id, ":name": name},
+            )
+            .map_err(map_write_error)?;
+        if renamed == 0 {
+            return Err(TagRepositoryError::NotFoundError.into());
+        }
+
+        Ok(TagObject {
+            id,
+            name: name.to_string(),
+        })
+    }
+
+    /// Move every animal of tag `id` over to `into_id`, then delete tag `id`.
+    pub fn merge_tag(
+        &self,
+        id: i64,
+        into_id: i64,
+    ) -> Result<TagObject, Report<TagRepositoryError>> {
+        if id == into_id {
+            return Err(TagRepositoryError::MergeIntoItselfError.into());
+        }
+        let mut conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TagRepositoryError::LockError)?;
+        let tx = conn
+            .transaction()
+            .change_context(TagRepositoryError::QueryError)?;
+
+        fetch_tag(&tx, id)?.ok_or(TagRepositoryError::NotFoundError)?;
+        let into = fetch_tag(&tx, into_id)?.ok_or(TagRepositoryError::NotFoundError)?;
+        tx.execute(
+            include_str!("_sql/merge_tag.sql"),
+            named_params! {":id": id, ":into_id": into_id},
+        )
+        .change_context(TagRepositoryError::QueryError)?;
+        tx.execute(
+            include_str!("_sql/delete_tag.sql"),
+            named_params! {":id": id},
+        )
+        .change_context(TagRepositoryError::QueryError)?;
+        tx.commit().change_context(TagRepositoryError::QueryError)?;
+
+        Ok(into)
+    }
+
+    /// Attach the tag named `name`, creating it if no tag has that name yet.
+    pub fn attach_tag_by_name(
+        &self,
+        animal_id: i64,
+        name: &str,
+    ) -> Result<(), Report<TagRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TagRepositoryError::LockError)?;
+
+        let existing = conn
+            .query_row(
+                include_str!("_sql/fetch_tag_by_name.sql"),
+                named_params! {":name": name},
+                tag_from_row,
+            )
+            .optional()
+            .change_context(TagRepositoryError::QueryError)?;
+        let tag_id = match existing {
+            Some(tag) => tag.id,
+            None => {
+                conn.execute(
+                    include_str!("_sql/add_tag.sql"),
+                    named_params! {":name": name},
+                )
+                .map_err(map_write_error)?;
+                conn.last_insert_rowid()
+            }
+        };
+
+        conn.execute(
+            include_str!("_sql/attach_tag.sql"),
+            named_params! {":animal_id": animal_id, ":tag_id": tag_id},
+        )
+        .change_context(TagRepositoryError::QueryError)?;
+
+        Ok(())
+    }
+
+    pub fn detach_tag(
+        &self,
+        animal_id: i64,
+        tag_id: i64,
+    ) -> Result<(), Report<TagRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TagRepositoryError::LockError)?;
+
+        let detached = conn
+            .execute(
+                include_str!("_sql/detach_tag.sql"),
+                named_params! {":animal_id": animal_id, ":tag_id": tag_id},
+            )
+            .change_context(TagRepositoryError::QueryError)?;
+        if detached == 0 {
+            return Err(TagRepositoryError::NotFoundError.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn fetch_tags_by_animal(
+        &self,
+        animal_id: i64,
+    ) -> Result<Box<[TagObject]>, Report<TagRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TagRepositoryError::LockError)?;
+
+        Self::query_tags(
+            &conn,
+            include_str!("_sql/fetch_tags_by_animal.sql"),
+            named_params! {":animal_id": animal_id},
+        )
+    }
+
+    /// Ids of the animals carrying all, or any, of the tags named in `names`, ignoring case.
+    pub fn fetch_animal_ids_by_tags(
+        &self,
+        names: &[String],
+        tag_match: TagMatch,
+    ) -> Result<HashSet<i64>, Report<TagRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TagRepositoryError::LockError)?;
+
+        let distinct: HashSet<String> = names.iter().map(|name| name.to_lowercase()).collect();
+        let required = match tag_match {
+            TagMatch::All => distinct.len(),
+            TagMatch::Any => 1,
+        };
+        let names = serde_json::to_string(names).change_context(TagRepositoryError::QueryError)?;
+
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_animal_ids_by_tags.sql"))
+            .change_context(TagRepositoryError::QueryError)?;
+        let item_iter = stmt
+            .query_map(
+                named_params! {":names": names, ":required": required as i64},
+                |row| row.get::<_, i64>("animal_id"),
+            )
+            .change_context(TagRepositoryError::QueryError)?;
+
+        let mut ids = HashSet::new();
+        for item in item_iter {
+            ids.insert(item.change_context(TagRepositoryError::RowValueError)?);
+        }
+
+        Ok(ids)
+    }
+}
+
+impl FromContext for TagRepository {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(
+            SqliteClient::<TenantConnection>::from_context(ctx).await?,
+        ))
+    }
+}
@@ -0,0 +1,61 @@
+use crate::tag::object::{TagErrorObject, TagObject};
+use poem_openapi::ApiResponse;
+use poem_openapi::payload::Json;
+
+#[derive(ApiResponse)]
+pub enum SearchTagsResponse {
+    #[oai(status = 200)]
+    Ok(Json<Vec<TagObject>>),
+    #[oai(status = 500)]
+    InternalServerError,
+}
+
+#[derive(ApiResponse)]
+pub enum AddTagResponse {
+    #[oai(status = 201)]
+    Created(Json<TagObject>),
+    #[oai(status = 422)]
+    UnprocessableEntity(Json<TagErrorObject>),
+    #[oai(status = 409)]
+    Conflict,
+    #[oai(status = 500)]
+    InternalServerError,
+}
+
+#[derive(ApiResponse)]
+pub enum RenameTagResponse {
+    #[oai(status = 200)]
+    Ok(Json<TagObject>),
+    #[oai(status = 404)]
+    NotFound,
+    #[oai(status = 422)]
+    UnprocessableEntity(Json<TagErrorObject>),
+    #[oai(status = 409)]
+    Conflict,
+    #[oai(status = 500)]
+    InternalServerError,
+}
+
+#[derive(ApiResponse)]
+pub enum MergeTagResponse {
+    #[oai(status = 200)]
+    Ok(Json<TagObject>),
+    #[oai(status = 404)]
+    NotFound,
+    #[oai(status = 422)]
+    UnprocessableEntity,
+    #[oai(status = 500)]
+    InternalServerError,
+}
+
+#[derive(ApiResponse)]
+pub enum AnimalTagsResponse {
+    #[oai(status = 200)]
+    Ok(Json<Vec<TagObject>>),
+    #[oai(status = 404)]
+    NotFound,
+    #[oai(status = 422)]
+    UnprocessableEntity(Json<TagErrorObject>),
+    #[oai(status = 500)]
+    InternalServerError,
+}
@@ -0,0 +1,222 @@
+use crate::test_support::TestApp;
+use poem::http::StatusCode;
+use serde_json::json;
+
+const DOG: i64 = 1;
+const CAT: i64 = 2;
+const LION: i64 = 3;
+
+async fn attach(app: &TestApp, animal_id: i64, name: &str) -> i64 {
+    let res = app
+        .client
+        .post(format!("/animal/{}/tags", animal_id))
+        .body_json(&json!({"name": name}))
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    let json = res.json().await;
+    json.value()
+        .array()
+        .iter()
+        .find(|tag| tag.object().get("name").string().eq_ignore_ascii_case(name))
+        .expect("attached tag is listed")
+        .object()
+        .get("id")
+        .i64()
+}
+
+async fn species_tagged(app: &TestApp, query: &str) -> Vec<String> {
+    let res = app.client.get(format!("/animal?{}", query)).send().await;
+    res.assert_status_is_ok();
+    let json = res.json().await;
+    json.value()
+        .array()
+        .iter()
+        .map(|animal| animal.object().get("species").string().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn add_validates_and_rejects_duplicates_regardless_of_case() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .post("/tag/add")
+        .body_json(&json!({"name": "Savannah"}))
+        .send()
+        .await;
+    res.assert_status(StatusCode::CREATED);
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("name")
+        .assert_string("Savannah");
+
+    app.client
+        .post("/tag/add")
+        .body_json(&json!({"name": "savannah"}))
+        .send()
+        .await
+        .assert_status(StatusCode::CONFLICT);
+    app.client
+        .post("/tag/add")
+        .body_json(&json!({"name": "  "}))
+        .send()
+        .await
+        .assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    app.client
+        .post("/tag/add")
+        .body_json(&json!({"name": "x".repeat(31)}))
+        .send()
+        .await
+        .assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn search_matches_the_start_of_the_name() {
+    let app = TestApp::builder().build();
+    attach(&app, DOG, "carnivore").await;
+    attach(&app, CAT, "cat house").await;
+    attach(&app, LION, "100%_wild").await;
+
+    let res = app.client.get("/tag?q=CA").send().await;
+    res.assert_status_is_ok();
+    let json = res.json().await;
+    let tags = json.value().array();
+    tags.assert_len(2);
+    tags.get(0).object().get("name").assert_string("carnivore");
+
+    // LIKE wildcards in the prefix are matched literally.
+    let res = app.client.get("/tag?q=_").send().await;
+    res.json().await.value().array().assert_len(0);
+    let res = app.client.get("/tag?q=100%25_").send().await;
+    res.json().await.value().array().assert_len(1);
+}
+
+#[tokio::test]
+async fn rename_keeps_the_animals() {
+    let app = TestApp::builder().build();
+    let id = attach(&app, DOG, "kennel").await;
+    attach(&app, CAT, "indoor").await;
+
+    let res = app
+        .client
+        .patch(format!("/tag/rename/{}", id))
+        .body_json(&json!({"name": "Paddock"}))
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    assert_eq!(species_tagged(&app, "tag=paddock").await, vec!["dog"]);
+
+    app.client
+        .patch(format!("/tag/rename/{}", id))
+        .body_json(&json!({"name": "Indoor"}))
+        .send()
+        .await
+        .assert_status(StatusCode::CONFLICT);
+    app.client
+        .patch("/tag/rename/999")
+        .body_json(&json!({"name": "nowhere"}))
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn merge_moves_the_animals_and_drops_the_tag() {
+    let app = TestApp::builder().build();
+    let meat = attach(&app, DOG, "meat").await;
+    attach(&app, LION, "meat").await;
+    let carnivore = attach(&app, LION, "carnivore").await;
+
+    let res = app
+        .client
+        .post(format!("/tag/merge/{}", meat))
+        .body_json(&json!({"into_id": carnivore}))
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    assert_eq!(
+        species_tagged(&app, "tag=carnivore").await,
+        vec!["dog", "lion"]
+    );
+    app.client
+        .get("/tag?q=meat")
+        .send()
+        .await
+        .json()
+        .await
+        .value()
+        .array()
+        .assert_len(0);
+
+    app.client
+        .post(format!("/tag/merge/{}", carnivore))
+        .body_json(&json!({"into_id": carnivore}))
+        .send()
+        .await
+        .assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    app.client
+        .post(format!("/tag/merge/{}", meat))
+        .body_json(&json!({"into_id": carnivore}))
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn attach_and_detach() {
+    let app = TestApp::builder().build();
+
+    app.client
+        .post("/animal/999/tags")
+        .body_json(&json!({"name": "ghost"}))
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+
+    let id = attach(&app, CAT, "indoor").await;
+    // Attaching twice, in any case, keeps a single tag.
+    assert_eq!(attach(&app, CAT, "INDOOR").await, id);
+    let res = app.client.get(format!("/animal/{}/tags", CAT)).send().await;
+    res.assert_status_is_ok();
+    res.assert_json(json!([{"id": id, "name": "indoor"}])).await;
+
+    let res = app
+        .client
+        .delete(format!("/animal/{}/tags/{}", CAT, id))
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    res.assert_json(json!([])).await;
+    app.client
+        .delete(format!("/animal/{}/tags/{}", CAT, id))
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn index_filters_by_all_or_any_tag() {
+    let app = TestApp::builder().build();
+    attach(&app, DOG, "carnivore").await;
+    attach(&app, CAT, "carnivore").await;
+    attach(&app, CAT, "indoor").await;
+    attach(&app, LION, "savannah").await;
+
+    assert_eq!(
+        species_tagged(&app, "tag=carnivore&tag=indoor").await,
+        vec!["cat"]
+    );
+    assert_eq!(
+        species_tagged(&app, "tag=indoor&tag=savannah&tag_match=any").await,
+        vec!["cat", "lion"]
+    );
+    assert_eq!(
+        species_tagged(&app, "tag=Carnivore&tag=carnivore").await,
+        vec!["dog", "cat"]
+    );
+    assert!(species_tagged(&app, "tag=unknown").await.is_empty());
+}
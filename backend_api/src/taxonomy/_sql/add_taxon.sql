@@ -0,0 +1,2 @@
+INSERT INTO taxon (parent_id, rank, name)
+VALUES (:parent_id, :rank, :name)
\ No newline at end of file
@@ -0,0 +1,9 @@
+WITH RECURSIVE subtree(id) AS (SELECT id
+                               FROM taxon
+                               WHERE id = :id
+                               UNION ALL
+                               SELECT taxon.id
+                               FROM taxon
+                                        JOIN subtree ON taxon.parent_id = subtree.id)
+SELECT id
+FROM subtree
\ No newline at end of file
@@ -0,0 +1,3 @@
+SELECT id, parent_id, rank, name
+FROM taxon
+WHERE id = :id
\ No newline at end of file
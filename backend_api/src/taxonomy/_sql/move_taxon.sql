@@ -0,0 +1,3 @@
+UPDATE taxon
+SET parent_id = :parent_id
+WHERE id = :id
\ No newline at end of file
@@ -0,0 +1,146 @@
+pub mod object;
+pub mod repository;
+pub mod response;
+#[cfg(test)]
+mod tests;
+
+use crate::ApiTag;
+use crate::animal::repository::DynAnimalRepository;
+use crate::common::context::Dep;
+use crate::common::object::Message;
+use crate::common::results::unified;
+use crate::taxonomy::object::{TaxonAddObject, TaxonMoveObject};
+use crate::taxonomy::repository::{TaxonRepository, TaxonRepositoryError};
+use crate::taxonomy::response::{
+    AddTaxonResponse, FetchAllTaxaResponse, FetchTaxonAnimalsResponse, FetchTaxonByIdResponse,
+    MoveTaxonResponse,
+};
+use poem_openapi::OpenApi;
+use poem_openapi::param::Path;
+use poem_openapi::payload::Json;
+
+fn rank_message() -> Message {
+    Message {
+        message: "Kingdoms must be roots, other ranks must sit under a broader rank".to_string(),
+    }
+}
+
+fn parent_message() -> Message {
+    Message {
+        message: "Parent taxon does not exist".to_string(),
+    }
+}
+
+pub struct TaxonomyApi;
+
+#[OpenApi(prefix_path = "/taxonomy", tag = "ApiTag::Taxonomy")]
+impl TaxonomyApi {
+    /// Fetch All Taxa, linked to their parent to form the tree
+    #[oai(path = "/", method = "get")]
+    async fn index(&self, Dep(taxon_repository): Dep<TaxonRepository>) -> FetchAllTaxaResponse {
+        unified(async {
+            taxon_repository
+                .fetch_all_taxa()
+                .map(|taxa| FetchAllTaxaResponse::Ok(Json(taxa.to_vec())))
+                .map_err(|_| FetchAllTaxaResponse::InternalServerError)
+        })
+        .await
+    }
+
+    /// Fetch Taxon By ID
+    #[oai(path = "/fetch/:id", method = "get")]
+    async fn fetch_by_id(
+        &self,
+        Path(id): Path<u64>,
+        Dep(taxon_repository): Dep<TaxonRepository>,
+    ) -> FetchTaxonByIdResponse {
+        unified(async {
+            taxon_repository
+                .fetch_taxon_by_id(id as i64)
+                .map(|taxon| FetchTaxonByIdResponse::Ok(Json(taxon)))
+                .map_err(|_| FetchTaxonByIdResponse::NotFound)
+        })
+        .await
+    }
+
+    /// Fetch the Animals classified under the Taxon or anywhere below it
+    #[oai(path = "/animals/:id", method = "get")]
+    async fn animals(
+        &self,
+        Path(id): Path<u64>,
+        Dep(taxon_repository): Dep<TaxonRepository>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+    ) -> FetchTaxonAnimalsResponse {
+        unified(async {
+            let taxon_ids =
+                taxon_repository
+                    .fetch_subtree_ids(id as i64)
+                    .map_err(|err| match err.current_context() {
+                        TaxonRepositoryError::NotFoundError => FetchTaxonAnimalsResponse::NotFound,
+                        _ => FetchTaxonAnimalsResponse::InternalServerError,
+                    })?;
+            animal_repository
+                .fetch_animals_by_taxa(&taxon_ids)
+                .map(|animals| FetchTaxonAnimalsResponse::Ok(Json(animals.to_vec())))
+                .map_err(|_| FetchTaxonAnimalsResponse::InternalServerError)
+        })
+        .await
+    }
+
+    /// Add Taxon
+    #[oai(path = "/add", method = "post")]
+    async fn add(
+        &self,
+        Json(taxon): Json<TaxonAddObject>,
+        Dep(taxon_repository): Dep<TaxonRepository>,
+    ) -> AddTaxonResponse {
+        unified(async {
+            if taxon.name.trim().is_empty() {
+                return Err(AddTaxonResponse::UnprocessableEntity(Json(Message {
+                    message: "Name cannot be empty".to_string(),
+                })));
+            }
+            taxon_repository
+                .add_taxon(&taxon)
+                .map(|taxon| AddTaxonResponse::Created(Json(taxon)))
+                .map_err(|err| match err.current_context() {
+                    TaxonRepositoryError::ParentNotFoundError => {
+                        AddTaxonResponse::UnprocessableEntity(Json(parent_message()))
+                    }
+                    TaxonRepositoryError::InvalidRankError => {
+                        AddTaxonResponse::UnprocessableEntity(Json(rank_message()))
+                    }
+                    TaxonRepositoryError::NameTakenError => AddTaxonResponse::Conflict,
+                    _ => AddTaxonResponse::InternalServerError,
+                })
+        })
+        .await
+    }
+
+    /// Move Taxon, along with everything below it, under another parent
+    #[oai(path = "/move/:id", method = "patch")]
+    async fn move_taxon(
+        &self,
+        Path(id): Path<u64>,
+        Json(target): Json<TaxonMoveObject>,
+        Dep(taxon_repository): Dep<TaxonRepository>,
+    ) -> MoveTaxonResponse {
+        unified(async {
+            taxon_repository
+                .move_taxon(id as i64, target.parent_id)
+                .map(|taxon| MoveTaxonResponse::Ok(Json(taxon)))
+                .map_err(|err| match err.current_context() {
+                    TaxonRepositoryError::NotFoundError => MoveTaxonResponse::NotFound,
+                    TaxonRepositoryError::ParentNotFoundError => {
+                        MoveTaxonResponse::UnprocessableEntity(Json(parent_message()))
+                    }
+                    TaxonRepositoryError::InvalidRankError => {
+                        MoveTaxonResponse::UnprocessableEntity(Json(rank_message()))
+                    }
+                    TaxonRepositoryError::NameTakenError => MoveTaxonResponse::Conflict,
+                    _ => MoveTaxonResponse::InternalServerError,
+                })
+        })
+        .await
+    }
+}
@@ -0,0 +1,87 @@
+use poem_openapi::{Enum, Object};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+/// Linnaean ranks, from the broadest to the narrowest.
+#[derive(Debug, Enum, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[oai(rename_all = "snake_case")]
+pub enum TaxonRank {
+    Kingdom,
+    Phylum,
+    Class,
+    Order,
+    Family,
+    Genus,
+    Species,
+}
+
+impl TaxonRank {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Kingdom => "kingdom",
+            Self::Phylum => "phylum",
+            Self::Class => "class",
+            Self::Order => "order",
+            Self::Family => "family",
+            Self::Genus => "genus",
+            Self::Species => "species",
+        }
+    }
+
+    fn from_str(rank: &str) -> Option<Self> {
+        match rank {
+            "kingdom" => Some(Self::Kingdom),
+            "phylum" => Some(Self::Phylum),
+            "class" => Some(Self::Class),
+            "order" => Some(Self::Order),
+            "family" => Some(Self::Family),
+            "genus" => Some(Self::Genus),
+            "species" => Some(Self::Species),
+            _ => None,
+        }
+    }
+
+    /// Kingdoms are the roots, any other rank sits under a strictly broader one; ranks in
+    /// between may be skipped.
+    ///
+    /// As ranks narrow along every path, a node can never end up under its own subtree.
+    pub fn can_be_child_of(self, parent: Option<TaxonRank>) -> bool {
+        match parent {
+            None => self == Self::Kingdom,
+            Some(parent) => parent < self,
+        }
+    }
+}
+
+impl ToSql for TaxonRank {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(self.as_str().into())
+    }
+}
+
+impl FromSql for TaxonRank {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        Self::from_str(value.as_str()?).ok_or(FromSqlError::InvalidType)
+    }
+}
+
+#[derive(Debug, Object, Clone)]
+pub struct TaxonObject {
+    pub id: i64,
+    /// Absent for kingdoms, the roots of the tree.
+    pub parent_id: Option<i64>,
+    pub rank: TaxonRank,
+    pub name: String,
+}
+
+#[derive(Debug, Object)]
+pub struct TaxonAddObject {
+    pub parent_id: Option<i64>,
+    pub rank: TaxonRank,
+    pub name: String,
+}
+
+#[derive(Debug, Object)]
+pub struct TaxonMoveObject {
+    /// New parent, absent to make the taxon a root.
+    pub parent_id: Option<i64>,
+}
@@ -0,0 +1,209 @@
+use crate::common::context::{Context, ContextError, FromContext};
+use crate::common::db::{SqliteClient, TenantConnection};
+use crate::taxonomy::object::{TaxonAddObject, TaxonObject};
+use error_stack::{Report, ResultExt};
+use rusqlite::{Connection, ErrorCode, OptionalExtension, Row, named_params};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TaxonRepositoryError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Row Value error")]
+    RowValueError,
+    #[error("Lock error")]
+    LockError,
+    #[error("Not found error")]
+    NotFoundError,
+    #[error("Parent not found error")]
+    ParentNotFoundError,
+    #[error("Invalid rank error")]
+    InvalidRankError,
+    #[error("Name taken error")]
+    NameTakenError,
+}
+
+fn taxon_from_row(row: &Row) -> rusqlite::Result<TaxonObject> {
+    Ok(TaxonObject {
+        id: row.get("id")?,
+        parent_id: row.get("parent_id")?,
+        rank: row.get("rank")?,
+        name: row.get("name")?,
+    })
+}
+
+fn fetch_taxon(
+    conn: &Connection,
+    id: i64,
+) -> Result<Option<TaxonObject>, Report<TaxonRepositoryError>> {
+    conn.query_row(
+        include_str!("_sql/fetch_taxon_by_id.sql"),
+        named_params! {":id": id},
+        taxon_from_row,
+    )
+    .optional()
+    .change_context(TaxonRepositoryError::QueryError)
+}
+
+fn map_write_error(err: rusqlite::Error) -> Report<TaxonRepositoryError> {
+    match err.sqlite_error_code() {
+        Some(ErrorCode::ConstraintViolation) => {
+            Report::new(err).change_context(TaxonRepositoryError::NameTakenError)
+        }
+        _ => Report::new(err).change_context(TaxonRepositoryError::QueryError),
+    }
+}
+
+/// The classification tree, kept next to the animals it classifies.
+pub struct TaxonRepository {
+    sqlite_client: SqliteClient<TenantConnection>,
+}
+
+impl TaxonRepository {
+    pub fn new(sqlite_client: SqliteClient<TenantConnection>) -> Self {
+        Self { sqlite_client }
+    }
+
+    pub fn fetch_all_taxa(&self) -> Result<Box<[TaxonObject]>, Report<TaxonRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TaxonRepositoryError::LockError)?;
+
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_all_taxa.sql"))
+            .change_context(TaxonRepositoryError::QueryError)?;
+        let item_iter = stmt
+            .query_map([], taxon_from_row)
+            .change_context(TaxonRepositoryError::QueryError)?;
+
+        let mut items = Vec::new();
+        for item in item_iter {
+            items.push(item.change_context(TaxonRepositoryError::RowValueError)?);
+        }
+
+        Ok(items.into())
+    }
+
+    pub fn fetch_taxon_by_id(&self, id: i64) -> Result<TaxonObject, Report<TaxonRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TaxonRepositoryError::LockError)?;
+
+        Ok(fetch_taxon(&conn, id)?.ok_or(TaxonRepositoryError::NotFoundError)?)
+    }
+
+    /// Id of the taxon and of every taxon below it.
+    pub fn fetch_subtree_ids(&self, id: i64) -> Result<Vec<i64>, Report<TaxonRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TaxonRepositoryError::LockError)?;
+
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_subtree_ids.sql"))
+            .change_context(TaxonRepositoryError::QueryError)?;
+        let item_iter = stmt
+            .query_map(named_params! {":id": id}, |row| row.get::<_, i64>("id"))
+            .change_context(TaxonRepositoryError::QueryError)?;
+
+        let mut ids = Vec::new();
+        for item in item_iter {
+            ids.push(item.change_context(TaxonRepositoryError::RowValueError)?);
+        }
+        if ids.is_empty() {
+            return Err(TaxonRepositoryError::NotFoundError.into());
+        }
+
+        Ok(ids)
+    }
+
+    pub fn add_taxon(
+        &self,
+        object: &TaxonAddObject,
+    ) -> Result<TaxonObject, Report<TaxonRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TaxonRepositoryError::LockError)?;
+
+        let parent_rank = match object.parent_id {
+            Some(parent_id) => Some(
+                fetch_taxon(&conn, parent_id)?
+                    .ok_or(TaxonRepositoryError::ParentNotFoundError)?
+                    .rank,
+            ),
+            None => None,
+        };
+        if !object.rank.can_be_child_of(parent_rank) {
+            return Err(TaxonRepositoryError::InvalidRankError.into());
+        }
+
+        conn.execute(
+            include_str!("_sql/add_taxon.sql"),
+            named_params! {
+                ":parent_id": object.parent_id,
+                ":rank": object.rank,
+                ":name": object.name,
+            },
+        )
+        .map_err(map_write_error)?;
+
+        Ok(TaxonObject {
+            id: conn.last_insert_rowid(),
+            parent_id: object.parent_id,
+            rank: object.rank,
+            name: object.name.clone(),
+        })
+    }
+
+    /// Move the taxon, with everything below it, under `parent_id`.
+    pub fn move_taxon(
+        &self,
+        id: i64,
+        parent_id: Option<i64>,
+    ) -> Result<TaxonObject, Report<TaxonRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TaxonRepositoryError::LockError)?;
+
+        let taxon = fetch_taxon(&conn, id)?.ok_or(TaxonRepositoryError::NotFoundError)?;
+        let parent_rank = match parent_id {
+            Some(parent_id) => Some(
+                fetch_taxon(&conn, parent_id)?
+                    .ok_or(TaxonRepositoryError::ParentNotFoundError)?
+                    .rank,
+            ),
+            None => None,
+        };
+        if !taxon.rank.can_be_child_of(parent_rank) {
+            return Err(TaxonRepositoryError::InvalidRankError.into());
+        }
+
+        conn.execute(
+            include_str!("_sql/move_taxon.sql"),
+            named_params! {
+                ":id": id,
+                ":parent_id": parent_id,
+            },
+        )
+        .map_err(map_write_error)?;
+
+        Ok(TaxonObject { parent_id, ..taxon })
+    }
+}
+
+impl FromContext for TaxonRepository {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(
+            SqliteClient::<TenantConnection>::from_context(ctx).await?,
+        ))
+    }
+}
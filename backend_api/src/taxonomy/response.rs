@@ -0,0 +1,57 @@
+use crate::animal::object::AnimalObject;
+use crate::common::object::Message;
+use crate::taxonomy::object::TaxonObject;
+use poem_openapi::ApiResponse;
+use poem_openapi::payload::Json;
+
+#[derive(ApiResponse)]
+pub enum FetchAllTaxaResponse {
+    #[oai(status = 200)]
+    Ok(Json<Vec<TaxonObject>>),
+    #[oai(status = 500)]
+    InternalServerError,
+}
+
+#[derive(ApiResponse)]
+pub enum FetchTaxonByIdResponse {
+    #[oai(status = 200)]
+    Ok(Json<TaxonObject>),
+    #[oai(status = 404)]
+    NotFound,
+}
+
+#[derive(ApiResponse)]
+pub enum FetchTaxonAnimalsResponse {
+    #[oai(status = 200)]
+    Ok(Json<Vec<AnimalObject>>),
+    #[oai(status = 404)]
+    NotFound,
+    #[oai(status = 500)]
+    InternalServerError,
+}
+
+#[derive(ApiResponse)]
+pub enum AddTaxonResponse {
+    #[oai(status = 201)]
+    Created(Json<TaxonObject>),
+    #[oai(status = 422)]
+    UnprocessableEntity(Json<Message>),
+    #[oai(status = 409)]
+    Conflict,
+    #[oai(status = 500)]
+    InternalServerError,
+}
+
+#[derive(ApiResponse)]
+pub enum MoveTaxonResponse {
+    #[oai(status = 200)]
+    Ok(Json<TaxonObject>),
+    #[oai(status = 404)]
+    NotFound,
+    #[oai(status = 422)]
+    UnprocessableEntity(Json<Message>),
+    #[oai(status = 409)]
+    Conflict,
+    #[oai(status = 500)]
+    InternalServerError,
+}
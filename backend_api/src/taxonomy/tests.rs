@@ -0,0 +1,123 @@
+use crate::test_support::TestApp;
+use poem::http::StatusCode;
+use serde_json::json;
+
+const FELIDAE: i64 = 5;
+const FELIS: i64 = 9;
+const CANIDAE: i64 = 11;
+const PANTHERA_LEO: i64 = 7;
+
+async fn species_under(app: &TestApp, taxon_id: i64) -> Vec<String> {
+    let res = app
+        .client
+        .get(format!("/taxonomy/animals/{}", taxon_id))
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    let json = res.json().await;
+    json.value()
+        .array()
+        .iter()
+        .map(|animal| animal.object().get("species").string().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn animals_under_a_node_include_its_whole_subtree() {
+    let app = TestApp::builder().build();
+
+    assert_eq!(
+        species_under(&app, FELIDAE).await,
+        vec!["cat", "lion", "tiger"]
+    );
+    assert_eq!(species_under(&app, PANTHERA_LEO).await, vec!["lion"]);
+
+    app.client
+        .get("/taxonomy/animals/999")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn add_enforces_rank_order_and_unique_names() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .post("/taxonomy/add")
+        .body_json(&json!({"parent_id": FELIS, "rank": "species", "name": "Felis silvestris"}))
+        .send()
+        .await;
+    res.assert_status(StatusCode::CREATED);
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("parent_id")
+        .assert_i64(FELIS);
+
+    app.client
+        .post("/taxonomy/add")
+        .body_json(&json!({"parent_id": FELIS, "rank": "family", "name": "Felidae"}))
+        .send()
+        .await
+        .assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    app.client
+        .post("/taxonomy/add")
+        .body_json(&json!({"rank": "genus", "name": "Rootless"}))
+        .send()
+        .await
+        .assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    app.client
+        .post("/taxonomy/add")
+        .body_json(&json!({"parent_id": FELIS, "rank": "species", "name": "Felis catus"}))
+        .send()
+        .await
+        .assert_status(StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn move_carries_the_subtree_along() {
+    let app = TestApp::builder().build();
+
+    app.client
+        .patch(format!("/taxonomy/move/{}", FELIS))
+        .body_json(&json!({"parent_id": CANIDAE}))
+        .send()
+        .await
+        .assert_status_is_ok();
+    assert_eq!(species_under(&app, FELIDAE).await, vec!["lion", "tiger"]);
+    assert_eq!(species_under(&app, CANIDAE).await, vec!["dog", "cat"]);
+
+    // A family cannot go under one of its own genera.
+    app.client
+        .patch(format!("/taxonomy/move/{}", FELIDAE))
+        .body_json(&json!({"parent_id": 6}))
+        .send()
+        .await
+        .assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn animals_must_reference_an_existing_taxon() {
+    let app = TestApp::builder().build();
+
+    app.client
+        .post("/animal/add")
+        .body_json(&json!({"species": "ocelot", "description": "Spotted", "taxon_id": 999}))
+        .send()
+        .await
+        .assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+
+    app.client
+        .post("/animal/add")
+        .body_json(&json!({"species": "ocelot", "description": "Spotted", "taxon_id": FELIDAE}))
+        .send()
+        .await
+        .assert_status(StatusCode::CREATED);
+    assert_eq!(
+        species_under(&app, FELIDAE).await,
+        vec!["cat", "lion", "tiger", "ocelot"]
+    );
+}
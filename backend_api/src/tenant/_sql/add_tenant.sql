@@ -0,0 +1,2 @@
+INSERT INTO tenant (slug, name, created_at)
+VALUES (:slug, :name, :created_at)
\ No newline at end of file
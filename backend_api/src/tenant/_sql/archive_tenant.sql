@@ -0,0 +1,4 @@
+UPDATE tenant
+SET archived_at=:archived_at
+WHERE slug = :slug
+  AND archived_at IS NULL;
\ No newline at end of file
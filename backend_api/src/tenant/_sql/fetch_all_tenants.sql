@@ -0,0 +1,2 @@
+SELECT id, slug, name, created_at, archived_at
+FROM tenant
\ No newline at end of file
@@ -0,0 +1,3 @@
+SELECT id, slug, name, created_at, archived_at
+FROM tenant
+WHERE slug = :slug;
\ No newline at end of file
@@ -0,0 +1,88 @@
+pub mod object;
+pub mod pool;
+pub mod repository;
+pub mod response;
+#[cfg(test)]
+mod tests;
+
+use crate::ApiTag;
+use crate::api_key::AdminApiKey;
+use crate::common::context::Dep;
+use crate::common::object::Message;
+use crate::common::results::unified;
+use crate::tenant::object::{TenantAddObject, is_valid_slug};
+use crate::tenant::pool::TenantPool;
+use crate::tenant::repository::{TenantRepository, TenantRepositoryError};
+use crate::tenant::response::{AddTenantResponse, ArchiveTenantResponse, FetchAllTenantsResponse};
+use poem_openapi::OpenApi;
+use poem_openapi::param::Path;
+use poem_openapi::payload::Json;
+
+pub struct TenantApi;
+
+#[OpenApi(prefix_path = "/admin/tenant", tag = "ApiTag::Tenant")]
+impl TenantApi {
+    /// Fetch All Tenants
+    #[oai(path = "/", method = "get")]
+    async fn index(
+        &self,
+        Dep(_admin): Dep<AdminApiKey>,
+        Dep(tenant_repository): Dep<TenantRepository>,
+    ) -> FetchAllTenantsResponse {
+        unified(async {
+            tenant_repository
+                .fetch_all_tenants()
+                .map(|tenants| FetchAllTenantsResponse::Ok(Json(tenants.to_vec())))
+                .map_err(|_| FetchAllTenantsResponse::InternalServerError)
+        })
+        .await
+    }
+
+    /// Add Tenant and create its database
+    #[oai(path = "/add", method = "post")]
+    async fn add(
+        &self,
+        Json(tenant): Json<TenantAddObject>,
+        Dep(_admin): Dep<AdminApiKey>,
+        Dep(tenant_repository): Dep<TenantRepository>,
+        Dep(tenant_pool): Dep<TenantPool>,
+    ) -> AddTenantResponse {
+        unified(async {
+            if !is_valid_slug(&tenant.slug) {
+                return Err(AddTenantResponse::UnprocessableEntity(Json(Message {
+                    message: "Slug must be 1 to 40 lowercase letters, digits or dashes".to_string(),
+                })));
+            }
+            let tenant = tenant_repository.add_tenant(&tenant).map_err(|err| {
+                match err.current_context() {
+                    TenantRepositoryError::SlugTakenError => AddTenantResponse::Conflict,
+                    _ => AddTenantResponse::InternalServerError,
+                }
+            })?;
+            tenant_pool
+                .client(&tenant.slug)
+                .map_err(|_| AddTenantResponse::InternalServerError)?;
+            Ok(AddTenantResponse::Created(Json(tenant)))
+        })
+        .await
+    }
+
+    /// Archive Tenant, its data is kept but no longer served
+    #[oai(path = "/archive/:slug", method = "post")]
+    async fn archive(
+        &self,
+        Path(slug): Path<String>,
+        Dep(_admin): Dep<AdminApiKey>,
+        Dep(tenant_repository): Dep<TenantRepository>,
+        Dep(tenant_pool): Dep<TenantPool>,
+    ) -> ArchiveTenantResponse {
+        unified(async {
+            tenant_repository
+                .archive_tenant(&slug)
+                .map_err(|_| ArchiveTenantResponse::NotFound)?;
+            tenant_pool.evict(&slug);
+            Ok(ArchiveTenantResponse::Ok)
+        })
+        .await
+    }
+}
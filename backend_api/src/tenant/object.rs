@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use poem_openapi::Object;
+
+#[derive(Debug, Object, Clone)]
+pub struct TenantObject {
+    pub id: i64,
+    pub slug: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub archived_at: Option<DateTime<Utc>>,
+}
+
+impl TenantObject {
+    pub fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
+}
+
+#[derive(Debug, Object)]
+pub struct TenantAddObject {
+    /// Lowercase letters, digits and dashes; also names the tenant's database file.
+    pub slug: String,
+    pub name: String,
+}
+
+/// Slugs end up in file names and host names, so only a safe subset is accepted.
+pub fn is_valid_slug(slug: &str) -> bool {
+    !slug.is_empty()
+        && slug.len() <= 40
+        && !slug.starts_with('-')
+        && !slug.ends_with('-')
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
@@ -0,0 +1,161 @@
+use crate::api_key::RequestApiKey;
+use crate::common::config::tenant::{TenantConfig, TenantResolver};
+use crate::common::context::{Context, ContextError, FromContext};
+use crate::common::db::{DefaultConnection, SqliteClient, SqliteClientError, TenantConnection};
+use crate::common::error::FromIntoStackError;
+use crate::tenant::object::{TenantObject, is_valid_slug};
+use crate::tenant::repository::{TenantRepository, TenantRepositoryError};
+use error_stack::{Report, ResultExt};
+use lru::LruCache;
+use poem::http::StatusCode;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+/// Open tenant databases, least recently used first out once `cache_size` is reached.
+///
+/// An evicted database closes once the requests still using it finish.
+#[derive(Clone)]
+pub struct TenantPool(Arc<TenantPoolInner>);
+
+struct TenantPoolInner {
+    config: Arc<TenantConfig>,
+    clients: Mutex<LruCache<String, SqliteClient<TenantConnection>>>,
+}
+
+impl TenantPool {
+    pub fn new(config: Arc<TenantConfig>) -> Self {
+        Self(Arc::new(TenantPoolInner {
+            config: Arc::clone(&config),
+            clients: Mutex::new(LruCache::new(
+                NonZeroUsize::new(config.cache_size).unwrap_or(NonZeroUsize::MIN),
+            )),
+        }))
+    }
+
+    /// Database of the tenant, opened and migrated on first use.
+    pub fn client(
+        &self,
+        slug: &str,
+    ) -> Result<SqliteClient<TenantConnection>, Report<SqliteClientError>> {
+        if !is_valid_slug(slug) {
+            return Err(SqliteClientError::Connection
+                .into_stack_error_critical(format!("Invalid tenant slug '{}'", slug)));
+        }
+
+        let mut clients = self
+            .0
+            .clients
+            .lock()
+            .map_err(|_| SqliteClientError::Connection)?;
+        if let Some(client) = clients.get(slug) {
+            return Ok(client.clone());
+        }
+
+        std::fs::create_dir_all(&self.0.config.directory)
+            .change_context(SqliteClientError::Connection)?;
+        let path = self.0.config.database_path(slug);
+        let client = SqliteClient::<TenantConnection>::new(path.to_string_lossy().to_string())?;
+        clients.put(slug.to_string(), client.clone());
+        Ok(client)
+    }
+
+    pub fn evict(&self, slug: &str) {
+        if let Ok(mut clients) = self.0.clients.lock() {
+            clients.pop(slug);
+        }
+    }
+}
+
+static TENANT_POOL_CACHE: OnceCell<TenantPool> = OnceCell::const_new();
+
+impl FromContext for TenantPool {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        if let Some(tenant_pool) = ctx.overridden::<Self>() {
+            return Ok(tenant_pool);
+        }
+        let tenant_pool: Result<&Self, Report<ContextError>> = TENANT_POOL_CACHE
+            .get_or_try_init(|| async {
+                match ctx.config.upgrade() {
+                    None => Err(ContextError::Other.into()),
+                    Some(config) => Ok(Self::new(Arc::clone(&config.tenant))),
+                }
+            })
+            .await;
+        Ok(tenant_pool?.clone())
+    }
+}
+
+pub fn resolve_slug(req: &poem::Request, config: &TenantConfig) -> Option<String> {
+    match config.resolver {
+        TenantResolver::Header => req.header(&config.header_name).map(str::to_string),
+        TenantResolver::Subdomain => {
+            let host = req.header("host")?;
+            let host = host.split(':').next()?;
+            let slug = host.strip_suffix(&config.base_domain)?.strip_suffix('.')?;
+            Some(slug.to_string())
+        }
+        TenantResolver::ApiKey => None,
+    }
+}
+
+/// The tenant the request is for, which must exist and not be archived.
+pub struct Tenant(pub TenantObject);
+
+impl FromContext for Tenant {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let config = ctx.config.upgrade().ok_or(ContextError::ConfigError)?;
+        let tenant_repository = TenantRepository::from_context(ctx).await?;
+
+        let tenant = match config.tenant.resolver {
+            TenantResolver::ApiKey => {
+                let RequestApiKey(api_key) = RequestApiKey::from_context(ctx).await?;
+                let tenant_id = api_key.tenant_id.ok_or_else(|| {
+                    ContextError::Status(
+                        StatusCode::FORBIDDEN,
+                        "API key does not belong to a tenant".to_string(),
+                    )
+                })?;
+                tenant_repository.fetch_tenant_by_id(tenant_id)
+            }
+            _ => {
+                let slug = resolve_slug(ctx.req, &config.tenant).ok_or_else(|| {
+                    ContextError::Status(StatusCode::BAD_REQUEST, "Missing tenant".to_string())
+                })?;
+                tenant_repository.fetch_tenant_by_slug(&slug)
+            }
+        }
+        .map_err(|err| match err.current_context() {
+            TenantRepositoryError::NotFoundError => {
+                ContextError::Status(StatusCode::NOT_FOUND, "Unknown tenant".to_string())
+            }
+            _ => ContextError::Other,
+        })?;
+
+        if tenant.is_archived() {
+            return Err(
+                ContextError::Status(StatusCode::GONE, "Tenant archived".to_string()).into(),
+            );
+        }
+        Ok(Self(tenant))
+    }
+}
+
+impl FromContext for SqliteClient<TenantConnection> {
+    /// The tenant's database, or the main database in single-tenant mode.
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let config = ctx.config.upgrade().ok_or(ContextError::ConfigError)?;
+        if !config.tenant.enabled {
+            return Ok(Self::single_tenant(
+                &SqliteClient::<DefaultConnection>::from_context(ctx).await?,
+            ));
+        }
+
+        let Tenant(tenant) = Tenant::from_context(ctx).await?;
+        TenantPool::from_context(ctx)
+            .await?
+            .client(&tenant.slug)
+            .map(|sqlite_client| sqlite_client.for_request(ctx.req))
+            .change_context(ContextError::Other)
+    }
+}
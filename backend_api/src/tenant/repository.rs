@@ -0,0 +1,173 @@
+use crate::common::context::{Context, ContextError, FromContext};
+use crate::common::db::{DefaultConnection, SqliteClient};
+use crate::tenant::object::{TenantAddObject, TenantObject};
+use chrono::Utc;
+use error_stack::{Report, ResultExt};
+use rusqlite::{ErrorCode, Row, named_params};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TenantRepositoryError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Row Value error")]
+    RowValueError,
+    #[error("Lock error")]
+    LockError,
+    #[error("Not found error")]
+    NotFoundError,
+    #[error("Slug taken error")]
+    SlugTakenError,
+}
+
+fn tenant_from_row(row: &Row) -> rusqlite::Result<TenantObject> {
+    Ok(TenantObject {
+        id: row.get("id")?,
+        slug: row.get("slug")?,
+        name: row.get("name")?,
+        created_at: row.get("created_at")?,
+        archived_at: row.get("archived_at")?,
+    })
+}
+
+/// The tenant registry, kept in the main database.
+pub struct TenantRepository {
+    sqlite_client: SqliteClient,
+}
+
+impl TenantRepository {
+    pub fn new(sqlite_client: SqliteClient) -> Self {
+        Self { sqlite_client }
+    }
+
+    pub fn add_tenant(
+        &self,
+        object: &TenantAddObject,
+    ) -> Result<TenantObject, Report<TenantRepositoryError>> {
+        {
+            let conn = self
+                .sqlite_client
+                .get_conn()
+                .lock()
+                .map_err(|_| TenantRepositoryError::LockError)?;
+
+            conn.execute(
+                include_str!("_sql/add_tenant.sql"),
+                named_params! {
+                    ":slug": object.slug,
+                    ":name": object.name,
+                    ":created_at": Utc::now(),
+                },
+            )
+            .map_err(|err| match err.sqlite_error_code() {
+                Some(ErrorCode::ConstraintViolation) => {
+                    Report::new(err).change_context(TenantRepositoryError::SlugTakenError)
+                }
+                _ => Report::new(err).change_context(TenantRepositoryError::QueryError),
+            })?;
+        }
+
+        self.fetch_tenant_by_slug(&object.slug)
+    }
+
+    pub fn fetch_all_tenants(&self) -> Result<Box<[TenantObject]>, Report<TenantRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TenantRepositoryError::LockError)?;
+
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_all_tenants.sql"))
+            .change_context(TenantRepositoryError::QueryError)?;
+
+        let item_iter = stmt
+            .query_map([], tenant_from_row)
+            .change_context(TenantRepositoryError::QueryError)?;
+
+        let mut items = Vec::new();
+        for item in item_iter {
+            items.push(item.change_context(TenantRepositoryError::RowValueError)?);
+        }
+
+        Ok(items.into())
+    }
+
+    pub fn fetch_tenant_by_slug(
+        &self,
+        slug: &str,
+    ) -> Result<TenantObject, Report<TenantRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TenantRepositoryError::LockError)?;
+
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_tenant_by_slug.sql"))
+            .change_context(TenantRepositoryError::QueryError)?;
+
+        let item = stmt
+            .query_map(named_params! {":slug": slug}, tenant_from_row)
+            .change_context(TenantRepositoryError::QueryError)?
+            .next()
+            .ok_or(TenantRepositoryError::NotFoundError)?;
+
+        Ok(item.change_context(TenantRepositoryError::RowValueError)?)
+    }
+
+    pub fn fetch_tenant_by_id(
+        &self,
+        id: i64,
+    ) -> Result<TenantObject, Report<TenantRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TenantRepositoryError::LockError)?;
+
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_tenant_by_id.sql"))
+            .change_context(TenantRepositoryError::QueryError)?;
+
+        let item = stmt
+            .query_map(named_params! {":id": id}, tenant_from_row)
+            .change_context(TenantRepositoryError::QueryError)?
+            .next()
+            .ok_or(TenantRepositoryError::NotFoundError)?;
+
+        Ok(item.change_context(TenantRepositoryError::RowValueError)?)
+    }
+
+    /// Mark the tenant archived. Its database file is kept on disk.
+    pub fn archive_tenant(&self, slug: &str) -> Result<(), Report<TenantRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TenantRepositoryError::LockError)?;
+
+        let archived = conn
+            .execute(
+                include_str!("_sql/archive_tenant.sql"),
+                named_params! {
+                    ":slug": slug,
+                    ":archived_at": Utc::now(),
+                },
+            )
+            .change_context(TenantRepositoryError::QueryError)?;
+        if archived == 0 {
+            return Err(TenantRepositoryError::NotFoundError.into());
+        }
+
+        Ok(())
+    }
+}
+
+impl FromContext for TenantRepository {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(
+            SqliteClient::<DefaultConnection>::from_context(ctx).await?,
+        ))
+    }
+}
@@ -0,0 +1,32 @@
+use crate::common::object::Message;
+use crate::tenant::object::TenantObject;
+use poem_openapi::ApiResponse;
+use poem_openapi::payload::Json;
+
+#[derive(ApiResponse)]
+pub enum FetchAllTenantsResponse {
+    #[oai(status = 200)]
+    Ok(Json<Vec<TenantObject>>),
+    #[oai(status = 500)]
+    InternalServerError,
+}
+
+#[derive(ApiResponse)]
+pub enum AddTenantResponse {
+    #[oai(status = 201)]
+    Created(Json<TenantObject>),
+    #[oai(status = 422)]
+    UnprocessableEntity(Json<Message>),
+    #[oai(status = 409)]
+    Conflict,
+    #[oai(status = 500)]
+    InternalServerError,
+}
+
+#[derive(ApiResponse)]
+pub enum ArchiveTenantResponse {
+    #[oai(status = 200)]
+    Ok,
+    #[oai(status = 404)]
+    NotFound,
+}
@@ -0,0 +1,144 @@
+use crate::api_key::repository::ApiKeyRepository;
+use crate::common::config::Config;
+use crate::common::config::tenant::TenantConfig;
+use crate::test_support::TestApp;
+use poem::http::StatusCode;
+use serde_json::json;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+/// The tenant databases live in the returned directory, which is removed once dropped.
+fn tenant_app() -> (TestApp, String, TempDir) {
+    let directory = TempDir::new().expect("tenant directory");
+    let config = Config {
+        tenant: Arc::new(TenantConfig {
+            enabled: true,
+            directory: directory.path().to_string_lossy().to_string(),
+            ..TenantConfig::default()
+        }),
+        ..Config::default()
+    };
+    let app = TestApp::builder().config(config).build();
+    let admin_key = ApiKeyRepository::new(app.sqlite_client.clone())
+        .create_api_key("admin", None)
+        .expect("admin key");
+    (app, admin_key, directory)
+}
+
+async fn add_tenant(app: &TestApp, admin_key: &str, slug: &str) {
+    app.client
+        .post("/admin/tenant/add")
+        .header("X-Api-Key", admin_key)
+        .body_json(&json!({"slug": slug, "name": slug}))
+        .send()
+        .await
+        .assert_status(StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn admin_endpoints_need_a_platform_key() {
+    let (app, admin_key, _directory) = tenant_app();
+
+    app.client
+        .get("/admin/tenant")
+        .send()
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    add_tenant(&app, &admin_key, "zoo-a").await;
+    let tenant_key = ApiKeyRepository::new(app.sqlite_client.clone())
+        .create_api_key("zoo a", Some(1))
+        .expect("tenant key");
+    app.client
+        .get("/admin/tenant")
+        .header("X-Api-Key", tenant_key)
+        .send()
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn add_tenant_rejects_bad_and_taken_slugs() {
+    let (app, admin_key, _directory) = tenant_app();
+
+    app.client
+        .post("/admin/tenant/add")
+        .header("X-Api-Key", &admin_key)
+        .body_json(&json!({"slug": "../zoo", "name": "Sneaky"}))
+        .send()
+        .await
+        .assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+
+    add_tenant(&app, &admin_key, "zoo-a").await;
+    app.client
+        .post("/admin/tenant/add")
+        .header("X-Api-Key", &admin_key)
+        .body_json(&json!({"slug": "zoo-a", "name": "Again"}))
+        .send()
+        .await
+        .assert_status(StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn tenants_do_not_share_animals() {
+    let (app, admin_key, _directory) = tenant_app();
+    add_tenant(&app, &admin_key, "zoo-a").await;
+    add_tenant(&app, &admin_key, "zoo-b").await;
+
+    app.client
+        .post("/animal/add")
+        .header("X-Tenant", "zoo-a")
+        .body_json(&json!({"species": "otter", "description": "Floats on its back"}))
+        .send()
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let res = app
+        .client
+        .get("/animal")
+        .header("X-Tenant", "zoo-a")
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    res.json().await.value().array().assert_len(1);
+
+    let res = app
+        .client
+        .get("/animal")
+        .header("X-Tenant", "zoo-b")
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    res.json().await.value().array().assert_is_empty();
+}
+
+#[tokio::test]
+async fn unknown_missing_and_archived_tenants_are_refused() {
+    let (app, admin_key, _directory) = tenant_app();
+    add_tenant(&app, &admin_key, "zoo-a").await;
+
+    app.client
+        .get("/animal")
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    app.client
+        .get("/animal")
+        .header("X-Tenant", "zoo-z")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+
+    app.client
+        .post("/admin/tenant/archive/zoo-a")
+        .header("X-Api-Key", &admin_key)
+        .send()
+        .await
+        .assert_status_is_ok();
+    app.client
+        .get("/animal")
+        .header("X-Tenant", "zoo-a")
+        .send()
+        .await
+        .assert_status(StatusCode::GONE);
+}
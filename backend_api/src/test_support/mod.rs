@@ -0,0 +1,67 @@
+use crate::animal::events::AnimalEvents;
+use crate::animal::repository::memory::InMemoryAnimalRepository;
+use crate::build_app;
+use crate::common::config::Config;
+use crate::common::db::SqliteClient;
+use crate::tenant::pool::TenantPool;
+use poem::EndpointExt;
+use poem::endpoint::BoxEndpoint;
+use poem::test::TestClient;
+use std::sync::Arc;
+
+/// The whole app wired to a private in-memory database and its own config, so tests can run
+/// in parallel without touching `sqlite.db` or the config files. Tenant databases go in
+/// `tenant.directory`, so tests enabling tenants should point it at a directory of their own.
+/// With `repository.backend = "memory"` animals go to a private in-memory repository. Animal
+/// writes are published to events of its own.
+pub struct TestApp {
+    pub client: TestClient<BoxEndpoint<'static>>,
+    pub sqlite_client: SqliteClient,
+    pub config: Arc<Config>,
+}
+
+impl TestApp {
+    pub fn builder() -> TestAppBuilder {
+        TestAppBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct TestAppBuilder {
+    config: Option<Config>,
+    sqlite_client: Option<SqliteClient>,
+}
+
+impl TestAppBuilder {
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    pub fn sqlite_client(mut self, sqlite_client: SqliteClient) -> Self {
+        self.sqlite_client = Some(sqlite_client);
+        self
+    }
+
+    pub fn build(self) -> TestApp {
+        let config = Arc::new(self.config.unwrap_or_default());
+        let sqlite_client = self
+            .sqlite_client
+            .unwrap_or_else(|| SqliteClient::new_in_memory().expect("in-memory database"));
+
+        let app = build_app()
+            .expect("app")
+            .data(sqlite_client.clone())
+            .data(TenantPool::new(Arc::clone(&config.tenant)))
+            .data(Arc::new(InMemoryAnimalRepository::with_sample_data()))
+            .data(AnimalEvents::default())
+            .data(Arc::clone(&config))
+            .boxed();
+
+        TestApp {
+            client: TestClient::new(app),
+            sqlite_client,
+            config,
+        }
+    }
+}
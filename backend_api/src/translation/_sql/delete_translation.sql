@@ -0,0 +1,4 @@
+DELETE
+FROM animal_translation
+WHERE animal_id = :animal_id
+  AND locale = :locale
\ No newline at end of file
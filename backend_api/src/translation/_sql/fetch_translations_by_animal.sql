@@ -0,0 +1,4 @@
+SELECT animal_id, locale, species, description
+FROM animal_translation
+WHERE animal_id = :animal_id
+ORDER BY locale
\ No newline at end of file
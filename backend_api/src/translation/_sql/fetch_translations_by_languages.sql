@@ -0,0 +1,3 @@
+SELECT animal_id, locale, species, description
+FROM animal_translation
+WHERE lower(substr(locale, 1, instr(locale || '-', '-') - 1)) IN (SELECT value FROM json_each(:languages))
\ No newline at end of file
@@ -0,0 +1,4 @@
+INSERT INTO animal_translation (animal_id, locale, species, description)
+VALUES (:animal_id, :locale, :species, :description)
+ON CONFLICT (animal_id, locale) DO UPDATE SET species     = excluded.species,
+                                              description = excluded.description
\ No newline at end of file
@@ -0,0 +1,118 @@
+use crate::animal::object::AnimalObject;
+use crate::common::config::locale::LocaleConfig;
+use crate::common::context::{Context, ContextError, FromContext};
+use crate::common::locale::negotiation::{accepted_languages, best_match, lookup_chain};
+use crate::translation::object::AnimalTranslationObject;
+use crate::translation::repository::{TranslationRepository, TranslationRepositoryError};
+use error_stack::Report;
+use shared::validation::types::locale_tag::LocaleTag;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Puts animals into the language that best matches `Accept-Language`, falling back through
+/// `locale.fallback` and then to the source language.
+pub struct AnimalLocalizer {
+    repository: TranslationRepository,
+    config: Arc<LocaleConfig>,
+}
+
+impl AnimalLocalizer {
+    pub fn new(repository: TranslationRepository, config: Arc<LocaleConfig>) -> Self {
+        Self { repository, config }
+    }
+
+    pub fn repository(&self) -> &TranslationRepository {
+        &self.repository
+    }
+
+    /// The language animals are written in, a translation into it would never be picked.
+    pub fn source(&self) -> LocaleTag {
+        LocaleTag::parse(&self.config.source).unwrap_or_default()
+    }
+
+    /// Translate `animals` in place, returning the `Content-Language` of the result: the
+    /// languages used, in the order first met.
+    pub fn localize(
+        &self,
+        animals: &mut [AnimalObject],
+        accept_language: Option<&str>,
+    ) -> Result<String, Report<TranslationRepositoryError>> {
+        let source = self.source();
+        let fallback: Vec<LocaleTag> = self
+            .config
+            .fallback
+            .iter()
+            .filter_map(|locale| LocaleTag::parse(locale).ok())
+            .collect();
+        let chain = lookup_chain(
+            &accepted_languages(accept_language.unwrap_or_default()),
+            &fallback,
+            &source,
+        );
+
+        let mut languages: Vec<&str> = Vec::new();
+        for tag in chain.iter() {
+            if !languages.contains(&tag.language()) {
+                languages.push(tag.language());
+            }
+        }
+        let mut translations: HashMap<i64, Vec<(LocaleTag, AnimalTranslationObject)>> =
+            HashMap::new();
+        for (animal_id, translation) in self
+            .repository
+            .fetch_translations_by_languages(&languages)?
+        {
+            if let Ok(tag) = LocaleTag::parse(&translation.locale) {
+                translations
+                    .entry(animal_id)
+                    .or_default()
+                    .push((tag, translation));
+            }
+        }
+
+        let mut used: Vec<LocaleTag> = Vec::new();
+        for animal in animals.iter_mut() {
+            let animal_translations = translations.get(&animal.id);
+            let available: Vec<&LocaleTag> = [&source]
+                .into_iter()
+                .chain(
+                    animal_translations
+                        .into_iter()
+                        .flatten()
+                        .map(|(tag, _)| tag),
+                )
+                .collect();
+            let best = best_match(&chain, &available).unwrap_or(&source);
+            if let Some((_, translation)) = animal_translations
+                .into_iter()
+                .flatten()
+                .find(|(tag, _)| *best != source && tag == best)
+            {
+                animal.species = translation.species.clone();
+                animal.description = translation.description.clone();
+            }
+            if !used.contains(best) {
+                used.push(best.clone());
+            }
+        }
+
+        if used.is_empty() {
+            used.push(source);
+        }
+        Ok(used
+            .iter()
+            .map(|tag| tag.as_str())
+            .collect::<Vec<_>>()
+            .join(", "))
+    }
+}
+
+impl FromContext for AnimalLocalizer {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let config = ctx.config.upgrade().ok_or(ContextError::ConfigError)?;
+        Ok(Self::new(
+            TranslationRepository::from_context(ctx).await?,
+            Arc::clone(&config.locale),
+        ))
+    }
+}
@@ -0,0 +1,119 @@
+pub mod localizer;
+pub mod object;
+pub mod repository;
+pub mod response;
+#[cfg(test)]
+mod tests;
+
+use crate::ApiTag;
+use crate::animal::repository::DynAnimalRepository;
+use crate::common::context::Dep;
+use crate::common::results::unified;
+use crate::translation::localizer::AnimalLocalizer;
+use crate::translation::object::{
+    AnimalTranslationErrorObject, AnimalTranslationObject, AnimalTranslationSaveObject,
+};
+use crate::translation::repository::TranslationRepositoryError;
+use crate::translation::response::{
+    DeleteTranslationResponse, FetchTranslationsResponse, SaveTranslationResponse,
+};
+use poem::i18n::Locale;
+use poem_openapi::OpenApi;
+use poem_openapi::param::Path;
+use poem_openapi::payload::Json;
+use shared::validation::rules::AnimalRules;
+use shared::validation::types::locale_tag::LocaleTag;
+
+pub struct TranslationApi;
+
+#[OpenApi(tag = "ApiTag::Translation")]
+impl TranslationApi {
+    /// Fetch the Translations of an Animal
+    #[oai(path = "/animal/:id/translations", method = "get")]
+    async fn translations(
+        &self,
+        Path(id): Path<u64>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(localizer): Dep<AnimalLocalizer>,
+    ) -> FetchTranslationsResponse {
+        unified(async {
+            animal_repository
+                .fetch_animal_by_id(id as i64)
+                .map_err(|_| FetchTranslationsResponse::NotFound)?;
+            localizer
+                .repository()
+                .fetch_translations(id as i64)
+                .map(|translations| FetchTranslationsResponse::Ok(Json(translations)))
+                .map_err(|_| FetchTranslationsResponse::InternalServerError)
+        })
+        .await
+    }
+
+    /// Add or replace the Translation of an Animal into a language, such as `fr` or `pt-BR`
+    #[oai(path = "/animal/:id/translations/:locale", method = "put")]
+    #[allow(clippy::too_many_arguments)]
+    async fn save_translation(
+        &self,
+        Path(id): Path<u64>,
+        #[oai(name = "locale")] Path(locale_tag): Path<String>,
+        Json(translation): Json<AnimalTranslationSaveObject>,
+        Dep(animal_repository): Dep<DynAnimalRepository>,
+        Dep(localizer): Dep<AnimalLocalizer>,
+        Dep(rules): Dep<AnimalRules>,
+        locale: Locale,
+    ) -> SaveTranslationResponse {
+        unified(async {
+            animal_repository
+                .fetch_animal_by_id(id as i64)
+                .map_err(|_| SaveTranslationResponse::NotFound)?;
+            let locale_tag = LocaleTag::parse(&locale_tag).map_err(|locale_error| {
+                SaveTranslationResponse::UnprocessableEntity(Json((locale_error, &locale).into()))
+            })?;
+            if locale_tag == localizer.source() {
+                return Err(SaveTranslationResponse::UnprocessableEntity(Json(
+                    AnimalTranslationErrorObject::source_locale(&locale),
+                )));
+            }
+            let validated = translation.to_validate(&rules).map_err(|animal_error| {
+                SaveTranslationResponse::UnprocessableEntity(Json((animal_error, &locale).into()))
+            })?;
+
+            let translation = AnimalTranslationObject {
+                locale: locale_tag.as_str().to_string(),
+                species: validated.species.as_str().to_string(),
+                description: validated.description.as_str().to_string(),
+            };
+            localizer
+                .repository()
+                .save_translation(id as i64, &translation)
+                .map_err(|_| SaveTranslationResponse::InternalServerError)?;
+            Ok(SaveTranslationResponse::Ok(Json(translation)))
+        })
+        .await
+    }
+
+    /// Delete the Translation of an Animal into a language
+    #[oai(path = "/animal/:id/translations/:locale", method = "delete")]
+    async fn delete_translation(
+        &self,
+        Path(id): Path<u64>,
+        #[oai(name = "locale")] Path(locale_tag): Path<String>,
+        Dep(localizer): Dep<AnimalLocalizer>,
+    ) -> DeleteTranslationResponse {
+        unified(async {
+            let locale_tag =
+                LocaleTag::parse(&locale_tag).map_err(|_| DeleteTranslationResponse::NotFound)?;
+            localizer
+                .repository()
+                .delete_translation(id as i64, locale_tag.as_str())
+                .map(|_| DeleteTranslationResponse::Ok)
+                .map_err(|err| match err.current_context() {
+                    TranslationRepositoryError::NotFoundError => {
+                        DeleteTranslationResponse::NotFound
+                    }
+                    _ => DeleteTranslationResponse::InternalServerError,
+                })
+        })
+        .await
+    }
+}
@@ -0,0 +1,82 @@
+use crate::common::locale::LocaleForErrors;
+use crate::common::object::{ValidationErrorObject, field_messages};
+use poem::i18n::Locale;
+use poem_openapi::Object;
+use shared::i18n::MessageId;
+use shared::validation::models::animal::{AnimalValidated, AnimalValidationError};
+use shared::validation::rules::AnimalRules;
+use shared::validation::types::locale_tag::LocaleTagError;
+
+#[derive(Debug, Object, Clone)]
+pub struct AnimalTranslationObject {
+    pub locale: String,
+    pub species: String,
+    pub description: String,
+}
+
+#[derive(Debug, Object)]
+pub struct AnimalTranslationSaveObject {
+    pub species: String,
+    pub description: String,
+}
+
+impl AnimalTranslationSaveObject {
+    pub fn to_validate(
+        &self,
+        rules: &AnimalRules,
+    ) -> Result<AnimalValidated, AnimalValidationError> {
+        AnimalValidated::parse_custom(self.species.clone(), self.description.clone(), rules)
+    }
+}
+
+#[derive(Debug, Object, Default)]
+pub struct AnimalTranslationErrorObject {
+    pub locale: Vec<String>,
+    pub species: Vec<String>,
+    pub description: Vec<String>,
+    /// The same errors with their code and arguments.
+    pub errors: Vec<ValidationErrorObject>,
+}
+
+impl AnimalTranslationErrorObject {
+    fn from_errors(errors: Vec<ValidationErrorObject>) -> Self {
+        Self {
+            locale: field_messages(&errors, "locale"),
+            species: field_messages(&errors, "species"),
+            description: field_messages(&errors, "description"),
+            errors,
+        }
+    }
+
+    /// The source language is edited through the animal itself.
+    pub fn source_locale(locale: &Locale) -> Self {
+        Self::from_errors(vec![ValidationErrorObject::from_message(
+            "locale",
+            MessageId::ValidateLocaleTagIsSource,
+            locale,
+        )])
+    }
+}
+
+impl From<(LocaleTagError, &Locale)> for AnimalTranslationErrorObject {
+    fn from((error, locale): (LocaleTagError, &Locale)) -> Self {
+        Self::from_errors(error.0.as_validation_errors("locale", Some(locale)))
+    }
+}
+
+impl From<(AnimalValidationError, &Locale)> for AnimalTranslationErrorObject {
+    fn from((error, locale): (AnimalValidationError, &Locale)) -> Self {
+        let mut errors = Vec::new();
+        if let Err(species) = error.species {
+            errors.extend(species.0.as_validation_errors("species", Some(locale)));
+        }
+        if let Err(description) = error.description {
+            errors.extend(
+                description
+                    .0
+                    .as_validation_errors("description", Some(locale)),
+            );
+        }
+        Self::from_errors(errors)
+    }
+}
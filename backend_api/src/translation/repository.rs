@@ -0,0 +1,160 @@
+use crate::common::context::{Context, ContextError, FromContext};
+use crate::common::db::{SqliteClient, TenantConnection};
+use crate::translation::object::AnimalTranslationObject;
+use error_stack::{Report, ResultExt};
+use rusqlite::{Row, named_params};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TranslationRepositoryError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Row Value error")]
+    RowValueError,
+    #[error("Lock error")]
+    LockError,
+    #[error("Not found error")]
+    NotFoundError,
+}
+
+fn translation_from_row(row: &Row) -> rusqlite::Result<(i64, AnimalTranslationObject)> {
+    Ok((
+        row.get("animal_id")?,
+        AnimalTranslationObject {
+            locale: row.get("locale")?,
+            species: row.get("species")?,
+            description: row.get("description")?,
+        },
+    ))
+}
+
+/// Species and description of the animals in languages other than the source one.
+pub struct TranslationRepository {
+    sqlite_client: SqliteClient<TenantConnection>,
+}
+
+impl TranslationRepository {
+    pub fn new(sqlite_client: SqliteClient<TenantConnection>) -> Self {
+        Self { sqlite_client }
+    }
+
+    pub fn fetch_translations(
+        &self,
+        animal_id: i64,
+    ) -> Result<Vec<AnimalTranslationObject>, Report<TranslationRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TranslationRepositoryError::LockError)?;
+
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_translations_by_animal.sql"))
+            .change_context(TranslationRepositoryError::QueryError)?;
+        let item_iter = stmt
+            .query_map(
+                named_params! {":animal_id": animal_id},
+                translation_from_row,
+            )
+            .change_context(TranslationRepositoryError::QueryError)?;
+
+        let mut items = Vec::new();
+        for item in item_iter {
+            let (_, translation) =
+                item.change_context(TranslationRepositoryError::RowValueError)?;
+            items.push(translation);
+        }
+
+        Ok(items)
+    }
+
+    /// Translations of every animal into any form of the given primary languages, `pt` taking
+    /// in `pt-BR` as well.
+    pub fn fetch_translations_by_languages(
+        &self,
+        languages: &[&str],
+    ) -> Result<Vec<(i64, AnimalTranslationObject)>, Report<TranslationRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TranslationRepositoryError::LockError)?;
+
+        let languages = serde_json::to_string(languages)
+            .change_context(TranslationRepositoryError::QueryError)?;
+        let mut stmt = conn
+            .prepare(include_str!("_sql/fetch_translations_by_languages.sql"))
+            .change_context(TranslationRepositoryError::QueryError)?;
+        let item_iter = stmt
+            .query_map(
+                named_params! {":languages": languages},
+                translation_from_row,
+            )
+            .change_context(TranslationRepositoryError::QueryError)?;
+
+        let mut items = Vec::new();
+        for item in item_iter {
+            items.push(item.change_context(TranslationRepositoryError::RowValueError)?);
+        }
+
+        Ok(items)
+    }
+
+    /// Add the translation of an animal, or replace the one it has into the same locale.
+    pub fn save_translation(
+        &self,
+        animal_id: i64,
+        translation: &AnimalTranslationObject,
+    ) -> Result<(), Report<TranslationRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TranslationRepositoryError::LockError)?;
+
+        conn.execute(
+            include_str!("_sql/save_translation.sql"),
+            named_params! {
+                ":animal_id": animal_id,
+                ":locale": translation.locale,
+                ":species": translation.species,
+                ":description": translation.description,
+            },
+        )
+        .change_context(TranslationRepositoryError::QueryError)?;
+
+        Ok(())
+    }
+
+    pub fn delete_translation(
+        &self,
+        animal_id: i64,
+        locale: &str,
+    ) -> Result<(), Report<TranslationRepositoryError>> {
+        let conn = self
+            .sqlite_client
+            .get_conn()
+            .lock()
+            .map_err(|_| TranslationRepositoryError::LockError)?;
+
+        let deleted = conn
+            .execute(
+                include_str!("_sql/delete_translation.sql"),
+                named_params! {":animal_id": animal_id, ":locale": locale},
+            )
+            .change_context(TranslationRepositoryError::QueryError)?;
+        if deleted == 0 {
+            return Err(TranslationRepositoryError::NotFoundError.into());
+        }
+
+        Ok(())
+    }
+}
+
+impl FromContext for TranslationRepository {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(
+            SqliteClient::<TenantConnection>::from_context(ctx).await?,
+        ))
+    }
+}
@@ -0,0 +1,35 @@
+use crate::translation::object::{AnimalTranslationErrorObject, AnimalTranslationObject};
+use poem_openapi::ApiResponse;
+use poem_openapi::payload::Json;
+
+#[derive(ApiResponse)]
+pub enum FetchTranslationsResponse {
+    #[oai(status = 200)]
+    Ok(Json<Vec<AnimalTranslationObject>>),
+    #[oai(status = 404)]
+    NotFound,
+    #[oai(status = 500)]
+    InternalServerError,
+}
+
+#[derive(ApiResponse)]
+pub enum SaveTranslationResponse {
+    #[oai(status = 200)]
+    Ok(Json<AnimalTranslationObject>),
+    #[oai(status = 404)]
+    NotFound,
+    #[oai(status = 422)]
+    UnprocessableEntity(Json<AnimalTranslationErrorObject>),
+    #[oai(status = 500)]
+    InternalServerError,
+}
+
+#[derive(ApiResponse)]
+pub enum DeleteTranslationResponse {
+    #[oai(status = 200)]
+    Ok,
+    #[oai(status = 404)]
+    NotFound,
+    #[oai(status = 500)]
+    InternalServerError,
+}
@@ -0,0 +1,208 @@
+use crate::common::config::Config;
+use crate::common::config::locale::LocaleConfig;
+use crate::test_support::TestApp;
+use poem::http::StatusCode;
+use serde_json::json;
+use std::sync::Arc;
+
+const LION: i64 = 3;
+
+async fn put_french_lion(app: &TestApp) {
+    app.client
+        .put(format!("/animal/{}/translations/fr", LION))
+        .body_json(&json!({"species": "lion", "description": "Roi de la jungle"}))
+        .send()
+        .await
+        .assert_status_is_ok();
+}
+
+#[tokio::test]
+async fn translations_are_saved_replaced_and_deleted() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .put(format!("/animal/{}/translations/PT_br", LION))
+        .body_json(&json!({"species": "leão", "description": "Rei da selva"}))
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    res.assert_json(json!({"locale": "pt-BR", "species": "leão", "description": "Rei da selva"}))
+        .await;
+    put_french_lion(&app).await;
+    put_french_lion(&app).await;
+
+    let res = app
+        .client
+        .get(format!("/animal/{}/translations", LION))
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    res.assert_json(json!([
+        {"locale": "fr", "species": "lion", "description": "Roi de la jungle"},
+        {"locale": "pt-BR", "species": "leão", "description": "Rei da selva"},
+    ]))
+    .await;
+
+    app.client
+        .delete(format!("/animal/{}/translations/pt-br", LION))
+        .send()
+        .await
+        .assert_status_is_ok();
+    app.client
+        .delete(format!("/animal/{}/translations/pt-BR", LION))
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn translations_are_validated() {
+    let app = TestApp::builder().build();
+
+    let res = app
+        .client
+        .put(format!("/animal/{}/translations/f1", LION))
+        .body_json(&json!({"species": "lion", "description": "Roi de la jungle"}))
+        .send()
+        .await;
+    res.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("locale")
+        .assert_string_array(&["Must be a language tag such as fr or pt-BR"]);
+
+    let res = app
+        .client
+        .put(format!("/animal/{}/translations/en", LION))
+        .header("Accept-Language", "fr-FR")
+        .body_json(&json!({"species": "lion", "description": "King of the Jungle"}))
+        .send()
+        .await;
+    res.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("locale")
+        .assert_string_array(&["Les animaux sont rédigés dans cette langue"]);
+
+    let res = app
+        .client
+        .put(format!("/animal/{}/translations/fr", LION))
+        .body_json(&json!({"species": "", "description": "Roi de la jungle"}))
+        .send()
+        .await;
+    res.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("species")
+        .assert_string_array(&["Cannot be empty"]);
+
+    app.client
+        .put("/animal/999/translations/fr")
+        .body_json(&json!({"species": "lion", "description": "Roi de la jungle"}))
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn fetch_by_id_negotiates_the_language() {
+    let app = TestApp::builder().build();
+    put_french_lion(&app).await;
+
+    let res = app
+        .client
+        .get(format!("/animal/fetch/{}", LION))
+        .header("Accept-Language", "de, fr-CA;q=0.8, en;q=0.5")
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    res.assert_header("Content-Language", "fr");
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("description")
+        .assert_string("Roi de la jungle");
+
+    let res = app
+        .client
+        .get(format!("/animal/fetch/{}", LION))
+        .header("Accept-Language", "en, fr;q=0.5")
+        .send()
+        .await;
+    res.assert_header("Content-Language", "en");
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("description")
+        .assert_string("King of the Jungle");
+
+    let res = app
+        .client
+        .get(format!("/animal/fetch/{}", LION))
+        .send()
+        .await;
+    res.assert_header("Content-Language", "en");
+}
+
+#[tokio::test]
+async fn fetch_by_id_falls_back_through_the_configured_chain() {
+    let config = Config {
+        locale: Arc::new(LocaleConfig {
+            fallback: vec!["fr".to_string()],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let app = TestApp::builder().config(config).build();
+    put_french_lion(&app).await;
+
+    let res = app
+        .client
+        .get(format!("/animal/fetch/{}", LION))
+        .header("Accept-Language", "de-DE")
+        .send()
+        .await;
+    res.assert_header("Content-Language", "fr");
+    res.json()
+        .await
+        .value()
+        .object()
+        .get("description")
+        .assert_string("Roi de la jungle");
+}
+
+#[tokio::test]
+async fn index_lists_the_languages_used() {
+    let app = TestApp::builder().build();
+    put_french_lion(&app).await;
+
+    let res = app
+        .client
+        .get("/animal")
+        .header("Accept-Language", "fr")
+        .send()
+        .await;
+    res.assert_status_is_ok();
+    res.assert_header("Content-Language", "en, fr");
+    let json = res.json().await;
+    let animals = json.value().array();
+    animals
+        .get(0)
+        .object()
+        .get("description")
+        .assert_string("Domestic Dog");
+    animals
+        .get(2)
+        .object()
+        .get("description")
+        .assert_string("Roi de la jungle");
+}
@@ -0,0 +1,22 @@
+pub mod object;
+pub mod spec;
+#[cfg(test)]
+mod tests;
+
+use crate::ApiTag;
+use crate::common::context::Dep;
+use crate::validation::object::ValidationRulesObject;
+use poem_openapi::OpenApi;
+use poem_openapi::payload::Json;
+use shared::validation::rules::AnimalRules;
+
+pub struct ValidationApi;
+
+#[OpenApi(prefix_path = "/validation", tag = "ApiTag::Validation")]
+impl ValidationApi {
+    /// Rules the animal fields are validated against
+    #[oai(path = "/rules", method = "get")]
+    async fn rules(&self, Dep(rules): Dep<AnimalRules>) -> Json<ValidationRulesObject> {
+        Json(rules.into())
+    }
+}
@@ -0,0 +1,35 @@
+use poem_openapi::Object;
+use shared::validation::rules::{AnimalRules, FieldRules};
+
+#[derive(Debug, Object)]
+pub struct FieldRulesObject {
+    pub required: bool,
+    pub min_length: Option<u64>,
+    pub max_length: Option<u64>,
+}
+
+impl From<FieldRules> for FieldRulesObject {
+    fn from(rules: FieldRules) -> Self {
+        Self {
+            required: rules.is_mandatory,
+            min_length: rules.min_length.map(|length| length as u64),
+            max_length: rules.max_length.map(|length| length as u64),
+        }
+    }
+}
+
+/// Rules the animal fields are validated against, lengths counted in characters.
+#[derive(Debug, Object)]
+pub struct ValidationRulesObject {
+    pub species: FieldRulesObject,
+    pub description: FieldRulesObject,
+}
+
+impl From<AnimalRules> for ValidationRulesObject {
+    fn from(rules: AnimalRules) -> Self {
+        Self {
+            species: rules.species.into(),
+            description: rules.description.into(),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::common::config::Config;
+use crate::version::ApiVersion;
+use poem::web::Json;
+use poem::{Request, handler};
+use serde_json::{Map, Value};
+use shared::validation::rules::{AnimalRules, FieldRules};
+
+/// Schemas whose `species` and `description` follow the animal rules.
+const ANIMAL_SCHEMAS: [&str; 2] = ["AnimalAddUpdateObject", "AnimalTranslationSaveObject"];
+
+fn set_length(property: &mut Map<String, Value>, key: &str, length: Option<usize>) {
+    match length {
+        Some(length) => property.insert(key.to_string(), length.into()),
+        None => property.remove(key),
+    };
+}
+
+fn apply_field_rules(property: &mut Map<String, Value>, rules: FieldRules) {
+    let min_length = match (rules.is_mandatory, rules.min_length) {
+        (true, min_length) => Some(min_length.unwrap_or_default().max(1)),
+        (false, min_length) => min_length,
+    };
+    set_length(property, "minLength", min_length);
+    set_length(property, "maxLength", rules.max_length);
+}
+
+/// The OpenAPI spec of `version` with the configured rules as `minLength` and `maxLength` of
+/// the animal fields, which poem-openapi only takes from attributes.
+pub fn spec_with_rules(version: ApiVersion, rules: &AnimalRules) -> Value {
+    let mut spec: Value = serde_json::from_str(&version.spec()).unwrap_or_default();
+    for schema in ANIMAL_SCHEMAS {
+        for (field, field_rules) in [
+            ("species", rules.species),
+            ("description", rules.description),
+        ] {
+            let pointer = format!("/components/schemas/{}/properties/{}", schema, field);
+            if let Some(property) = spec.pointer_mut(&pointer).and_then(Value::as_object_mut) {
+                apply_field_rules(property, field_rules);
+            }
+        }
+    }
+    spec
+}
+
+/// OpenAPI spec of the version the request went to, following the live config.
+#[handler]
+pub async fn openapi_json(req: &Request) -> poem::Result<Json<Value>> {
+    let version = req.data::<ApiVersion>().copied().unwrap_or(ApiVersion::V1);
+    let config = Config::for_request(req)
+        .await
+        .map_err(|_| poem::Error::from_status(poem::http::StatusCode::INTERNAL_SERVER_ERROR))?;
+    Ok(Json(spec_with_rules(
+        version,
+        &config.validation.animal_rules(),
+    )))
+}
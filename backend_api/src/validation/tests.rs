@@ -0,0 +1,114 @@
+use crate::common::config::Config;
+use crate::common::config::validation::{FieldRulesConfig, ValidationConfig};
+use crate::test_support::TestApp;
+use poem::http::StatusCode;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+/// The spec `ui/build.rs` generates the API client from.
+const UI_SPEC: &str = include_str!("../../../ui/openapi.json");
+
+fn strict_config() -> Config {
+    Config {
+        validation: Arc::new(ValidationConfig {
+            species: FieldRulesConfig {
+                required: true,
+                min_length: Some(3),
+                max_length: Some(8),
+            },
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn rules_default_to_the_shared_ones() {
+    let app = TestApp::builder().build();
+
+    let res = app.client.get("/validation/rules").send().await;
+    res.assert_status_is_ok();
+    let json = res.json().await;
+    let species = json.value().object().get("species").object();
+    species.get("required").assert_bool(true);
+    species.get("max_length").assert_i64(20);
+}
+
+#[tokio::test]
+async fn configured_rules_are_published_and_enforced() {
+    let app = TestApp::builder().config(strict_config()).build();
+
+    let res = app.client.get("/validation/rules").send().await;
+    let json = res.json().await;
+    let species = json.value().object().get("species").object();
+    species.get("min_length").assert_i64(3);
+    species.get("max_length").assert_i64(8);
+
+    let res = app
+        .client
+        .post("/animal/add")
+        .body_json(&json!({"species": "Elephant seal", "description": "Big"}))
+        .send()
+        .await;
+    res.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+
+    app.client
+        .post("/animal/add")
+        .body_json(&json!({"species": "Aardwolf", "description": "Eats termites"}))
+        .send()
+        .await
+        .assert_status(StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn openapi_json_carries_the_configured_lengths() {
+    let app = TestApp::builder().config(strict_config()).build();
+
+    let res = app.client.get("/openapi.json").send().await;
+    res.assert_status_is_ok();
+    let json = res.json().await;
+    let species = json
+        .value()
+        .object()
+        .get("components")
+        .object()
+        .get("schemas")
+        .object()
+        .get("AnimalAddUpdateObject")
+        .object()
+        .get("properties")
+        .object()
+        .get("species")
+        .object();
+    species.get("minLength").assert_i64(3);
+    species.get("maxLength").assert_i64(8);
+}
+
+/// The spec without what only documents it, nor the lengths that follow the config.
+fn contract(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .filter(|(key, value)| {
+                    !matches!(key.as_str(), "summary" | "description" | "title")
+                        || !value.is_string()
+                })
+                .filter(|(key, _)| !matches!(key.as_str(), "minLength" | "maxLength"))
+                .map(|(key, value)| (key.clone(), contract(value)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(contract).collect()),
+        value => value.clone(),
+    }
+}
+
+#[test]
+fn ui_spec_matches_the_server() {
+    let ui_spec: Value = serde_json::from_str(UI_SPEC).expect("ui/openapi.json");
+    let served: Value = serde_json::from_str(&crate::api_service().spec()).expect("spec");
+    assert!(
+        contract(&ui_spec) == contract(&served),
+        "ui/openapi.json no longer matches the server, regenerate it with \
+         `cargo run -p backend_api -- openapi > ui/openapi.json` and update the UI"
+    );
+}
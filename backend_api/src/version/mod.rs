@@ -0,0 +1,141 @@
+pub mod object;
+#[cfg(test)]
+mod tests;
+
+use crate::common::config::Config;
+use crate::version::object::VersionObject;
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use poem::http::{HeaderName, HeaderValue, StatusCode};
+use poem::web::{Data, Json};
+use poem::{Endpoint, IntoResponse, Request, Response, handler};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Version of the REST API, served under its base path. The root serves `v1` too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ApiVersion {
+    V1,
+    V2,
+}
+
+impl ApiVersion {
+    pub const ALL: [ApiVersion; 2] = [ApiVersion::V1, ApiVersion::V2];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "v1",
+            ApiVersion::V2 => "v2",
+        }
+    }
+
+    pub fn base_path(self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "/v1",
+            ApiVersion::V2 => "/v2",
+        }
+    }
+
+    /// The spec of the version as poem-openapi builds it.
+    pub fn spec(self) -> String {
+        match self {
+            ApiVersion::V1 => crate::api_service().spec(),
+            ApiVersion::V2 => crate::api_service_v2().spec(),
+        }
+    }
+}
+
+/// Path of the route, without the base path of the version serving it.
+pub fn route_path(path: &str) -> &str {
+    ApiVersion::ALL
+        .into_iter()
+        .find_map(|version| {
+            let rest = path.strip_prefix(version.base_path())?;
+            (rest.is_empty() || rest.starts_with('/')).then_some(rest)
+        })
+        .unwrap_or(path)
+}
+
+/// Requests served by each version since startup.
+#[derive(Clone, Default)]
+pub struct VersionUsage(Arc<[AtomicU64; 2]>);
+
+impl VersionUsage {
+    fn record(&self, version: ApiVersion) {
+        self.0[version as usize].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn requests(&self, version: ApiVersion) -> u64 {
+        self.0[version as usize].load(Ordering::Relaxed)
+    }
+}
+
+/// An HTTP date, as `Sunset` takes.
+fn http_date(at: DateTime<Utc>) -> String {
+    at.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// Count the request against `version`, make the version available to the handlers and
+/// announce its configured retirement with `Deprecation` and `Sunset`, error responses
+/// included.
+pub async fn versioned<E: Endpoint>(
+    version: ApiVersion,
+    next: Arc<E>,
+    mut req: Request,
+) -> poem::Result<Response> {
+    let config = Config::for_request(&req)
+        .await
+        .map_err(|_| poem::Error::from_status(StatusCode::INTERNAL_SERVER_ERROR))?;
+    if let Some(usage) = req.data::<VersionUsage>() {
+        usage.record(version);
+    }
+    req.set_data(version);
+
+    let mut resp = match next.call(req).await {
+        Ok(resp) => resp.into_response(),
+        Err(err) => err.into_response(),
+    };
+    let policy = config.versions.policy(version);
+    let headers = [
+        (
+            "deprecation",
+            policy
+                .deprecated_at
+                .map(|at| format!("@{}", at.timestamp())),
+        ),
+        ("sunset", policy.sunset_at.map(http_date)),
+    ];
+    for (name, value) in headers {
+        if let Some(value) = value.and_then(|value| HeaderValue::from_str(&value).ok()) {
+            resp.headers_mut()
+                .insert(HeaderName::from_static(name), value);
+        }
+    }
+    Ok(resp)
+}
+
+/// Every version with its base path, retirement and usage.
+#[handler]
+pub async fn versions(
+    req: &Request,
+    usage: Data<&VersionUsage>,
+) -> poem::Result<Json<Vec<VersionObject>>> {
+    let config = Config::for_request(req)
+        .await
+        .map_err(|_| poem::Error::from_status(StatusCode::INTERNAL_SERVER_ERROR))?;
+    Ok(Json(
+        ApiVersion::ALL
+            .into_iter()
+            .map(|version| {
+                let policy = config.versions.policy(version);
+                VersionObject {
+                    version: version.as_str().to_string(),
+                    path: version.base_path().to_string(),
+                    deprecated_at: policy.deprecated_at,
+                    sunset_at: policy.sunset_at,
+                    requests: usage.requests(version),
+                }
+            })
+            .collect(),
+    ))
+}
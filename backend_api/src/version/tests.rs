@@ -0,0 +1,121 @@
+use crate::common::config::Config;
+use crate::common::config::versions::{VersionPolicyConfig, VersionsConfig};
+use crate::test_support::TestApp;
+use chrono::{TimeZone, Utc};
+use std::sync::Arc;
+
+fn deprecated_v1_config() -> Config {
+    Config {
+        versions: Arc::new(VersionsConfig {
+            v1: VersionPolicyConfig {
+                deprecated_at: Some(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap()),
+                sunset_at: Some(Utc.with_ymd_and_hms(2027, 6, 30, 0, 0, 0).unwrap()),
+            },
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn versions_serve_their_own_shape() {
+    let app = TestApp::builder().build();
+
+    app.client
+        .get("/v1/animal")
+        .send()
+        .await
+        .json()
+        .await
+        .value()
+        .array()
+        .assert_len(12);
+    app.client
+        .get("/v2/animal")
+        .send()
+        .await
+        .json()
+        .await
+        .value()
+        .object()
+        .get("total")
+        .assert_i64(12);
+    app.client
+        .get("/v2/animal/fetch/3")
+        .send()
+        .await
+        .assert_status(poem::http::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn every_version_has_its_spec_and_docs() {
+    let app = TestApp::builder().build();
+
+    for (path, version) in [("/v1", "1.0.0"), ("/v2", "2.0.0"), ("", "1.0.0")] {
+        let res = app
+            .client
+            .get(format!("{}/openapi.json", path))
+            .send()
+            .await;
+        res.assert_status_is_ok();
+        res.json()
+            .await
+            .value()
+            .object()
+            .get("info")
+            .object()
+            .get("version")
+            .assert_string(version);
+        app.client
+            .get(format!("{}/docs", path))
+            .send()
+            .await
+            .assert_status_is_ok();
+    }
+}
+
+#[tokio::test]
+async fn deprecated_versions_announce_their_sunset() {
+    let app = TestApp::builder().config(deprecated_v1_config()).build();
+
+    for path in ["/v1/animal", "/animal", "/v1/animal/fetch/999"] {
+        let res = app.client.get(path).send().await;
+        res.assert_header("Deprecation", "@1767225600");
+        res.assert_header("Sunset", "Wed, 30 Jun 2027 00:00:00 GMT");
+    }
+
+    let res = app.client.get("/v2/animal").send().await;
+    res.assert_header_is_not_exist("Deprecation");
+    res.assert_header_is_not_exist("Sunset");
+}
+
+#[tokio::test]
+async fn versions_count_their_requests() {
+    let app = TestApp::builder().config(deprecated_v1_config()).build();
+
+    app.client.get("/v1/animal").send().await;
+    app.client.get("/animal").send().await;
+    app.client.get("/v2/animal").send().await;
+
+    let res = app.client.get("/versions").send().await;
+    res.assert_status_is_ok();
+    let json = res.json().await;
+    let versions = json.value().array();
+    let v1 = versions.get(0).object();
+    v1.get("path").assert_string("/v1");
+    v1.get("requests").assert_i64(2);
+    v1.get("sunset_at").assert_string("2027-06-30T00:00:00Z");
+    versions.get(1).object().get("requests").assert_i64(1);
+}
+
+#[test]
+fn sunset_before_deprecation_is_rejected() {
+    let config = VersionsConfig {
+        v2: VersionPolicyConfig {
+            deprecated_at: Some(Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap()),
+            sunset_at: Some(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap()),
+        },
+        ..Default::default()
+    };
+    assert!(config.validate().is_err());
+}
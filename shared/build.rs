@@ -0,0 +1,51 @@
+#[path = "src/i18n/keys.rs"]
+mod keys;
+
+use std::fmt::Write;
+use std::path::Path;
+
+/// `validate-max-length` as `ValidateMaxLength`.
+fn variant_name(key: &str) -> String {
+    key.split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Generate `MessageId` from the keys of the English bundle, so a mistyped key fails to compile.
+fn main() {
+    let source = "src/i18n/_locale/english.ftl";
+    println!("cargo:rerun-if-changed={}", source);
+    println!("cargo:rerun-if-changed=src/i18n/keys.rs");
+
+    let ftl = std::fs::read_to_string(source).expect("English bundle");
+    let keys: Vec<(&str, String)> = keys::bundle_keys(&ftl)
+        .into_iter()
+        .map(|key| (key, variant_name(key)))
+        .collect();
+
+    let mut code = String::new();
+    code.push_str("/// Key of a message of the bundles, generated from `english.ftl`.\n");
+    code.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]\npub enum MessageId {\n");
+    for (_, variant) in keys.iter() {
+        writeln!(code, "    {},", variant).unwrap();
+    }
+    code.push_str("}\n\nimpl MessageId {\n    pub const ALL: &[MessageId] = &[\n");
+    for (_, variant) in keys.iter() {
+        writeln!(code, "        MessageId::{},", variant).unwrap();
+    }
+    code.push_str("    ];\n\n    pub fn key(self) -> &'static str {\n        match self {\n");
+    for (key, variant) in keys.iter() {
+        writeln!(code, "            MessageId::{} => {:?},", variant, key).unwrap();
+    }
+    code.push_str("        }\n    }\n\n    pub fn from_key(key: &str) -> Option<Self> {\n");
+    code.push_str("        Self::ALL.iter().copied().find(|id| id.key() == key)\n    }\n}\n");
+
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR");
+    std::fs::write(Path::new(&out_dir).join("message_id.rs"), code).expect("message_id.rs");
+}
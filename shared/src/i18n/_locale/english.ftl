@@ -0,0 +1,47 @@
+# Validation
+validate-cannot-be-empty = Cannot be empty
+
+validate-min-length =
+    Must be at least { $min ->
+        [one] 1 character
+        *[other] { $min } characters
+    }
+validate-max-length =
+    Must be at most { $max ->
+        [one] 1 character
+        *[other] { $max } characters
+    }
+
+validate-must-have-special-chars = Must contain at least one special character
+validate-must-have-uppercase-and-lowercase = Must contain at least one uppercase and lowercase letter
+validate-must-have-uppercase = Must contain at least one uppercase letter
+validate-must-have-lowercase = Must contain at least one lowercase letter
+validate-must-have-digit = Must contain at least one digit
+
+validate-password-does-not-match = Does not match
+validate-username-taken = Already taken
+validate-species-taken = Already taken
+validate-taxon-unknown = Unknown taxon
+
+validate-position-invalid = Must be a longitude and latitude pair
+validate-longitude-out-of-range = Longitude must be between -180 and 180
+validate-latitude-out-of-range = Latitude must be between -90 and 90
+validate-bbox-invalid = Must be four numbers: west, south, east and north
+validate-bbox-inverted = West and south must not exceed east and north
+validate-ring-too-short = A ring needs at least four positions
+validate-ring-not-closed = A ring must end where it starts
+
+validate-conservation-status-unknown = Must be one of LC, NT, VU, EN, CR, EW, EX, DD or NE
+validate-locale-tag-invalid = Must be a language tag such as fr or pt-BR
+validate-locale-tag-is-source = Animals are written in this language
+
+# IUCN conservation status
+conservation-status-lc = Least Concern
+conservation-status-nt = Near Threatened
+conservation-status-vu = Vulnerable
+conservation-status-en = Endangered
+conservation-status-cr = Critically Endangered
+conservation-status-ew = Extinct in the Wild
+conservation-status-ex = Extinct
+conservation-status-dd = Data Deficient
+conservation-status-ne = Not Evaluated
\ No newline at end of file
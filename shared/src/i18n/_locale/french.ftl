@@ -0,0 +1,47 @@
+# Validation
+validate-cannot-be-empty = Ne peut pas être vide
+
+validate-min-length =
+    Doit comporter au moins { $min ->
+        [one] 1 caractère
+        *[other] { $min } caractères
+    }
+validate-max-length =
+    Doit comporter au maximum { $max ->
+        [one] 1 caractère
+        *[other] { $max } caractères
+    }
+
+validate-must-have-special-chars = Doit contenir au moins un caractère spécial
+validate-must-have-uppercase-and-lowercase = Doit contenir au moins une lettre majuscule et une lettre minuscule.
+validate-must-have-uppercase = Doit contenir au moins une lettre majuscule
+validate-must-have-lowercase = Doit contenir au moins une lettre minuscule
+validate-must-have-digit = MDoit contenir au moins un chiffre
+
+validate-password-does-not-match = Ne correspond pas
+validate-username-taken = Déjà pris
+validate-species-taken = Déjà prise
+validate-taxon-unknown = Taxon inconnu
+
+validate-position-invalid = Doit être une paire longitude et latitude
+validate-longitude-out-of-range = La longitude doit être comprise entre -180 et 180
+validate-latitude-out-of-range = La latitude doit être comprise entre -90 et 90
+validate-bbox-invalid = Doit contenir quatre nombres : ouest, sud, est et nord
+validate-bbox-inverted = L'ouest et le sud ne doivent pas dépasser l'est et le nord
+validate-ring-too-short = Un anneau doit compter au moins quatre positions
+validate-ring-not-closed = Un anneau doit se terminer là où il commence
+
+validate-conservation-status-unknown = Doit être LC, NT, VU, EN, CR, EW, EX, DD ou NE
+validate-locale-tag-invalid = Doit être une étiquette de langue comme fr ou pt-BR
+validate-locale-tag-is-source = Les animaux sont rédigés dans cette langue
+
+# Statut de conservation UICN
+conservation-status-lc = Préoccupation mineure
+conservation-status-nt = Quasi menacé
+conservation-status-vu = Vulnérable
+conservation-status-en = En danger
+conservation-status-cr = En danger critique
+conservation-status-ew = Éteint à l'état sauvage
+conservation-status-ex = Éteint
+conservation-status-dd = Données insuffisantes
+conservation-status-ne = Non évalué
\ No newline at end of file
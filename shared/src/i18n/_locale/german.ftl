@@ -0,0 +1,47 @@
+# Validation
+validate-cannot-be-empty = Darf nicht leer sein
+
+validate-min-length =
+    Muss mindestens { $min ->
+        [one] 1 Zeichen
+        *[other] { $min } Zeichen
+    } lang sein
+validate-max-length =
+    Darf höchstens { $max ->
+        [one] 1 Zeichen
+        *[other] { $max } Zeichen
+    } lang sein
+
+validate-must-have-special-chars = Muss mindestens ein Sonderzeichen enthalten
+validate-must-have-uppercase-and-lowercase = Muss mindestens einen Groß- und einen Kleinbuchstaben enthalten
+validate-must-have-uppercase = Muss mindestens einen Großbuchstaben enthalten
+validate-must-have-lowercase = Muss mindestens einen Kleinbuchstaben enthalten
+validate-must-have-digit = Muss mindestens eine Ziffer enthalten
+
+validate-password-does-not-match = Stimmt nicht überein
+validate-username-taken = Bereits vergeben
+validate-species-taken = Bereits vergeben
+validate-taxon-unknown = Unbekanntes Taxon
+
+validate-position-invalid = Muss ein Paar aus Längen- und Breitengrad sein
+validate-longitude-out-of-range = Der Längengrad muss zwischen -180 und 180 liegen
+validate-latitude-out-of-range = Der Breitengrad muss zwischen -90 und 90 liegen
+validate-bbox-invalid = Muss aus vier Zahlen bestehen: West, Süd, Ost und Nord
+validate-bbox-inverted = West und Süd dürfen Ost und Nord nicht überschreiten
+validate-ring-too-short = Ein Ring braucht mindestens vier Positionen
+validate-ring-not-closed = Ein Ring muss dort enden, wo er beginnt
+
+validate-conservation-status-unknown = Muss LC, NT, VU, EN, CR, EW, EX, DD oder NE sein
+validate-locale-tag-invalid = Muss ein Sprachkürzel wie fr oder pt-BR sein
+validate-locale-tag-is-source = Die Tiere sind in dieser Sprache verfasst
+
+# IUCN-Gefährdungsstatus
+conservation-status-lc = Nicht gefährdet
+conservation-status-nt = Potenziell gefährdet
+conservation-status-vu = Gefährdet
+conservation-status-en = Stark gefährdet
+conservation-status-cr = Vom Aussterben bedroht
+conservation-status-ew = In der Natur ausgestorben
+conservation-status-ex = Ausgestorben
+conservation-status-dd = Unzureichende Datenlage
+conservation-status-ne = Nicht beurteilt
\ No newline at end of file
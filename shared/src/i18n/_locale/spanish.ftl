@@ -0,0 +1,47 @@
+# Validación
+validate-cannot-be-empty = No puede estar vacío
+
+validate-min-length =
+    Debe tener al menos { $min ->
+        [one] 1 carácter
+        *[other] { $min } caracteres
+    }
+validate-max-length =
+    Debe tener como máximo { $max ->
+        [one] 1 carácter
+        *[other] { $max } caracteres
+    }
+
+validate-must-have-special-chars = Debe contener al menos un carácter especial
+validate-must-have-uppercase-and-lowercase = Debe contener al menos una letra mayúscula y una minúscula
+validate-must-have-uppercase = Debe contener al menos una letra mayúscula
+validate-must-have-lowercase = Debe contener al menos una letra minúscula
+validate-must-have-digit = Debe contener al menos un dígito
+
+validate-password-does-not-match = No coincide
+validate-username-taken = Ya está en uso
+validate-species-taken = Ya está en uso
+validate-taxon-unknown = Taxón desconocido
+
+validate-position-invalid = Debe ser un par de longitud y latitud
+validate-longitude-out-of-range = La longitud debe estar entre -180 y 180
+validate-latitude-out-of-range = La latitud debe estar entre -90 y 90
+validate-bbox-invalid = Debe contener cuatro números: oeste, sur, este y norte
+validate-bbox-inverted = El oeste y el sur no deben superar al este y al norte
+validate-ring-too-short = Un anillo necesita al menos cuatro posiciones
+validate-ring-not-closed = Un anillo debe terminar donde empieza
+
+validate-conservation-status-unknown = Debe ser LC, NT, VU, EN, CR, EW, EX, DD o NE
+validate-locale-tag-invalid = Debe ser una etiqueta de idioma como fr o pt-BR
+validate-locale-tag-is-source = Los animales están redactados en este idioma
+
+# Estado de conservación de la UICN
+conservation-status-lc = Preocupación menor
+conservation-status-nt = Casi amenazado
+conservation-status-vu = Vulnerable
+conservation-status-en = En peligro
+conservation-status-cr = En peligro crítico
+conservation-status-ew = Extinto en estado silvestre
+conservation-status-ex = Extinto
+conservation-status-dd = Datos insuficientes
+conservation-status-ne = No evaluado
\ No newline at end of file
@@ -0,0 +1,23 @@
+use crate::i18n::Translator;
+use cjtoolkit_structured_validator::common::locale::LocaleValue;
+use dioxus_i18n::fluent::FluentArgs;
+use dioxus_i18n::prelude::I18n;
+
+impl Translator for I18n {
+    fn translate(&self, key: &str, args: &[(&str, &LocaleValue)]) -> Option<String> {
+        if args.is_empty() {
+            return self.try_translate(key).ok();
+        }
+        let mut values = FluentArgs::default();
+        for (name, value) in args.iter() {
+            let name = name.to_string();
+            match value {
+                LocaleValue::String(string) => values.set::<String, String>(name, string.clone()),
+                LocaleValue::Uint(unit) => values.set::<String, usize>(name, *unit),
+                LocaleValue::Int(int) => values.set::<String, isize>(name, *int),
+                LocaleValue::Float(float) => values.set::<String, f64>(name, *float),
+            }
+        }
+        self.try_translate_with_args(key, Some(&values)).ok()
+    }
+}
@@ -0,0 +1,11 @@
+use std::collections::BTreeSet;
+
+/// Identifiers of the messages of a bundle, the lines starting a message or a term.
+pub fn bundle_keys(ftl: &str) -> BTreeSet<&str> {
+    ftl.lines()
+        .filter(|line| !line.starts_with([' ', '\t', '#', '.', '[', '*', '}']))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, _)| key.trim())
+        .filter(|key| !key.is_empty())
+        .collect()
+}
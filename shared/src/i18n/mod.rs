@@ -0,0 +1,108 @@
+use crate::validation::types::locale_tag::LocaleTag;
+use cjtoolkit_structured_validator::common::locale::{LocaleData, LocaleValue, ValidateErrorStore};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[cfg(feature = "dioxus")]
+pub mod dioxus;
+mod keys;
+#[cfg(feature = "poem")]
+pub mod poem;
+
+pub use keys::bundle_keys;
+
+include!(concat!(env!("OUT_DIR"), "/message_id.rs"));
+
+/// Message bundles by language, English first as the one the others must match.
+pub const BUNDLES: [(&str, &str); 4] = [
+    ("en", include_str!("_locale/english.ftl")),
+    ("fr", include_str!("_locale/french.ftl")),
+    ("de", include_str!("_locale/german.ftl")),
+    ("es", include_str!("_locale/spanish.ftl")),
+];
+
+/// Languages there is a message bundle for.
+pub fn bundle_languages() -> Vec<LocaleTag> {
+    BUNDLES
+        .iter()
+        .filter_map(|(language, _)| LocaleTag::parse(language).ok())
+        .collect()
+}
+
+#[derive(Debug, Error)]
+#[error("Bundle '{language}' is missing {missing:?} and has extra {extra:?}")]
+pub struct BundleKeysError {
+    pub language: String,
+    pub missing: Vec<String>,
+    pub extra: Vec<String>,
+}
+
+/// Check every bundle defines exactly the keys of the English one, so no language falls back
+/// to raw keys.
+pub fn check_bundle_keys() -> Result<(), BundleKeysError> {
+    let [(_, english), others @ ..] = &BUNDLES;
+    let expected = bundle_keys(english);
+    for (language, ftl) in others {
+        let keys = bundle_keys(ftl);
+        let missing: Vec<String> = expected
+            .difference(&keys)
+            .map(|key| key.to_string())
+            .collect();
+        let extra: Vec<String> = keys
+            .difference(&expected)
+            .map(|key| key.to_string())
+            .collect();
+        if !missing.is_empty() || !extra.is_empty() {
+            return Err(BundleKeysError {
+                language: language.to_string(),
+                missing,
+                extra,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Looks messages up in the bundles of one language. Implemented for poem's `Locale` and
+/// dioxus-i18n's `I18n` behind the `poem` and `dioxus` features.
+pub trait Translator {
+    /// Message of `key` filled in with `args`, `None` when the bundle lacks it.
+    fn translate(&self, key: &str, args: &[(&str, &LocaleValue)]) -> Option<String>;
+
+    /// Message of `id`, its key when the bundle lacks it.
+    fn message(&self, id: MessageId) -> String {
+        self.translate(id.key(), &[])
+            .unwrap_or_else(|| id.key().to_string())
+    }
+}
+
+pub trait LocaleForData {
+    fn get_translation(&self, translator: &impl Translator, original: String) -> String;
+}
+
+impl LocaleForData for LocaleData {
+    fn get_translation(&self, translator: &impl Translator, original: String) -> String {
+        let args: Vec<(&str, &LocaleValue)> = self
+            .args
+            .iter()
+            .map(|(key, value)| (key.as_str(), value))
+            .collect();
+        translator.translate(&self.name, &args).unwrap_or(original)
+    }
+}
+
+pub trait LocaleForStore {
+    fn as_translated_message(&self, translator: &impl Translator) -> Arc<[String]>;
+}
+
+impl LocaleForStore for ValidateErrorStore {
+    fn as_translated_message(&self, translator: &impl Translator) -> Arc<[String]> {
+        self.0
+            .iter()
+            .map(|e| {
+                e.1.get_locale_data()
+                    .get_translation(translator, e.0.clone())
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,22 @@
+use crate::i18n::Translator;
+use cjtoolkit_structured_validator::common::locale::LocaleValue;
+use poem::i18n::{I18NArgs, Locale};
+
+impl Translator for Locale {
+    fn translate(&self, key: &str, args: &[(&str, &LocaleValue)]) -> Option<String> {
+        if args.is_empty() {
+            return self.text(key).ok();
+        }
+        let mut values = I18NArgs::default();
+        for (name, value) in args.iter() {
+            let name = name.to_string();
+            values = match value {
+                LocaleValue::String(string) => values.set::<String, String>(name, string.clone()),
+                LocaleValue::Uint(unit) => values.set::<String, usize>(name, *unit),
+                LocaleValue::Int(int) => values.set::<String, isize>(name, *int),
+                LocaleValue::Float(float) => values.set::<String, f64>(name, *float),
+            };
+        }
+        self.text_with_args(key, values).ok()
+    }
+}
@@ -1,3 +1,4 @@
 pub mod models;
+pub mod rules;
 
 pub mod types;
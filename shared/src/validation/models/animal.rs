@@ -1,4 +1,5 @@
 use crate::validation::models::error_flag;
+use crate::validation::rules::AnimalRules;
 use crate::validation::types::species::{Species, SpeciesError};
 use cjtoolkit_structured_validator::types::description::{Description, DescriptionError};
 use thiserror::Error;
@@ -27,13 +28,24 @@ pub struct AnimalValidated {
 
 impl AnimalValidated {
     pub fn parse(species: String, description: String) -> Result<Self, AnimalValidationError> {
+        Self::parse_custom(species, description, &AnimalRules::default())
+    }
+
+    pub fn parse_custom(
+        species: String,
+        description: String,
+        rules: &AnimalRules,
+    ) -> Result<Self, AnimalValidationError> {
         let mut flag = false;
 
         use error_flag as ef;
-        let species = ef(&mut flag, Species::parse(species.clone()));
+        let species = ef(
+            &mut flag,
+            Species::parse_custom(species.clone(), rules.species.into()),
+        );
         let description = ef(
             &mut flag,
-            Description::parse(Some(description.clone().as_str())),
+            Description::parse_custom(Some(description.clone().as_str()), rules.description.into()),
         );
 
         if flag {
@@ -48,4 +60,67 @@ impl AnimalValidated {
             description: description.unwrap_or_default(),
         })
     }
+
+    /// The species a uniqueness check applies to, valid or not for other reasons.
+    fn checked_species(parsed: &Result<Self, AnimalValidationError>) -> Option<Species> {
+        match parsed {
+            Ok(validated) => Some(validated.species.clone()),
+            Err(error) => error.species.clone().ok(),
+        }
+    }
+
+    fn species_taken(
+        parsed: Result<Self, AnimalValidationError>,
+    ) -> Result<Self, AnimalValidationError> {
+        Err(AnimalValidationError {
+            species: Err(SpeciesError::taken()),
+            description: match parsed {
+                Ok(validated) => Ok(validated.description),
+                Err(error) => error.description,
+            },
+        })
+    }
+
+    /// [`Self::parse_custom`], then check the species is not used by another animal, which
+    /// `is_taken` looks up, usually in a database.
+    pub async fn parse_unique<F, Fut>(
+        species: String,
+        description: String,
+        rules: &AnimalRules,
+        is_taken: F,
+    ) -> Result<Self, AnimalValidationError>
+    where
+        F: FnOnce(&Species) -> Fut,
+        Fut: Future<Output = bool>,
+    {
+        let parsed = Self::parse_custom(species, description, rules);
+        let Some(species) = Self::checked_species(&parsed) else {
+            return parsed;
+        };
+        if !is_taken(&species).await {
+            return parsed;
+        }
+        Self::species_taken(parsed)
+    }
+
+    /// [`Self::parse_unique`] with a lookup that does not wait, such as one made inside a
+    /// database transaction.
+    pub fn parse_unique_blocking<F>(
+        species: String,
+        description: String,
+        rules: &AnimalRules,
+        is_taken: F,
+    ) -> Result<Self, AnimalValidationError>
+    where
+        F: FnOnce(&Species) -> bool,
+    {
+        let parsed = Self::parse_custom(species, description, rules);
+        let Some(species) = Self::checked_species(&parsed) else {
+            return parsed;
+        };
+        if !is_taken(&species) {
+            return parsed;
+        }
+        Self::species_taken(parsed)
+    }
 }
@@ -0,0 +1,57 @@
+use crate::validation::types::species::SpeciesRules;
+use cjtoolkit_structured_validator::types::description::DescriptionRules;
+
+/// Mandatory and length rules of a text field, lengths counted in characters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FieldRules {
+    pub is_mandatory: bool,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+}
+
+impl From<FieldRules> for SpeciesRules {
+    fn from(rules: FieldRules) -> Self {
+        Self {
+            is_mandatory: rules.is_mandatory,
+            min_length: rules.min_length,
+            max_length: rules.max_length,
+        }
+    }
+}
+
+impl From<FieldRules> for DescriptionRules {
+    fn from(rules: FieldRules) -> Self {
+        Self {
+            is_mandatory: rules.is_mandatory,
+            min_length: rules.min_length,
+            max_length: rules.max_length,
+        }
+    }
+}
+
+/// Rules of the animal fields. The server takes them from its config and publishes them, so
+/// clients validate exactly as it does.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnimalRules {
+    pub species: FieldRules,
+    pub description: FieldRules,
+}
+
+impl Default for AnimalRules {
+    fn default() -> Self {
+        let species = SpeciesRules::default();
+        let description = DescriptionRules::default();
+        Self {
+            species: FieldRules {
+                is_mandatory: species.is_mandatory,
+                min_length: species.min_length,
+                max_length: species.max_length,
+            },
+            description: FieldRules {
+                is_mandatory: description.is_mandatory,
+                min_length: description.min_length,
+                max_length: description.max_length,
+            },
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::i18n::MessageId;
+use cjtoolkit_structured_validator::common::locale::{
+    LocaleData, LocaleMessage, ValidateErrorCollector, ValidateErrorStore,
+};
+use cjtoolkit_structured_validator::common::validation_check::ValidationCheck;
+use thiserror::Error;
+
+struct UnknownStatusLocale;
+
+impl LocaleMessage for UnknownStatusLocale {
+    fn get_locale_data(&self) -> LocaleData {
+        LocaleData::new(MessageId::ValidateConservationStatusUnknown.key())
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Default, Clone)]
+#[error("Conservation Status Validation Error")]
+pub struct ConservationStatusError(pub ValidateErrorStore);
+
+impl ValidationCheck for ConservationStatusError {
+    fn validate_new(messages: ValidateErrorStore) -> Self {
+        Self(messages)
+    }
+}
+
+/// IUCN Red List category, from least to most at risk, then the unassessed ones.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ConservationStatus {
+    LeastConcern,
+    NearThreatened,
+    Vulnerable,
+    Endangered,
+    CriticallyEndangered,
+    ExtinctInTheWild,
+    Extinct,
+    DataDeficient,
+    #[default]
+    NotEvaluated,
+}
+
+impl ConservationStatus {
+    pub const ALL: [Self; 9] = [
+        Self::LeastConcern,
+        Self::NearThreatened,
+        Self::Vulnerable,
+        Self::Endangered,
+        Self::CriticallyEndangered,
+        Self::ExtinctInTheWild,
+        Self::Extinct,
+        Self::DataDeficient,
+        Self::NotEvaluated,
+    ];
+
+    /// Two letter IUCN code, such as `EN`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::LeastConcern => "LC",
+            Self::NearThreatened => "NT",
+            Self::Vulnerable => "VU",
+            Self::Endangered => "EN",
+            Self::CriticallyEndangered => "CR",
+            Self::ExtinctInTheWild => "EW",
+            Self::Extinct => "EX",
+            Self::DataDeficient => "DD",
+            Self::NotEvaluated => "NE",
+        }
+    }
+
+    /// Message of the localised label.
+    pub fn label(&self) -> MessageId {
+        match self {
+            Self::LeastConcern => MessageId::ConservationStatusLc,
+            Self::NearThreatened => MessageId::ConservationStatusNt,
+            Self::Vulnerable => MessageId::ConservationStatusVu,
+            Self::Endangered => MessageId::ConservationStatusEn,
+            Self::CriticallyEndangered => MessageId::ConservationStatusCr,
+            Self::ExtinctInTheWild => MessageId::ConservationStatusEw,
+            Self::Extinct => MessageId::ConservationStatusEx,
+            Self::DataDeficient => MessageId::ConservationStatusDd,
+            Self::NotEvaluated => MessageId::ConservationStatusNe,
+        }
+    }
+
+    /// Vulnerable, Endangered or Critically Endangered.
+    pub fn is_threatened(&self) -> bool {
+        matches!(
+            self,
+            Self::Vulnerable | Self::Endangered | Self::CriticallyEndangered
+        )
+    }
+
+    /// Parse a two letter IUCN code, ignoring case.
+    pub fn parse(code: &str) -> Result<Self, ConservationStatusError> {
+        let code = code.trim();
+        let status = Self::ALL
+            .into_iter()
+            .find(|status| status.code().eq_ignore_ascii_case(code));
+
+        let mut msgs = ValidateErrorCollector::new();
+        if status.is_none() {
+            msgs.push((
+                "Must be one of LC, NT, VU, EN, CR, EW, EX, DD or NE".to_string(),
+                Box::new(UnknownStatusLocale),
+            ));
+        }
+        ValidationCheck::validate_check(msgs)?;
+        Ok(status.unwrap_or_default())
+    }
+}
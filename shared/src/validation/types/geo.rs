@@ -0,0 +1,275 @@
+use crate::i18n::MessageId;
+use cjtoolkit_structured_validator::common::locale::{
+    LocaleData, LocaleMessage, ValidateErrorCollector, ValidateErrorStore,
+};
+use cjtoolkit_structured_validator::common::validation_check::ValidationCheck;
+use thiserror::Error;
+
+struct GeoLocale(MessageId);
+
+impl LocaleMessage for GeoLocale {
+    fn get_locale_data(&self) -> LocaleData {
+        LocaleData::new(self.0.key())
+    }
+}
+
+fn push(msgs: &mut ValidateErrorCollector, name: MessageId, message: &str) {
+    msgs.push((message.to_string(), Box::new(GeoLocale(name))));
+}
+
+#[derive(Debug, Error, PartialEq, Default, Clone)]
+#[error("Geo Validation Error")]
+pub struct GeoError(pub ValidateErrorStore);
+
+impl ValidationCheck for GeoError {
+    fn validate_new(messages: ValidateErrorStore) -> Self {
+        Self(messages)
+    }
+}
+
+/// A WGS 84 position, in GeoJSON order.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct Coordinate {
+    pub longitude: f64,
+    pub latitude: f64,
+}
+
+impl Coordinate {
+    fn check(msgs: &mut ValidateErrorCollector, position: &[f64]) -> Option<Self> {
+        let [longitude, latitude, ..] = *position else {
+            push(
+                msgs,
+                MessageId::ValidatePositionInvalid,
+                "Must be a longitude and latitude pair",
+            );
+            return None;
+        };
+        if !(-180.0..=180.0).contains(&longitude) {
+            push(
+                msgs,
+                MessageId::ValidateLongitudeOutOfRange,
+                "Longitude must be between -180 and 180",
+            );
+            return None;
+        }
+        if !(-90.0..=90.0).contains(&latitude) {
+            push(
+                msgs,
+                MessageId::ValidateLatitudeOutOfRange,
+                "Latitude must be between -90 and 90",
+            );
+            return None;
+        }
+        Some(Self {
+            longitude,
+            latitude,
+        })
+    }
+
+    /// Parse a GeoJSON position, `[longitude, latitude]` with an optional ignored altitude.
+    pub fn parse(position: &[f64]) -> Result<Self, GeoError> {
+        let mut msgs = ValidateErrorCollector::new();
+        let coordinate = Self::check(&mut msgs, position);
+        ValidationCheck::validate_check(msgs)?;
+        Ok(coordinate.unwrap_or_default())
+    }
+}
+
+/// Longitude and latitude bounds, in GeoJSON `bbox` order. Boxes crossing the antimeridian are
+/// not supported.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct BoundingBox {
+    pub min: Coordinate,
+    pub max: Coordinate,
+}
+
+impl BoundingBox {
+    /// Parse `[west, south, east, north]`.
+    pub fn parse(bbox: &[f64]) -> Result<Self, GeoError> {
+        let mut msgs = ValidateErrorCollector::new();
+        let bounds = match bbox {
+            [west, south, east, north] => Coordinate::check(&mut msgs, &[*west, *south])
+                .zip(Coordinate::check(&mut msgs, &[*east, *north])),
+            _ => {
+                push(
+                    &mut msgs,
+                    MessageId::ValidateBboxInvalid,
+                    "Must be four numbers: west, south, east and north",
+                );
+                None
+            }
+        };
+        if bounds
+            .is_some_and(|(min, max)| min.longitude > max.longitude || min.latitude > max.latitude)
+        {
+            push(
+                &mut msgs,
+                MessageId::ValidateBboxInverted,
+                "West and south must not exceed east and north",
+            );
+        }
+        ValidationCheck::validate_check(msgs)?;
+        let (min, max) = bounds.unwrap_or_default();
+        Ok(Self { min, max })
+    }
+
+    pub fn contains(&self, coordinate: &Coordinate) -> bool {
+        (self.min.longitude..=self.max.longitude).contains(&coordinate.longitude)
+            && (self.min.latitude..=self.max.latitude).contains(&coordinate.latitude)
+    }
+
+    pub fn intersects(&self, other: &BoundingBox) -> bool {
+        self.min.longitude <= other.max.longitude
+            && other.min.longitude <= self.max.longitude
+            && self.min.latitude <= other.max.latitude
+            && other.min.latitude <= self.max.latitude
+    }
+
+    fn corners(&self) -> [Coordinate; 4] {
+        [
+            self.min,
+            Coordinate {
+                longitude: self.max.longitude,
+                latitude: self.min.latitude,
+            },
+            self.max,
+            Coordinate {
+                longitude: self.min.longitude,
+                latitude: self.max.latitude,
+            },
+        ]
+    }
+}
+
+/// A GeoJSON polygon: an outer ring followed by its holes, each closed.
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct Polygon {
+    rings: Vec<Vec<Coordinate>>,
+}
+
+impl Polygon {
+    pub fn parse(rings: &[Vec<Vec<f64>>]) -> Result<Self, GeoError> {
+        let mut msgs = ValidateErrorCollector::new();
+        if rings.is_empty() {
+            push(
+                &mut msgs,
+                MessageId::ValidateRingTooShort,
+                "A ring needs at least four positions",
+            );
+        }
+        let mut parsed = Vec::with_capacity(rings.len());
+        for ring in rings {
+            let coordinates: Option<Vec<Coordinate>> = ring
+                .iter()
+                .map(|position| Coordinate::check(&mut msgs, position))
+                .collect();
+            let Some(coordinates) = coordinates else {
+                continue;
+            };
+            if coordinates.len() < 4 {
+                push(
+                    &mut msgs,
+                    MessageId::ValidateRingTooShort,
+                    "A ring needs at least four positions",
+                );
+            } else if coordinates.first() != coordinates.last() {
+                push(
+                    &mut msgs,
+                    MessageId::ValidateRingNotClosed,
+                    "A ring must end where it starts",
+                );
+            }
+            parsed.push(coordinates);
+        }
+        ValidationCheck::validate_check(msgs)?;
+        Ok(Self { rings: parsed })
+    }
+
+    pub fn rings(&self) -> &[Vec<Coordinate>] {
+        &self.rings
+    }
+
+    /// Bounds of the outer ring.
+    pub fn bounding_box(&self) -> BoundingBox {
+        let mut bbox = BoundingBox {
+            min: Coordinate {
+                longitude: f64::MAX,
+                latitude: f64::MAX,
+            },
+            max: Coordinate {
+                longitude: f64::MIN,
+                latitude: f64::MIN,
+            },
+        };
+        for coordinate in self.rings.first().into_iter().flatten() {
+            bbox.min.longitude = bbox.min.longitude.min(coordinate.longitude);
+            bbox.min.latitude = bbox.min.latitude.min(coordinate.latitude);
+            bbox.max.longitude = bbox.max.longitude.max(coordinate.longitude);
+            bbox.max.latitude = bbox.max.latitude.max(coordinate.latitude);
+        }
+        bbox
+    }
+
+    /// Whether the point lies inside the outer ring and outside every hole.
+    pub fn contains(&self, coordinate: &Coordinate) -> bool {
+        let mut rings = self.rings.iter();
+        rings
+            .next()
+            .is_some_and(|outer| ring_contains(outer, coordinate))
+            && !rings.any(|hole| ring_contains(hole, coordinate))
+    }
+
+    /// Whether the polygon and the box share any point.
+    pub fn intersects(&self, bbox: &BoundingBox) -> bool {
+        if !self.bounding_box().intersects(bbox) {
+            return false;
+        }
+        let Some(outer) = self.rings.first() else {
+            return false;
+        };
+        if outer.iter().any(|coordinate| bbox.contains(coordinate)) {
+            return true;
+        }
+        let corners = bbox.corners();
+        if corners.iter().any(|corner| self.contains(corner)) {
+            return true;
+        }
+        let box_edges = [0, 1, 2, 3].map(|i| (corners[i], corners[(i + 1) % 4]));
+        self.rings.iter().any(|ring| {
+            ring.windows(2).any(|edge| {
+                box_edges
+                    .iter()
+                    .any(|(a, b)| segments_cross(&edge[0], &edge[1], a, b))
+            })
+        })
+    }
+}
+
+/// Even-odd ray casting, towards increasing longitude.
+fn ring_contains(ring: &[Coordinate], point: &Coordinate) -> bool {
+    let mut inside = false;
+    for edge in ring.windows(2) {
+        let (a, b) = (&edge[0], &edge[1]);
+        if (a.latitude > point.latitude) != (b.latitude > point.latitude) {
+            let crossing = a.longitude
+                + (point.latitude - a.latitude) / (b.latitude - a.latitude)
+                    * (b.longitude - a.longitude);
+            if point.longitude < crossing {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+fn orientation(a: &Coordinate, b: &Coordinate, c: &Coordinate) -> f64 {
+    (b.longitude - a.longitude) * (c.latitude - a.latitude)
+        - (b.latitude - a.latitude) * (c.longitude - a.longitude)
+}
+
+fn segments_cross(a: &Coordinate, b: &Coordinate, c: &Coordinate, d: &Coordinate) -> bool {
+    let (d1, d2) = (orientation(c, d, a), orientation(c, d, b));
+    let (d3, d4) = (orientation(a, b, c), orientation(a, b, d));
+    ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0))
+        && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0))
+}
@@ -0,0 +1,77 @@
+use crate::i18n::MessageId;
+use cjtoolkit_structured_validator::common::locale::{
+    LocaleData, LocaleMessage, ValidateErrorCollector, ValidateErrorStore,
+};
+use cjtoolkit_structured_validator::common::validation_check::ValidationCheck;
+use thiserror::Error;
+
+struct InvalidLocaleTagLocale;
+
+impl LocaleMessage for InvalidLocaleTagLocale {
+    fn get_locale_data(&self) -> LocaleData {
+        LocaleData::new(MessageId::ValidateLocaleTagInvalid.key())
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Default, Clone)]
+#[error("Locale Tag Validation Error")]
+pub struct LocaleTagError(pub ValidateErrorStore);
+
+impl ValidationCheck for LocaleTagError {
+    fn validate_new(messages: ValidateErrorStore) -> Self {
+        Self(messages)
+    }
+}
+
+/// Language tag such as `fr` or `pt-BR`, in its canonical case.
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LocaleTag(String);
+
+impl LocaleTag {
+    /// Canonical case of a subtag: lowercase language, titlecase script, uppercase region.
+    fn canonical_subtag(index: usize, subtag: &str) -> Option<String> {
+        let is_alpha = subtag.chars().all(|c| c.is_ascii_alphabetic());
+        match (index, subtag.len()) {
+            (0, 2..=3) if is_alpha => Some(subtag.to_ascii_lowercase()),
+            (0, _) => None,
+            (_, 4) if is_alpha => {
+                let (first, rest) = subtag.split_at(1);
+                Some(first.to_ascii_uppercase() + &rest.to_ascii_lowercase())
+            }
+            (_, 2) if is_alpha => Some(subtag.to_ascii_uppercase()),
+            (_, 1..=8) if subtag.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                Some(subtag.to_ascii_lowercase())
+            }
+            _ => None,
+        }
+    }
+
+    pub fn parse(subject: &str) -> Result<Self, LocaleTagError> {
+        let subtags: Option<Vec<String>> = subject
+            .trim()
+            .replace('_', "-")
+            .split('-')
+            .enumerate()
+            .map(|(index, subtag)| Self::canonical_subtag(index, subtag))
+            .collect();
+
+        let mut msgs = ValidateErrorCollector::new();
+        if subtags.is_none() {
+            msgs.push((
+                "Must be a language tag such as fr or pt-BR".to_string(),
+                Box::new(InvalidLocaleTagLocale),
+            ));
+        }
+        ValidationCheck::validate_check(msgs)?;
+        Ok(LocaleTag(subtags.unwrap_or_default().join("-")))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Primary language subtag, `pt` for `pt-BR`.
+    pub fn language(&self) -> &str {
+        self.0.split('-').next().unwrap_or_default()
+    }
+}
@@ -1 +1,5 @@
+pub mod conservation_status;
+pub mod geo;
+pub mod locale_tag;
 pub mod species;
+pub mod tag_name;
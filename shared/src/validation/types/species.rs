@@ -1,11 +1,22 @@
+use crate::i18n::MessageId;
 use cjtoolkit_structured_validator::base::string_rules::{StringLengthRules, StringMandatoryRules};
-use cjtoolkit_structured_validator::common::locale::{ValidateErrorCollector, ValidateErrorStore};
+use cjtoolkit_structured_validator::common::locale::{
+    LocaleData, LocaleMessage, ValidateErrorCollector, ValidateErrorStore,
+};
 use cjtoolkit_structured_validator::common::string_validator::{
     StrValidationExtension, StringValidator,
 };
 use cjtoolkit_structured_validator::common::validation_check::ValidationCheck;
 use thiserror::Error;
 
+struct SpeciesTakenLocale;
+
+impl LocaleMessage for SpeciesTakenLocale {
+    fn get_locale_data(&self) -> LocaleData {
+        LocaleData::new(MessageId::ValidateSpeciesTaken.key())
+    }
+}
+
 pub struct SpeciesRules {
     pub is_mandatory: bool,
     pub min_length: Option<usize>,
@@ -61,6 +72,15 @@ impl ValidationCheck for SpeciesError {
     }
 }
 
+impl SpeciesError {
+    /// The species is already used by another animal.
+    pub fn taken() -> Self {
+        let mut msgs = ValidateErrorCollector::new();
+        msgs.push(("Already taken".to_string(), Box::new(SpeciesTakenLocale)));
+        Self::validate_check(msgs).err().unwrap_or_default()
+    }
+}
+
 #[derive(Default, Clone, Debug, PartialEq)]
 pub struct Species(String);
 
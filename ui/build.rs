@@ -0,0 +1,420 @@
+use serde_json::{Map, Value};
+use std::fmt::Write;
+use std::path::Path;
+
+const SCHEMA_PREFIX: &str = "#/components/schemas/";
+const METHODS: [&str; 5] = ["get", "post", "put", "patch", "delete"];
+
+/// `MultiPolygon` as is, `LC` as `Lc` and `snake_case` as `SnakeCase`.
+fn type_name(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .map(|word| {
+            let mut chars = word.chars();
+            let shouting = !word.chars().any(|c| c.is_ascii_lowercase());
+            chars
+                .next()
+                .map(|first| {
+                    let rest = match shouting {
+                        true => chars.as_str().to_ascii_lowercase(),
+                        false => chars.as_str().to_string(),
+                    };
+                    first.to_ascii_uppercase().to_string() + &rest
+                })
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// `Accept-Language` as `accept_language`, keywords such as `type` as raw identifiers.
+fn field_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c.to_ascii_lowercase(),
+            false => '_',
+        })
+        .collect();
+    match name.as_str() {
+        "type" | "match" | "ref" | "mod" | "enum" | "struct" | "fn" | "in" | "for" | "move" => {
+            format!("r#{}", name)
+        }
+        _ => name,
+    }
+}
+
+/// `GET /animal/{id}/photos` as `get_animal_photos`, so a renamed route renames the method and
+/// breaks the build rather than the running UI.
+fn method_name(method: &str, path: &str) -> String {
+    let words: Vec<String> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty() && !segment.starts_with('{'))
+        .map(field_name)
+        .map(|word| word.trim_start_matches("r#").to_string())
+        .collect();
+    match words.is_empty() {
+        true => format!("{}_index", method),
+        false => format!("{}_{}", method, words.join("_")),
+    }
+}
+
+/// Name of the schema a `$ref` points to, also when wrapped in `allOf` to carry a description
+/// or a default.
+fn reference(schema: &Value) -> Option<&str> {
+    schema
+        .get("$ref")
+        .or_else(|| schema.get("allOf")?.get(0)?.get("$ref"))?
+        .as_str()?
+        .strip_prefix(SCHEMA_PREFIX)
+}
+
+fn rust_type(schema: &Value) -> String {
+    if let Some(name) = reference(schema) {
+        return type_name(name);
+    }
+    let format = schema.get("format").and_then(Value::as_str);
+    match schema.get("type").and_then(Value::as_str) {
+        Some("integer") => match format {
+            Some("int8") => "i8",
+            Some("int16") => "i16",
+            Some("int32") => "i32",
+            Some("uint8") => "u8",
+            Some("uint16") => "u16",
+            Some("uint32") => "u32",
+            Some("uint64") => "u64",
+            _ => "i64",
+        }
+        .to_string(),
+        Some("number") => match format {
+            Some("float") => "f32",
+            _ => "f64",
+        }
+        .to_string(),
+        Some("boolean") => "bool".to_string(),
+        Some("string") => match format {
+            Some("binary") => "Vec<u8>",
+            _ => "String",
+        }
+        .to_string(),
+        Some("array") => format!("Vec<{}>", rust_type(&schema["items"])),
+        Some("object") => format!(
+            "std::collections::BTreeMap<String, {}>",
+            schema
+                .get("additionalProperties")
+                .map(rust_type)
+                .unwrap_or_else(|| "serde_json::Value".to_string())
+        ),
+        _ => "serde_json::Value".to_string(),
+    }
+}
+
+fn write_docs(code: &mut String, indent: &str, docs: &[&str]) {
+    for (index, doc) in docs.iter().enumerate() {
+        if index > 0 {
+            writeln!(code, "{}///", indent).unwrap();
+        }
+        for line in doc.lines() {
+            match line.is_empty() {
+                true => writeln!(code, "{}///", indent).unwrap(),
+                false => writeln!(code, "{}/// {}", indent, line).unwrap(),
+            }
+        }
+    }
+}
+
+fn description(schema: &Value) -> Vec<&str> {
+    schema
+        .get("description")
+        .and_then(Value::as_str)
+        .into_iter()
+        .collect()
+}
+
+/// String enums, whose first value is the default so objects can derive `Default` as form
+/// state.
+fn write_enum(code: &mut String, name: &str, schema: &Value, values: &[Value]) {
+    let values: Vec<&str> = values.iter().filter_map(Value::as_str).collect();
+    write_docs(code, "", &description(schema));
+    code.push_str("#[derive(\n    Debug, Clone, Copy, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize,\n)]\n");
+    writeln!(code, "pub enum {} {{", name).unwrap();
+    for (index, value) in values.iter().enumerate() {
+        if index == 0 {
+            code.push_str("    #[default]\n");
+        }
+        writeln!(code, "    #[serde(rename = {:?})]", value).unwrap();
+        writeln!(code, "    {},", type_name(value)).unwrap();
+    }
+    code.push_str("}\n\n");
+
+    writeln!(code, "impl {} {{", name).unwrap();
+    code.push_str("    pub fn as_str(self) -> &'static str {\n        match self {\n");
+    for value in values.iter() {
+        writeln!(
+            code,
+            "            Self::{} => {:?},",
+            type_name(value),
+            value
+        )
+        .unwrap();
+    }
+    code.push_str("        }\n    }\n}\n\n");
+    writeln!(code, "impl std::fmt::Display for {} {{", name).unwrap();
+    code.push_str("    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {\n");
+    code.push_str("        f.write_str(self.as_str())\n    }\n}\n\n");
+}
+
+fn write_struct(code: &mut String, name: &str, schema: &Value) {
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| required.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let empty = Map::new();
+    let properties = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+
+    write_docs(code, "", &description(schema));
+    code.push_str(
+        "#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]\n",
+    );
+    writeln!(code, "pub struct {} {{", name).unwrap();
+    for (property, property_schema) in properties {
+        write_docs(code, "    ", &description(property_schema));
+        let field_type = rust_type(property_schema);
+        match required.contains(&property.as_str()) {
+            true => writeln!(code, "    pub {}: {},", field_name(property), field_type).unwrap(),
+            false => {
+                code.push_str("    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n");
+                writeln!(
+                    code,
+                    "    pub {}: Option<{}>,",
+                    field_name(property),
+                    field_type
+                )
+                .unwrap();
+            }
+        }
+    }
+    code.push_str("}\n\n");
+}
+
+/// How a parameter is passed to the method and added to the request.
+struct Parameter {
+    name: String,
+    wire_name: String,
+    location: String,
+    argument: String,
+    optional: bool,
+    list: bool,
+}
+
+impl Parameter {
+    fn parse(parameter: &Value) -> Self {
+        let wire_name = parameter["name"]
+            .as_str()
+            .expect("parameter name")
+            .to_string();
+        let location = parameter["in"]
+            .as_str()
+            .expect("parameter location")
+            .to_string();
+        let schema = &parameter["schema"];
+        let optional = !parameter["required"].as_bool().unwrap_or(false);
+        let list = schema.get("type").and_then(Value::as_str) == Some("array");
+        let is_str = rust_type(schema) == "String";
+        let argument = match (list, is_str, optional) {
+            (true, _, _) => format!("&[{}]", rust_type(&schema["items"])),
+            (false, true, true) => "Option<&str>".to_string(),
+            (false, true, false) => "&str".to_string(),
+            (false, false, true) => format!("Option<{}>", rust_type(schema)),
+            (false, false, false) => rust_type(schema),
+        };
+        Self {
+            name: field_name(&wire_name),
+            wire_name,
+            location,
+            argument,
+            optional,
+            list,
+        }
+    }
+
+    /// Statement adding the parameter to `request`, `None` for path parameters.
+    fn apply(&self) -> Option<String> {
+        let add = match self.location.as_str() {
+            "query" => format!("request.query(&[({:?}, value)])", self.wire_name),
+            "header" => format!("request.header({:?}, value)", self.wire_name),
+            _ => return None,
+        };
+        Some(match (self.list, self.optional) {
+            (true, _) => format!(
+                "        for value in {} {{\n            request = {};\n        }}\n",
+                self.name, add
+            ),
+            (false, true) => format!(
+                "        if let Some(value) = {} {{\n            request = {};\n        }}\n",
+                self.name, add
+            ),
+            (false, false) => format!(
+                "        let value = {};\n        request = {};\n",
+                self.name, add
+            ),
+        })
+    }
+}
+
+fn write_operation(code: &mut String, method: &str, path: &str, operation: &Value) {
+    let parameters: Vec<Parameter> = operation
+        .get("parameters")
+        .and_then(Value::as_array)
+        .map(|parameters| parameters.iter().map(Parameter::parse).collect())
+        .unwrap_or_default();
+    let content = operation
+        .get("requestBody")
+        .and_then(|body| body.get("content"))
+        .and_then(Value::as_object);
+    let body = content.and_then(|content| {
+        content
+            .iter()
+            .map(|(content_type, media)| match content_type.as_str() {
+                "multipart/form-data" => (
+                    "form: reqwest::multipart::Form".to_string(),
+                    ".multipart(form)",
+                ),
+                _ if content_type.starts_with("application/json") => (
+                    format!("body: &{}", rust_type(&media["schema"])),
+                    ".json(body)",
+                ),
+                _ => ("body: impl Into<reqwest::Body>".to_string(), ".body(body)"),
+            })
+            .next()
+    });
+
+    let mut arguments = vec!["&self".to_string()];
+    arguments.extend(
+        parameters
+            .iter()
+            .map(|parameter| format!("{}: {}", parameter.name, parameter.argument)),
+    );
+    if let Some((argument, _)) = &body {
+        arguments.push(argument.clone());
+    }
+
+    let mut url = String::from("{}");
+    let mut url_arguments = vec!["self.base_url".to_string()];
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+        match segment
+            .strip_prefix('{')
+            .and_then(|name| name.strip_suffix('}'))
+        {
+            Some(name) => {
+                url.push_str("/{}");
+                url_arguments.push(field_name(name));
+            }
+            None => {
+                url.push('/');
+                url.push_str(segment);
+            }
+        }
+    }
+    if path == "/" {
+        url.push('/');
+    }
+    let mut request = format!(
+        "self.client.{}(format!({:?}, {}))",
+        method,
+        url,
+        url_arguments.join(", ")
+    );
+    if let Some((_, call)) = &body {
+        request.push_str(call);
+    }
+    if matches!(method, "post" | "patch") {
+        request.push_str(".header(IDEMPOTENCY_KEY_HEADER, idempotency_key())");
+    }
+
+    let summary = operation.get("summary").and_then(Value::as_str);
+    let route = format!("`{} {}`", method.to_ascii_uppercase(), path);
+    let docs: Vec<&str> = summary
+        .into_iter()
+        .chain(description(operation))
+        .chain([route.as_str()])
+        .collect();
+    write_docs(code, "    ", &docs);
+    writeln!(
+        code,
+        "    pub fn {}({}) -> reqwest::RequestBuilder {{",
+        method_name(method, path),
+        arguments.join(", ")
+    )
+    .unwrap();
+    let applied: Vec<String> = parameters.iter().filter_map(Parameter::apply).collect();
+    match applied.is_empty() {
+        true => writeln!(code, "        {}", request).unwrap(),
+        false => {
+            writeln!(code, "        let mut request = {};", request).unwrap();
+            applied.iter().for_each(|apply| code.push_str(apply));
+            code.push_str("        request\n");
+        }
+    }
+    code.push_str("    }\n\n");
+}
+
+/// Generate the types and the client of the API from `openapi.json`, the spec the backend
+/// serves; a test of the backend fails when they drift apart.
+fn main() {
+    let source = "openapi.json";
+    println!("cargo:rerun-if-changed={}", source);
+
+    let spec: Value = serde_json::from_str(&std::fs::read_to_string(source).expect("OpenAPI spec"))
+        .expect("OpenAPI spec as JSON");
+
+    let mut code = String::new();
+    let schemas = spec["components"]["schemas"]
+        .as_object()
+        .expect("schemas of the spec");
+    for (name, schema) in schemas {
+        match schema.get("enum").and_then(Value::as_array) {
+            Some(values) => write_enum(&mut code, &type_name(name), schema, values),
+            None => write_struct(&mut code, &type_name(name), schema),
+        }
+    }
+
+    code.push_str(
+        "/// Client of the API, one method per operation of the spec. Methods return the request\n",
+    );
+    code.push_str("/// so callers can handle each status the way the operation defines it. `POST` and `PATCH`\n");
+    code.push_str(
+        "/// requests carry a new `Idempotency-Key`, which retries of the request keep.\n",
+    );
+    code.push_str("#[derive(Debug, Clone)]\npub struct ApiClient {\n");
+    code.push_str("    client: reqwest::Client,\n    base_url: String,\n}\n\n");
+    code.push_str("impl ApiClient {\n");
+    code.push_str(
+        "    pub fn new(client: reqwest::Client, base_url: impl Into<String>) -> Self {\n",
+    );
+    code.push_str("        Self {\n            client,\n            base_url: base_url.into(),\n        }\n    }\n\n");
+    let paths = spec["paths"].as_object().expect("paths of the spec");
+    let mut names = Vec::new();
+    for (path, item) in paths {
+        for method in METHODS {
+            if let Some(operation) = item.get(method) {
+                let name = method_name(method, path);
+                assert!(
+                    !names.contains(&name),
+                    "{} {} is named {} like another operation",
+                    method,
+                    path,
+                    name
+                );
+                names.push(name);
+                write_operation(&mut code, method, path, operation);
+            }
+        }
+    }
+    code.push_str("}\n");
+
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR");
+    std::fs::write(Path::new(&out_dir).join("api_client.rs"), code).expect("api_client.rs");
+}
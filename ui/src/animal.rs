@@ -1,25 +1,42 @@
 use crate::api::animal::{add_animal, edit_animal, fetch_all_animals, fetch_animal_by_id};
-use crate::common::locale::{LocaleForStore, build_locale_config};
+use crate::api::client::AnimalObject;
+use crate::api::photo::{fetch_animal_photos, photo_url, upload_animal_photo};
+use crate::api::tag::{attach_animal_tag, detach_animal_tag, fetch_animal_tags, search_tags};
+use crate::api::translation::{
+    delete_animal_translation, fetch_animal_translations, save_animal_translation,
+};
+use crate::api::validation::fetch_validation_rules;
+use crate::common::locale::build_locale_config;
 use crate::ext::ResetSignal;
-use crate::model::animal::{AnimalModel, AnimalModelSignal};
+use crate::model::animal::AnimalObjectSignal;
+use crate::taxonomy::Taxonomy;
 use cjtoolkit_structured_validator::common::locale::ValidateErrorStore;
 use cjtoolkit_structured_validator::types::description::DescriptionError;
 use dioxus::document::Title;
 use dioxus::prelude::*;
 use dioxus_i18n::prelude::*;
 use dioxus_primitives::alert_dialog::*;
+use shared::i18n::{LocaleForStore, Translator};
 use shared::validation::models::animal::{AnimalValidated, AnimalValidationError};
+use shared::validation::rules::AnimalRules;
+use shared::validation::types::conservation_status::ConservationStatus;
 use shared::validation::types::species::SpeciesError;
+use shared::validation::types::tag_name::{TagName, TagNameError};
 
 const MAIN_CSS: Asset = asset!("/assets/styling/main.css");
 
+/// Languages offered as tabs, next to those an animal already has a translation into.
+const TRANSLATION_LOCALES: [&str; 3] = ["fr", "de", "es"];
+
 #[derive(Routable, PartialEq, Clone)]
 #[rustfmt::skip]
-enum Route {
+pub(crate) enum Route {
     #[route("/")]
     Animal {},
     #[route("/edit/:id")]
     EditAnimal { id: i64 },
+    #[route("/taxonomy")]
+    Taxonomy {},
     #[route("/error")]
     ErrorPage {},
 }
@@ -27,6 +44,13 @@ enum Route {
 #[component]
 pub fn UiApp() -> Element {
     use_init_i18n(|| build_locale_config());
+    // The server's rules replace the defaults once fetched, it validates again either way.
+    let mut rules = use_context_provider(|| Signal::new(AnimalRules::default()));
+    use_future(move || async move {
+        if let Ok(fetched) = fetch_validation_rules().await {
+            rules.set(fetched.into());
+        }
+    });
 
     rsx! {
         document::Link { rel: "stylesheet", href: MAIN_CSS }
@@ -54,8 +78,9 @@ pub fn Animal() -> Element {
             vec![]
         })
     });
-    let animal_input = use_signal(|| AnimalModel::default());
-    let mut animal_value = use_signal(|| AnimalModel::default());
+    let rules = use_context::<Signal<AnimalRules>>();
+    let animal_input = use_signal(|| AnimalObject::default());
+    let mut animal_value = use_signal(|| AnimalObject::default());
     let mut animal_validated = use_signal(|| AnimalValidated::default());
     let mut animal_error = use_signal(|| Option::<AnimalValidationError>::None);
     let mut open = use_signal(|| false);
@@ -64,7 +89,7 @@ pub fn Animal() -> Element {
         e.prevent_default();
         async move {
             let animal = animal_input.cloned();
-            match animal.validate() {
+            match animal.validate(&rules()) {
                 Ok(animal_validated_item) => {
                     animal_validated.set(animal_validated_item);
                     open.set(true);
@@ -79,13 +104,19 @@ pub fn Animal() -> Element {
 
     let submit = move |_| async move {
         let validated_animal = animal_validated.cloned();
-        animal_value.reset();
-        animal_error.reset();
-        add_animal(validated_animal.into())
-            .await
-            .unwrap_or_else(|_| {
+        match add_animal(validated_animal.into()).await {
+            Ok(Ok(())) => {
+                animal_value.reset();
+                animal_error.reset();
+            }
+            Ok(Err(error)) => {
+                animal_value.set((&error, &animal_input.cloned()).into());
+                animal_error.set(Some(error));
+            }
+            Err(_) => {
                 navigator().push(Route::ErrorPage {});
-            });
+            }
+        }
         animals.restart();
     };
 
@@ -95,12 +126,15 @@ pub fn Animal() -> Element {
     rsx! {
         Title { "Animal" }
         h1 { "Animal" }
+        Link { class: "btn btn-skyblue inline-block", to: Route::Taxonomy {}, "Browse Taxonomy" }
         div { class: "animals",
             for animal in animals.cloned().unwrap_or_default().iter() {
-                div { class: "animal-item",
+                div { class: "animal-item", key: "{animal.id}",
+                    AnimalThumbnail { id: animal.id }
                     span { class: "animal-id", "{animal.id}" }
                     span { class: "animal-other", "{animal.species}" }
                     span { class: "animal-other", "{animal.description}" }
+                    ConservationBadge { code: animal.conservation_status.to_string() }
                     Link { class: "animal-id btn btn-skyblue",
                         to: Route::EditAnimal { id: animal.id }, "Edit" }
                 }
@@ -137,11 +171,12 @@ pub fn EditAnimal(id: i64) -> Element {
     let animal = use_resource(move || async move {
         fetch_animal_by_id(id).await.unwrap_or_else(|_| {
             navigator().push(Route::ErrorPage {});
-            AnimalModel::default()
+            AnimalObject::default()
         })
     });
-    let mut animal_input = use_signal(|| AnimalModel::default());
-    let mut animal_value = use_signal(|| AnimalModel::default());
+    let rules = use_context::<Signal<AnimalRules>>();
+    let mut animal_input = use_signal(|| AnimalObject::default());
+    let mut animal_value = use_signal(|| AnimalObject::default());
     let mut animal_validated = use_signal(|| AnimalValidated::default());
     let mut animal_error = use_signal(|| Option::<AnimalValidationError>::None);
     let mut open = use_signal(|| false);
@@ -157,7 +192,7 @@ pub fn EditAnimal(id: i64) -> Element {
         e.prevent_default();
         async move {
             let animal = animal_input.cloned();
-            match animal.validate() {
+            match animal.validate(&rules()) {
                 Ok(animal_validated_item) => {
                     animal_validated.set(animal_validated_item);
                     open.set(true);
@@ -173,12 +208,18 @@ pub fn EditAnimal(id: i64) -> Element {
     let submit = move |_| async move {
         let validated_animal = animal_validated.cloned();
         animal_error.reset();
-        edit_animal(id, validated_animal.into())
-            .await
-            .unwrap_or_else(|_| {
+        match edit_animal(id, validated_animal.into()).await {
+            Ok(Ok(())) => {
+                navigator().push(Route::Animal {});
+            }
+            Ok(Err(error)) => {
+                animal_value.set((&error, &animal_input.cloned()).into());
+                animal_error.set(Some(error));
+            }
+            Err(_) => {
                 navigator().push(Route::ErrorPage {});
-            });
-        navigator().push(Route::Animal {});
+            }
+        }
     };
 
     let animal_error_clone = animal_error_clone.unwrap_or_default();
@@ -187,11 +228,14 @@ pub fn EditAnimal(id: i64) -> Element {
     rsx! {
         Title { "Edit Animal" }
         h1 { "Edit Animal" }
+        AnimalPhotos { id }
+        AnimalTags { id }
         form { class: "form", onsubmit: alert,
             AnimalFormBody { animal_value: animal_value_clone, animal_input: animal_input,
                 animal_validation_error: animal_error_clone }
             button { class: "btn btn-skyblue", type: "submit", "Edit"}
         }
+        AnimalTranslations { id }
         Link { class: "btn btn-skyblue inline-block", to: Route::Animal { }, "Back to Animal" }
         AlertDialogRoot {
             open: open(),
@@ -214,6 +258,257 @@ pub fn EditAnimal(id: i64) -> Element {
     }
 }
 
+#[component]
+pub fn AnimalThumbnail(id: i64) -> Element {
+    let photos =
+        use_resource(move || async move { fetch_animal_photos(id).await.unwrap_or_default() });
+    let photos = photos.cloned().unwrap_or_default();
+
+    rsx! {
+        if let Some(photo) = photos.first() {
+            img { class: "animal-thumbnail", src: photo_url(photo.thumbnail_url(128)), alt: "" }
+        } else {
+            span { class: "animal-thumbnail" }
+        }
+    }
+}
+
+#[component]
+pub fn AnimalPhotos(id: i64) -> Element {
+    let mut photos =
+        use_resource(move || async move { fetch_animal_photos(id).await.unwrap_or_default() });
+    let mut upload_error = use_signal(|| false);
+
+    let upload = move |e: Event<FormData>| async move {
+        let Some(file_engine) = e.files() else {
+            return;
+        };
+        for file_name in file_engine.files() {
+            let Some(bytes) = file_engine.read_file(&file_name).await else {
+                continue;
+            };
+            upload_error.set(upload_animal_photo(id, file_name, bytes).await.is_err());
+        }
+        photos.restart();
+    };
+
+    rsx! {
+        div { class: "photos",
+            for photo in photos.cloned().unwrap_or_default().iter() {
+                a { key: "{photo.id}", href: photo_url(&photo.url), target: "_blank",
+                    img { class: "photo-thumbnail", src: photo_url(photo.thumbnail_url(256)), alt: "" }
+                }
+            }
+        }
+        div { class: "form",
+            label { class:"form-label", r#for: "photo", "Add Photo" }
+            input { class:"form-item", type: "file", accept: "image/jpeg,image/png,image/webp",
+                multiple: true, name: "photo", id: "photo", onchange: upload
+            }
+            if upload_error() {
+                ul { class: "error",
+                    li { class: "error-item", "Unable to upload photo" }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn AnimalTags(id: i64) -> Element {
+    let mut tags =
+        use_resource(move || async move { fetch_animal_tags(id).await.unwrap_or_default() });
+    let mut tag_input = use_signal(|| String::new());
+    let suggestions = use_resource(move || async move {
+        let prefix = tag_input().trim().to_string();
+        if prefix.is_empty() {
+            return vec![];
+        }
+        search_tags(prefix).await.unwrap_or_default()
+    });
+    let mut tag_error = use_signal(|| Option::<TagNameError>::None);
+    let mut request_error = use_signal(|| false);
+
+    let attach = move |e: Event<FormData>| {
+        e.prevent_default();
+        async move {
+            match TagName::parse(tag_input().trim().to_string()) {
+                Ok(name) => {
+                    tag_error.reset();
+                    request_error.set(
+                        attach_animal_tag(id, name.as_str().to_string())
+                            .await
+                            .is_err(),
+                    );
+                    tag_input.set(String::new());
+                    tags.restart();
+                }
+                Err(error) => tag_error.set(Some(error)),
+            }
+        }
+    };
+
+    rsx! {
+        div { class: "tags",
+            for tag in tags.cloned().unwrap_or_default().into_iter() {
+                span { class: "tag", key: "{tag.id}",
+                    "{tag.name}"
+                    button { class: "tag-remove", type: "button", title: "Remove tag",
+                        onclick: move |_| async move {
+                            request_error.set(detach_animal_tag(id, tag.id).await.is_err());
+                            tags.restart();
+                        },
+                        "×"
+                    }
+                }
+            }
+        }
+        form { class: "form", onsubmit: attach,
+            label { class:"form-label", r#for: "tag", "Add Tag" }
+            input { class:"form-item", type: "text", placeholder: "Exhibit, diet, ...",
+                name: "tag", id: "tag", list: "tag-suggestions", value: tag_input(),
+                oninput: move |e| tag_input.set(e.value())
+            }
+            datalist { id: "tag-suggestions",
+                for tag in suggestions.cloned().unwrap_or_default().iter() {
+                    option { key: "{tag.id}", value: "{tag.name}" }
+                }
+            }
+            if let Some(TagNameError(msgs)) = tag_error() {
+                ErrorMessage { msgs }
+            }
+            if request_error() {
+                ul { class: "error",
+                    li { class: "error-item", "Unable to update tags" }
+                }
+            }
+            button { class: "btn btn-skyblue", type: "submit", "Add Tag" }
+        }
+    }
+}
+
+#[component]
+pub fn AnimalTranslations(id: i64) -> Element {
+    let mut translations =
+        use_resource(
+            move || async move { fetch_animal_translations(id).await.unwrap_or_default() },
+        );
+    let mut active = use_signal(|| TRANSLATION_LOCALES[0].to_string());
+    let rules = use_context::<Signal<AnimalRules>>();
+    let mut translation_input = use_signal(|| AnimalObject::default());
+    let mut translation_error = use_signal(|| Option::<AnimalValidationError>::None);
+    let mut request_error = use_signal(|| false);
+
+    // Load the translation of the active tab, when switching tabs or once saved.
+    use_effect(move || {
+        let translation = translations
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .find(|translation| translation.locale == active());
+        translation_input.set(AnimalObject {
+            species: translation
+                .as_ref()
+                .map(|t| t.species.clone())
+                .unwrap_or_default(),
+            description: translation.map(|t| t.description).unwrap_or_default(),
+            ..AnimalObject::default()
+        });
+        translation_error.reset();
+    });
+
+    let save = move |e: Event<FormData>| {
+        e.prevent_default();
+        async move {
+            match translation_input.cloned().validate(&rules()) {
+                Ok(validated) => {
+                    translation_error.reset();
+                    request_error.set(
+                        save_animal_translation(id, active(), validated.into())
+                            .await
+                            .is_err(),
+                    );
+                    translations.restart();
+                }
+                Err(error) => translation_error.set(Some(error)),
+            }
+        }
+    };
+
+    let remove = move |_| async move {
+        request_error.set(delete_animal_translation(id, active()).await.is_err());
+        translations.restart();
+    };
+
+    let translations_list = translations.cloned().unwrap_or_default();
+    let mut locales: Vec<String> = TRANSLATION_LOCALES.map(|locale| locale.to_string()).into();
+    for translation in translations_list.iter() {
+        if !locales.contains(&translation.locale) {
+            locales.push(translation.locale.clone());
+        }
+    }
+    let is_translated = translations_list
+        .iter()
+        .any(|translation| translation.locale == active());
+    let translation_value = translation_input.cloned();
+    let translation_error_clone = translation_error.cloned().unwrap_or_default();
+
+    rsx! {
+        div { class: "tabs",
+            for locale in locales.into_iter() {
+                button { key: "{locale}", type: "button",
+                    class: if locale == active() { "tab tab-active" } else { "tab" },
+                    onclick: {
+                        let locale = locale.clone();
+                        move |_| active.set(locale.clone())
+                    },
+                    "{locale}"
+                }
+            }
+        }
+        form { class: "form", onsubmit: save,
+            label { class:"form-label", r#for: "translation-species", "Species ({active})" }
+            input { class:"form-item", type: "text", placeholder: "Species",
+                name: "translation-species", id: "translation-species",
+                value: translation_value.species,
+                oninput: move |e| translation_input.species(e.value())
+            }
+            if let Err(SpeciesError(msgs)) = translation_error_clone.species {
+                ErrorMessage { msgs }
+            }
+            label { class:"form-label", r#for: "translation-description", "Description ({active})" }
+            input { class:"form-item", type: "text", placeholder: "Description",
+                name: "translation-description", id: "translation-description",
+                value: translation_value.description,
+                oninput: move |e| translation_input.description(e.value())
+            }
+            if let Err(DescriptionError(msgs)) = translation_error_clone.description {
+                ErrorMessage { msgs }
+            }
+            if request_error() {
+                ul { class: "error",
+                    li { class: "error-item", "Unable to update translation" }
+                }
+            }
+            button { class: "btn btn-skyblue mr-1", type: "submit", "Save Translation" }
+            if is_translated {
+                button { class: "btn btn-rubyred", type: "button", onclick: remove, "Remove Translation" }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn ConservationBadge(code: String) -> Element {
+    let i18n = i18n();
+    let status = ConservationStatus::parse(&code).unwrap_or_default();
+    let label = i18n.message(status.label());
+    let class = format!("badge badge-{}", status.code().to_lowercase());
+    rsx! {
+        span { class: class, title: "{label}", "{status.code()}" }
+    }
+}
+
 #[component]
 pub fn ErrorMessage(msgs: ValidateErrorStore) -> Element {
     let i18n = i18n();
@@ -229,8 +524,8 @@ pub fn ErrorMessage(msgs: ValidateErrorStore) -> Element {
 
 #[component]
 pub fn AnimalFormBody(
-    animal_value: AnimalModel,
-    mut animal_input: Signal<AnimalModel>,
+    animal_value: AnimalObject,
+    mut animal_input: Signal<AnimalObject>,
     animal_validation_error: AnimalValidationError,
 ) -> Element {
     rsx! {
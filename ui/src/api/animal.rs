@@ -1,68 +1,146 @@
-use crate::api::{ApiClientError, get_client, get_url};
-use crate::model::animal::{AnimalAddUpdateModel, AnimalModel};
+use crate::api::client::{
+    AnimalAddUpdateObject, AnimalErrorObject, AnimalObject, ConservationStatus,
+    ValidationErrorObject,
+};
+use crate::api::{ApiClientError, api, send};
+use cjtoolkit_structured_validator::common::locale::{
+    LocaleData, LocaleMessage, LocaleValue, ValidateErrorCollector,
+};
+use cjtoolkit_structured_validator::common::validation_check::ValidationCheck;
+use cjtoolkit_structured_validator::types::description::{Description, DescriptionError};
 use error_stack::{Report, ResultExt};
+use reqwest::{Response, StatusCode};
+use serde_json::Value;
+use shared::validation::models::animal::AnimalValidationError;
+use shared::validation::types::species::{Species, SpeciesError};
 
-pub fn default_animals() -> Vec<AnimalModel> {
-    let mut v: Vec<AnimalModel> = vec![];
-    v.push(AnimalModel {
+pub fn default_animals() -> Vec<AnimalObject> {
+    let mut v: Vec<AnimalObject> = vec![];
+    v.push(AnimalObject {
         id: 0,
         species: "Lion".to_string(),
         description: "King of the Jungle".to_string(),
+        taxon_id: None,
+        conservation_status: ConservationStatus::Vu,
     });
     v
 }
 
-pub async fn fetch_all_animals() -> Result<Vec<AnimalModel>, Report<ApiClientError>> {
-    let client = get_client();
-    let req = client
-        .get(format!("{}/animal", get_url()))
-        .build()
+pub async fn fetch_all_animals() -> Result<Vec<AnimalObject>, Report<ApiClientError>> {
+    let res = api()
+        .get_animal(&[], &[], None, None, None, None)
+        .send()
+        .await
         .change_context(ApiClientError)?;
-
-    let res = client.execute(req).await.change_context(ApiClientError)?;
     Ok(res
-        .json::<Vec<AnimalModel>>()
+        .json::<Vec<AnimalObject>>()
         .await
         .change_context(ApiClientError)?)
 }
 
-pub async fn fetch_animal_by_id(id: i64) -> Result<AnimalModel, Report<ApiClientError>> {
-    let client = get_client();
-    let req = client
-        .get(format!("{}/animal/fetch/{}", get_url(), id))
-        .build()
+pub async fn fetch_animal_by_id(id: i64) -> Result<AnimalObject, Report<ApiClientError>> {
+    let res = api()
+        .get_animal_fetch(id as u64, None)
+        .send()
+        .await
         .change_context(ApiClientError)?;
-
-    let res = client.execute(req).await.change_context(ApiClientError)?;
     Ok(res
-        .json::<AnimalModel>()
+        .json::<AnimalObject>()
         .await
         .change_context(ApiClientError)?)
 }
 
-pub async fn add_animal(animal: AnimalAddUpdateModel) -> Result<(), Report<ApiClientError>> {
-    let client = get_client();
-    let req = client
-        .post(format!("{}/animal/add", get_url()))
-        .json(&animal)
-        .build()
+/// A message the server reported by its key and arguments, translated again like the errors
+/// found before sending. The message the server sent is kept for keys the bundle lacks.
+struct ReportedMessage {
+    key: String,
+    args: Vec<(String, LocaleValue)>,
+}
+
+impl LocaleMessage for ReportedMessage {
+    fn get_locale_data(&self) -> LocaleData {
+        LocaleData::new_with_vec(&self.key, self.args.clone())
+    }
+}
+
+fn locale_value(value: &Value) -> Option<LocaleValue> {
+    match value {
+        Value::String(string) => Some(LocaleValue::String(string.clone())),
+        Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+            (Some(unsigned), _) => Some(LocaleValue::Uint(unsigned as usize)),
+            (None, Some(signed)) => Some(LocaleValue::Int(signed as isize)),
+            (None, None) => number.as_f64().map(LocaleValue::Float),
+        },
+        _ => None,
+    }
+}
+
+/// The errors the server reported for `field`.
+fn reported<E: ValidationCheck>(errors: &[ValidationErrorObject], field: &str) -> Result<(), E> {
+    let mut msgs = ValidateErrorCollector::new();
+    for error in errors.iter().filter(|error| error.field == field) {
+        let args = error
+            .args
+            .iter()
+            .filter_map(|(name, value)| Some((name.clone(), locale_value(value)?)))
+            .collect();
+        msgs.push((
+            error.message.clone(),
+            Box::new(ReportedMessage {
+                key: error.code.clone(),
+                args,
+            }),
+        ));
+    }
+    E::validate_check(msgs)
+}
+
+/// Errors of the fields on the form from a 422, or a 409 from the unique index catching a
+/// race. Any other failure, or errors of fields the form does not have, fail the request.
+async fn rejected_fields(
+    res: Response,
+) -> Result<Result<(), AnimalValidationError>, Report<ApiClientError>> {
+    let status = res.status();
+    if status.is_success() {
+        return Ok(Ok(()));
+    }
+    if status != StatusCode::UNPROCESSABLE_ENTITY && status != StatusCode::CONFLICT {
+        return Err(Report::new(ApiClientError)
+            .attach_printable(format!("Animal not saved, status {}", status)));
+    }
+
+    let AnimalErrorObject { errors, .. } = res
+        .json::<AnimalErrorObject>()
+        .await
         .change_context(ApiClientError)?;
+    let error = AnimalValidationError {
+        species: reported::<SpeciesError>(&errors, "species").map(|_| Species::default()),
+        description: reported::<DescriptionError>(&errors, "description")
+            .map(|_| Description::default()),
+    };
+    if error.species.is_ok() && error.description.is_ok() {
+        let fields: Vec<&str> = errors.iter().map(|error| error.field.as_str()).collect();
+        return Err(Report::new(ApiClientError)
+            .attach_printable(format!("Animal not saved, errors in {:?}", fields)));
+    }
+    Ok(Err(error))
+}
 
-    _ = client.execute(req).await.change_context(ApiClientError)?;
-    Ok(())
+pub async fn add_animal(
+    animal: AnimalAddUpdateObject,
+) -> Result<Result<(), AnimalValidationError>, Report<ApiClientError>> {
+    let res = send(api().post_animal_add(&animal))
+        .await
+        .change_context(ApiClientError)?;
+    rejected_fields(res).await
 }
 
 pub async fn edit_animal(
     id: i64,
-    animal: AnimalAddUpdateModel,
-) -> Result<(), Report<ApiClientError>> {
-    let client = get_client();
-    let req = client
-        .patch(format!("{}/animal/update/{}", get_url(), id))
-        .json(&animal)
-        .build()
+    animal: AnimalAddUpdateObject,
+) -> Result<Result<(), AnimalValidationError>, Report<ApiClientError>> {
+    let res = send(api().patch_animal_update(id as u64, &animal))
+        .await
         .change_context(ApiClientError)?;
-
-    _ = client.execute(req).await.change_context(ApiClientError)?;
-    Ok(())
+    rejected_fields(res).await
 }
@@ -0,0 +1,10 @@
+//! Types and client generated from `openapi.json` by the build script.
+
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Key the server processes a request once for, however many times it is sent.
+fn idempotency_key() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+include!(concat!(env!("OUT_DIR"), "/api_client.rs"));
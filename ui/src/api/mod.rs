@@ -1,6 +1,13 @@
 pub mod animal;
+pub mod client;
+pub mod photo;
+pub mod tag;
+pub mod taxonomy;
+pub mod translation;
+pub mod validation;
 
-use reqwest::Client;
+use crate::api::client::ApiClient;
+use reqwest::{Client, RequestBuilder, Response};
 use std::sync::OnceLock;
 use thiserror::Error;
 
@@ -15,6 +22,29 @@ fn get_client() -> Client {
     client.clone()
 }
 
+fn api() -> ApiClient {
+    ApiClient::new(get_client(), get_url())
+}
+
+const SEND_ATTEMPTS: usize = 3;
+
+/// Send the request, sending it again as it is, `Idempotency-Key` included, when it fails
+/// to reach the server or times out. Requests whose body cannot be cloned, such as uploads,
+/// are sent once.
+async fn send(mut request: RequestBuilder) -> reqwest::Result<Response> {
+    for _ in 1..SEND_ATTEMPTS {
+        let retry = match request.try_clone() {
+            Some(retry) => retry,
+            None => break,
+        };
+        match request.send().await {
+            Err(err) if err.is_request() || err.is_timeout() => request = retry,
+            result => return result,
+        }
+    }
+    request.send().await
+}
+
 #[derive(Debug, Error)]
 #[error("Api Client Error")]
 pub struct ApiClientError;
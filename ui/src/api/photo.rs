@@ -0,0 +1,41 @@
+use crate::api::client::AnimalPhotoObject;
+use crate::api::{ApiClientError, api, get_url};
+use error_stack::{Report, ResultExt};
+use reqwest::multipart::{Form, Part};
+
+/// Absolute url of a photo or thumbnail url returned by the API.
+pub fn photo_url(url: &str) -> String {
+    format!("{}{}", get_url(), url)
+}
+
+pub async fn fetch_animal_photos(
+    id: i64,
+) -> Result<Vec<AnimalPhotoObject>, Report<ApiClientError>> {
+    let res = api()
+        .get_animal_photos(id as u64)
+        .send()
+        .await
+        .change_context(ApiClientError)?;
+    Ok(res
+        .json::<Vec<AnimalPhotoObject>>()
+        .await
+        .change_context(ApiClientError)?)
+}
+
+pub async fn upload_animal_photo(
+    id: i64,
+    file_name: String,
+    bytes: Vec<u8>,
+) -> Result<AnimalPhotoObject, Report<ApiClientError>> {
+    let form = Form::new().part("file", Part::bytes(bytes).file_name(file_name));
+    let res = api()
+        .post_animal_photos(id as u64, form)
+        .send()
+        .await
+        .and_then(|res| res.error_for_status())
+        .change_context(ApiClientError)?;
+    Ok(res
+        .json::<AnimalPhotoObject>()
+        .await
+        .change_context(ApiClientError)?)
+}
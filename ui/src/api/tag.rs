@@ -0,0 +1,57 @@
+use crate::api::client::{TagNameObject, TagObject};
+use crate::api::{ApiClientError, api, send};
+use error_stack::{Report, ResultExt};
+
+pub async fn search_tags(prefix: String) -> Result<Vec<TagObject>, Report<ApiClientError>> {
+    let res = api()
+        .get_tag(Some(&prefix))
+        .send()
+        .await
+        .change_context(ApiClientError)?;
+    Ok(res
+        .json::<Vec<TagObject>>()
+        .await
+        .change_context(ApiClientError)?)
+}
+
+pub async fn fetch_animal_tags(id: i64) -> Result<Vec<TagObject>, Report<ApiClientError>> {
+    let res = api()
+        .get_animal_tags(id as u64)
+        .send()
+        .await
+        .change_context(ApiClientError)?;
+    Ok(res
+        .json::<Vec<TagObject>>()
+        .await
+        .change_context(ApiClientError)?)
+}
+
+pub async fn attach_animal_tag(
+    id: i64,
+    name: String,
+) -> Result<Vec<TagObject>, Report<ApiClientError>> {
+    let res = send(api().post_animal_tags(id as u64, &TagNameObject { name }))
+        .await
+        .and_then(|res| res.error_for_status())
+        .change_context(ApiClientError)?;
+    Ok(res
+        .json::<Vec<TagObject>>()
+        .await
+        .change_context(ApiClientError)?)
+}
+
+pub async fn detach_animal_tag(
+    id: i64,
+    tag_id: i64,
+) -> Result<Vec<TagObject>, Report<ApiClientError>> {
+    let res = api()
+        .delete_animal_tags(id as u64, tag_id as u64)
+        .send()
+        .await
+        .and_then(|res| res.error_for_status())
+        .change_context(ApiClientError)?;
+    Ok(res
+        .json::<Vec<TagObject>>()
+        .await
+        .change_context(ApiClientError)?)
+}
@@ -0,0 +1,27 @@
+use crate::api::client::{AnimalObject, TaxonObject};
+use crate::api::{ApiClientError, api};
+use error_stack::{Report, ResultExt};
+
+pub async fn fetch_all_taxa() -> Result<Vec<TaxonObject>, Report<ApiClientError>> {
+    let res = api()
+        .get_taxonomy()
+        .send()
+        .await
+        .change_context(ApiClientError)?;
+    Ok(res
+        .json::<Vec<TaxonObject>>()
+        .await
+        .change_context(ApiClientError)?)
+}
+
+pub async fn fetch_taxon_animals(id: i64) -> Result<Vec<AnimalObject>, Report<ApiClientError>> {
+    let res = api()
+        .get_taxonomy_animals(id as u64)
+        .send()
+        .await
+        .change_context(ApiClientError)?;
+    Ok(res
+        .json::<Vec<AnimalObject>>()
+        .await
+        .change_context(ApiClientError)?)
+}
@@ -0,0 +1,47 @@
+use crate::api::client::{AnimalTranslationObject, AnimalTranslationSaveObject};
+use crate::api::{ApiClientError, api};
+use error_stack::{Report, ResultExt};
+
+pub async fn fetch_animal_translations(
+    id: i64,
+) -> Result<Vec<AnimalTranslationObject>, Report<ApiClientError>> {
+    let res = api()
+        .get_animal_translations(id as u64)
+        .send()
+        .await
+        .change_context(ApiClientError)?;
+    Ok(res
+        .json::<Vec<AnimalTranslationObject>>()
+        .await
+        .change_context(ApiClientError)?)
+}
+
+pub async fn save_animal_translation(
+    id: i64,
+    locale: String,
+    translation: AnimalTranslationSaveObject,
+) -> Result<AnimalTranslationObject, Report<ApiClientError>> {
+    let res = api()
+        .put_animal_translations(id as u64, &locale, &translation)
+        .send()
+        .await
+        .and_then(|res| res.error_for_status())
+        .change_context(ApiClientError)?;
+    Ok(res
+        .json::<AnimalTranslationObject>()
+        .await
+        .change_context(ApiClientError)?)
+}
+
+pub async fn delete_animal_translation(
+    id: i64,
+    locale: String,
+) -> Result<(), Report<ApiClientError>> {
+    api()
+        .delete_animal_translations(id as u64, &locale)
+        .send()
+        .await
+        .and_then(|res| res.error_for_status())
+        .change_context(ApiClientError)?;
+    Ok(())
+}
@@ -0,0 +1,15 @@
+use crate::api::client::ValidationRulesObject;
+use crate::api::{ApiClientError, api};
+use error_stack::{Report, ResultExt};
+
+pub async fn fetch_validation_rules() -> Result<ValidationRulesObject, Report<ApiClientError>> {
+    let res = api()
+        .get_validation_rules()
+        .send()
+        .await
+        .change_context(ApiClientError)?;
+    Ok(res
+        .json::<ValidationRulesObject>()
+        .await
+        .change_context(ApiClientError)?)
+}
@@ -5,6 +5,7 @@ pub mod api;
 pub(crate) mod common;
 pub mod ext;
 pub mod model;
+mod taxonomy;
 
 pub use animal::Animal;
 
@@ -1,22 +1,17 @@
+use crate::api::client::{AnimalAddUpdateObject, AnimalObject};
 use dioxus::prelude::{Signal, WritableExt};
-use serde::{Deserialize, Serialize};
 use shared::validation::models::animal::{AnimalValidated, AnimalValidationError};
+use shared::validation::rules::AnimalRules;
 
-#[derive(Debug, Clone, Deserialize, PartialEq, Default)]
-pub struct AnimalModel {
-    pub id: i64,
-    pub species: String,
-    pub description: String,
-}
-
-impl AnimalModel {
-    pub fn validate(&self) -> Result<AnimalValidated, AnimalValidationError> {
-        AnimalValidated::parse(self.species.clone(), self.description.clone())
+impl AnimalObject {
+    /// Validate against the rules published by the server, see [`UiApp`](crate::UiApp).
+    pub fn validate(&self, rules: &AnimalRules) -> Result<AnimalValidated, AnimalValidationError> {
+        AnimalValidated::parse_custom(self.species.clone(), self.description.clone(), rules)
     }
 }
 
-impl From<(&AnimalValidationError, &AnimalModel)> for AnimalModel {
-    fn from((error, model): (&AnimalValidationError, &AnimalModel)) -> Self {
+impl From<(&AnimalValidationError, &AnimalObject)> for AnimalObject {
+    fn from((error, model): (&AnimalValidationError, &AnimalObject)) -> Self {
         Self {
             id: 0,
             species: error
@@ -29,16 +24,18 @@ impl From<(&AnimalValidationError, &AnimalModel)> for AnimalModel {
                 .clone()
                 .map(|s| s.as_str().to_string())
                 .unwrap_or(model.description.clone()),
+            taxon_id: model.taxon_id,
+            conservation_status: model.conservation_status,
         }
     }
 }
 
-pub trait AnimalModelSignal {
+pub trait AnimalObjectSignal {
     fn species(&mut self, species: String);
     fn description(&mut self, description: String);
 }
 
-impl AnimalModelSignal for Signal<AnimalModel> {
+impl AnimalObjectSignal for Signal<AnimalObject> {
     fn species(&mut self, species: String) {
         let mut signal = self.write();
         signal.species = species;
@@ -50,17 +47,12 @@ impl AnimalModelSignal for Signal<AnimalModel> {
     }
 }
 
-#[derive(Debug, Serialize)]
-pub struct AnimalAddUpdateModel {
-    pub species: String,
-    pub description: String,
-}
-
-impl From<AnimalValidated> for AnimalAddUpdateModel {
+impl From<AnimalValidated> for AnimalAddUpdateObject {
     fn from(value: AnimalValidated) -> Self {
         Self {
             species: value.species.as_str().to_string(),
             description: value.description.as_str().to_string(),
+            ..Self::default()
         }
     }
 }
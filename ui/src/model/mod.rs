@@ -1 +1,6 @@
+//! Behaviour the UI adds to the types generated from the spec, see [`crate::api::client`].
+
 pub mod animal;
+pub mod photo;
+pub mod translation;
+pub mod validation;
@@ -0,0 +1,14 @@
+use crate::api::client::AnimalPhotoObject;
+
+impl AnimalPhotoObject {
+    /// Url of the smallest thumbnail at least `size` pixels on its longest edge, falling back
+    /// to the largest thumbnail, then the photo itself.
+    pub fn thumbnail_url(&self, size: u32) -> &str {
+        self.thumbnails
+            .iter()
+            .find(|thumbnail| thumbnail.size >= size)
+            .or(self.thumbnails.last())
+            .map(|thumbnail| thumbnail.url.as_str())
+            .unwrap_or(self.url.as_str())
+    }
+}
@@ -0,0 +1,11 @@
+use crate::api::client::AnimalTranslationSaveObject;
+use shared::validation::models::animal::AnimalValidated;
+
+impl From<AnimalValidated> for AnimalTranslationSaveObject {
+    fn from(value: AnimalValidated) -> Self {
+        Self {
+            species: value.species.as_str().to_string(),
+            description: value.description.as_str().to_string(),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use crate::api::client::{FieldRulesObject, ValidationRulesObject};
+use shared::validation::rules::{AnimalRules, FieldRules};
+
+impl From<FieldRulesObject> for FieldRules {
+    fn from(object: FieldRulesObject) -> Self {
+        Self {
+            is_mandatory: object.required,
+            min_length: object.min_length.map(|length| length as usize),
+            max_length: object.max_length.map(|length| length as usize),
+        }
+    }
+}
+
+impl From<ValidationRulesObject> for AnimalRules {
+    fn from(object: ValidationRulesObject) -> Self {
+        Self {
+            species: object.species.into(),
+            description: object.description.into(),
+        }
+    }
+}
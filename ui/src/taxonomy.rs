@@ -0,0 +1,87 @@
+use crate::animal::Route;
+use crate::api::client::TaxonObject;
+use crate::api::taxonomy::{fetch_all_taxa, fetch_taxon_animals};
+use dioxus::document::Title;
+use dioxus::prelude::*;
+
+#[component]
+pub fn Taxonomy() -> Element {
+    let taxa = use_resource(|| async move {
+        fetch_all_taxa().await.unwrap_or_else(|_| {
+            navigator().push(Route::ErrorPage {});
+            vec![]
+        })
+    });
+    let selected = use_signal(|| Option::<TaxonObject>::None);
+    let animals = use_resource(move || async move {
+        match selected() {
+            Some(taxon) => fetch_taxon_animals(taxon.id).await.unwrap_or_default(),
+            None => vec![],
+        }
+    });
+
+    let taxa = taxa.cloned().unwrap_or_default();
+
+    rsx! {
+        Title { "Taxonomy" }
+        h1 { "Taxonomy" }
+        div { class: "taxonomy",
+            ul { class: "taxon-tree",
+                for root in taxa.iter().filter(|taxon| taxon.parent_id.is_none()) {
+                    TaxonNode { key: "{root.id}", taxon: root.clone(), taxa: taxa.clone(), selected }
+                }
+            }
+            div { class: "animals taxon-animals",
+                if let Some(taxon) = selected() {
+                    h2 { "{taxon.name}" }
+                    for animal in animals.cloned().unwrap_or_default().iter() {
+                        div { class: "animal-item", key: "{animal.id}",
+                            span { class: "animal-other", "{animal.species}" }
+                            Link { class: "animal-id btn btn-skyblue",
+                                to: Route::EditAnimal { id: animal.id }, "Edit" }
+                        }
+                    }
+                }
+            }
+        }
+        Link { class: "btn btn-skyblue inline-block", to: Route::Animal { }, "Back to Animal" }
+    }
+}
+
+#[component]
+fn TaxonNode(
+    taxon: TaxonObject,
+    taxa: Vec<TaxonObject>,
+    mut selected: Signal<Option<TaxonObject>>,
+) -> Element {
+    let mut expanded = use_signal(|| taxon.parent_id.is_none());
+    let children: Vec<TaxonObject> = taxa
+        .iter()
+        .filter(|child| child.parent_id == Some(taxon.id))
+        .cloned()
+        .collect();
+    let is_selected = selected().is_some_and(|current| current.id == taxon.id);
+    let taxon_clone = taxon.clone();
+
+    rsx! {
+        li { class: "taxon",
+            if !children.is_empty() {
+                button { class: "taxon-toggle", onclick: move |_| expanded.toggle(),
+                    if expanded() { "▾" } else { "▸" }
+                }
+            }
+            button { class: if is_selected { "taxon-name taxon-selected" } else { "taxon-name" },
+                onclick: move |_| selected.set(Some(taxon_clone.clone())),
+                span { class: "taxon-rank", "{taxon.rank}" }
+                "{taxon.name}"
+            }
+            if expanded() && !children.is_empty() {
+                ul { class: "taxon-tree",
+                    for child in children.iter() {
+                        TaxonNode { key: "{child.id}", taxon: child.clone(), taxa: taxa.clone(), selected }
+                    }
+                }
+            }
+        }
+    }
+}